@@ -0,0 +1,132 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use crate::period::Period;
+use crate::timezone::TimeZoneSpec;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Calendar in which datetimes are assigned to periods
+///
+/// Datetimes are converted to `tz` before their period is determined, or are left in their own offset if `tz` is `None`.
+/// Each (logical) day begins at `day_start` rather than midnight, (eg: with `day_start` 04:00, 01:30 belongs to the previous day). This applies to periods of a day or longer, and to `Weekday`, but not to `Hour` / `HourOfDay`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Calendar {
+    pub tz: Option<TimeZoneSpec>,
+    pub day_start: NaiveTime,
+}
+
+impl Calendar
+{
+    pub fn new(tz: Option<TimeZoneSpec>) -> Calendar
+    {
+        Calendar { tz, day_start: NaiveTime::MIN, }
+    }
+
+    /// Get a copy of this calendar with days beginning at `day_start`
+    pub fn with_day_start(self, day_start: NaiveTime) -> Calendar
+    {
+        Calendar { day_start, ..self }
+    }
+
+    /// Get `datetime` as a naive local datetime in this calendar
+    pub fn local(&self, datetime: &DateTime<FixedOffset>) -> NaiveDateTime
+    {
+        match &self.tz {
+            Some(tz) => tz.to_local(datetime),
+            None => datetime.naive_local(),
+        }
+    }
+
+    /// Get `datetime` as a naive local datetime in this calendar, shifted back by `day_start`, so that its date is the logical day it belongs to
+    pub fn logical(&self, datetime: &DateTime<FixedOffset>) -> NaiveDateTime
+    {
+        self.local(datetime) - (self.day_start - NaiveTime::MIN)
+    }
+
+    /// Get the logical day `datetime` belongs to in this calendar
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{DateTime, NaiveDate, NaiveTime};
+    /// use datetimescan::calendar::Calendar;
+    /// let calendar = Calendar::default().with_day_start(NaiveTime::from_hms_opt(4, 0, 0).unwrap());
+    /// let datetime = DateTime::parse_from_rfc3339("2023-05-15T01:30:00+10:00").unwrap();
+    /// assert_eq!(calendar.date(&datetime), NaiveDate::from_ymd_opt(2023, 5, 14).unwrap());
+    /// ```
+    pub fn date(&self, datetime: &DateTime<FixedOffset>) -> NaiveDate
+    {
+        self.logical(datetime).date()
+    }
+
+    /// Get the key (as per `Period::key()`) of the period containing `datetime` in this calendar
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::DateTime;
+    /// use datetimescan::calendar::Calendar;
+    /// use datetimescan::period::Period;
+    /// use datetimescan::timezone::TimeZoneSpec;
+    /// let datetime = DateTime::parse_from_rfc3339("2023-05-14T23:30:00+00:00").unwrap();
+    /// assert_eq!(Calendar::default().key(&datetime, &Period::Day), "2023-05-14");
+    /// let calendar = Calendar::new(TimeZoneSpec::parse("Australia/Melbourne"));
+    /// assert_eq!(calendar.key(&datetime, &Period::Day), "2023-05-15");
+    /// ```
+    pub fn key(&self, datetime: &DateTime<FixedOffset>, period: &Period) -> String
+    {
+        match period {
+            Period::Hour | Period::HourOfDay => period.key(&self.local(datetime)),
+            _ => period.key(&self.logical(datetime)),
+        }
+    }
+
+    /// Split the interval from `start` to `end` at the boundaries of `period` in this calendar
+    ///
+    /// # Returns
+    /// A list of `(key, part_start, part_end)` covering the interval, where `key` is the period containing the part (as per `key()`). Empty if `end <= start`.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::DateTime;
+    /// use datetimescan::calendar::Calendar;
+    /// use datetimescan::period::Period;
+    /// let start = DateTime::parse_from_rfc3339("2023-05-14T23:50:00+10:00").unwrap();
+    /// let end = DateTime::parse_from_rfc3339("2023-05-15T00:20:00+10:00").unwrap();
+    /// let parts = Calendar::default().split_at_boundaries(&start, &end, &Period::Day);
+    /// assert_eq!(parts.len(), 2);
+    /// assert_eq!(parts[0].0, "2023-05-14");
+    /// assert_eq!((parts[0].2 - parts[0].1).num_seconds(), 600);
+    /// assert_eq!(parts[1].0, "2023-05-15");
+    /// assert_eq!((parts[1].2 - parts[1].1).num_seconds(), 1200);
+    /// ```
+    pub fn split_at_boundaries(&self, start: &DateTime<FixedOffset>, end: &DateTime<FixedOffset>, period: &Period) -> Vec<(String, DateTime<FixedOffset>, DateTime<FixedOffset>)>
+    {
+        let mut result = vec![];
+        let mut current = *start;
+        while current < *end {
+            let boundary = self.next_boundary(&current, period).unwrap_or(*end);
+            let part_end = if boundary > current { boundary.min(*end) } else { *end };
+            result.push((self.key(&current, period), current, part_end));
+            current = part_end;
+        }
+        result
+    }
+
+    /// Get the first datetime after `datetime` at which a new period begins (`None` for `Period::All`)
+    fn next_boundary(&self, datetime: &DateTime<FixedOffset>, period: &Period) -> Option<DateTime<FixedOffset>>
+    {
+        let (local, shift) = match period {
+            Period::All => return None,
+            Period::Hour | Period::HourOfDay => (self.local(datetime), Duration::zero()),
+            _ => (self.logical(datetime), self.day_start - NaiveTime::MIN),
+        };
+        let next = period.next(&period.truncate(&local)) + shift;
+        let result = match &self.tz {
+            Some(tz) => tz.from_local(&next),
+            None => TimeZoneSpec::Fixed(*datetime.offset()).from_local(&next),
+        };
+        Some(result)
+    }
+}
+
@@ -0,0 +1,73 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use crate::parse_datetime;
+use crate::timezone;
+
+use chrono::{DateTime, FixedOffset, Local};
+use clap::ArgMatches;
+
+/// Source of the 'current time' for every time-relative feature
+///
+/// Anything which would otherwise call `Utc::now()` / `Local::now()` should instead ask a `Clock`, so that results can be made deterministic by providing a `FixedClock`.
+pub trait Clock {
+    /// The current datetime
+    fn now(&self) -> DateTime<FixedOffset>;
+}
+
+/// `Clock` reading the system time, in the local timezone
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        let now = Local::now();
+        now.with_timezone(now.offset())
+    }
+}
+
+/// `Clock` which always returns the same datetime
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::clock::{Clock, FixedClock};
+/// let now = DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap();
+/// let clock = FixedClock::new(now);
+/// assert_eq!(clock.now(), now);
+/// ```
+pub struct FixedClock {
+    now: DateTime<FixedOffset>,
+}
+
+impl FixedClock {
+    pub fn new(now: DateTime<FixedOffset>) -> FixedClock {
+        FixedClock { now, }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<FixedOffset> {
+        self.now
+    }
+}
+
+/// Get the `Clock` specified by `matches`
+///
+/// If 'now' is given (as an argument, or by the environment variable 'DATETIMESCAN_NOW'), a `FixedClock` at that datetime is used, otherwise a `SystemClock`. A 'now' given without an offset is in the timezone given by 'assumetz' (see `timezone::get_assumed_timezone()`)
+///
+/// # Panics
+/// Panics if 'now' is given but is not a valid datetime
+pub fn get_clock(matches: &ArgMatches) -> Box<dyn Clock>
+{
+    match matches.value_of("now") {
+        Some(now_str) => {
+            let now = parse_datetime::parse_datetime_with_tz(now_str, &timezone::get_assumed_timezone(matches))
+                .unwrap_or_else(|| panic!("invalid now=({})", now_str));
+            log::debug!("get_clock(), now=({})", now);
+            Box::new(FixedClock::new(now))
+        },
+        None => Box::new(SystemClock),
+    }
+}
+
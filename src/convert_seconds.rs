@@ -56,3 +56,18 @@ impl ConvertSeconds for u64 {
     }
 }
 
+impl ConvertSeconds for f64 {
+    fn convert_seconds(&self, unit: &str) -> String {
+        match unit.to_lowercase().as_str() {
+            "hms" => (self.round() as i64).convert_seconds(unit),
+            "h" => format!("{:.2}", *self / 3600.0),
+            "m" => format!("{:.2}", *self / 60.0),
+            "s" => {
+                let result = format!("{:.2}", self);
+                result.strip_suffix(".00").map(|x| x.to_string()).unwrap_or(result)
+            },
+            _ => panic!("unit=({}) must equal 'hms' / 'h' / 'm' / 's'", unit),
+        }
+    }
+}
+
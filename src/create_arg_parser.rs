@@ -20,12 +20,78 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
     let filter_start = Arg::with_name("filter_start")
         .long("filter_start")
         .value_name("FILTER_START")
-        .help("Exclude datetimes before");
+        .help("Exclude datetimes before (partial datetimes are expanded to their first second) (also accepts '-7d' / 'today' / 'yesterday' / 'this week' / 'last month' / 'ytd' / etc)")
+        .allow_hyphen_values(true);
 
     let filter_end = Arg::with_name("filter_end")
         .long("filter_end")
         .value_name("FILTER_END")
-        .help("Exclude datetimes before");
+        .help("Exclude datetimes after (partial datetimes are expanded to their last second) (also accepts '-7d' / 'today' / 'yesterday' / 'this week' / 'last month' / 'ytd' / etc)")
+        .allow_hyphen_values(true);
+
+    let last = Arg::with_name("last")
+        .long("last")
+        .value_name("DURATION")
+        .help("Exclude datetimes before the given duration ago (eg: '7d' / '2w' / '12h')")
+        .conflicts_with("filter_start")
+        .takes_value(true);
+
+    let range = Arg::with_name("range")
+        .long("range")
+        .value_name("RANGE")
+        .help("Exclude datetimes outside range, given as 'START..END' / 'START..' / '..END' / 'DATE' (may be repeated, selecting datetimes in any range)")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1);
+
+    let range_bounds = Arg::with_name("range_bounds")
+        .long("range_bounds")
+        .value_name("BOUNDS")
+        .help("Whether the END of a range is included (inclusive) or excluded (half_open)")
+        .takes_value(true)
+        .possible_values(&["inclusive", "half_open"])
+        .default_value("inclusive");
+
+    let time_of_day = Arg::with_name("time_of_day")
+        .long("time_of_day")
+        .value_name("HH:MM-HH:MM")
+        .help("Exclude datetimes outside the given time of day (eg: '09:00-17:30') (wraps past midnight if start > end)")
+        .takes_value(true);
+
+    let weekdays = Arg::with_name("weekdays")
+        .long("weekdays")
+        .value_name("WEEKDAYS")
+        .help("Exclude datetimes not on the given days (eg: 'mon-fri' / 'sat,sun')")
+        .takes_value(true);
+
+    let filter_tz = Arg::with_name("filter_tz")
+        .long("filter_tz")
+        .value_name("TZ")
+        .help("Timezone (IANA name or offset) in which --time_of_day / --weekdays are evaluated (default=offset of each datetime)")
+        .takes_value(true);
+
+    let group_tz = Arg::with_name("group_tz")
+        .long("group_tz")
+        .value_name("TZ")
+        .help("Timezone (IANA name or offset) in which datetimes are grouped by --per and matched by --range (default=offset of each datetime)")
+        .takes_value(true);
+
+    let day_start = Arg::with_name("day_start")
+        .long("day_start")
+        .value_name("HH:MM")
+        .help("Time at which each day begins for --per / --range (eg: 04:00 counts 01:30 toward the previous day) (default=00:00)")
+        .takes_value(true);
+
+    let verbose = Arg::with_name("verbose")
+        .long("verbose")
+        .short("v")
+        .help("Output more detail (eg: the start/end datetimes and lines of each split)")
+        .takes_value(false);
+
+    let recurring_invert = Arg::with_name("recurring_invert")
+        .long("recurring_invert")
+        .help("Invert --time_of_day / --weekdays (eg: to select after-hours datetimes)")
+        .takes_value(false);
 
     let filter_invert = Arg::with_name("filter_invert")
         .long("filter_invert")
@@ -37,11 +103,84 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
         .help("Do not allow out-of-order datetimes in input")
         .takes_value(false);
 
+    let sort = Arg::with_name("sort")
+        .long("sort")
+        .help("Sort datetimes chronologically before analysis (eg: for logs merged from several sources)")
+        .conflicts_with("no_unsorted")
+        .takes_value(false);
+
+    let dedup = Arg::with_name("dedup")
+        .long("dedup")
+        .value_name("DURATION")
+        .help("Remove datetimes identical to, or (given as --dedup=DURATION) within DURATION of, the last datetime kept, (after --sort) before analysis (eg: --dedup / --dedup=5s)")
+        .takes_value(true)
+        .min_values(0)
+        .require_equals(true);
+
     let no_future = Arg::with_name("no_future")
         .long("no_future")
         .help("Do not allow datetimes after the present")
         .takes_value(false);
 
+    let format = Arg::with_name("format")
+        .long("format")
+        .value_name("FORMAT")
+        .help("Output format (text/json/ndjson/csv/tsv)")
+        .takes_value(true)
+        .possible_values(&["text", "json", "ndjson", "csv", "tsv"])
+        .default_value("text");
+
+    let table = Arg::with_name("table")
+        .long("table")
+        .help("Output count/sum per interval as an aligned table, with a percent-of-total column and a total row (text format only)")
+        .takes_value(false);
+
+    let average = Arg::with_name("average")
+        .long("average")
+        .help("Include a row with the average per non-empty interval in --table output")
+        .requires("table")
+        .takes_value(false);
+
+    let color = Arg::with_name("color")
+        .long("color")
+        .value_name("WHEN")
+        .help("Colour --table output: when writing to a terminal (unless NO_COLOR is set), always, or never")
+        .takes_value(true)
+        .possible_values(&["auto", "always", "never"])
+        .default_value("auto");
+
+    let chart = Arg::with_name("chart")
+        .long("chart")
+        .help("Output count/sum per interval as horizontal bars, (and deltas as a log2 histogram) (text format only)")
+        .conflicts_with("table")
+        .takes_value(false);
+
+    let ascii = Arg::with_name("ascii")
+        .long("ascii")
+        .help("Draw --chart bars with '#' instead of Unicode blocks")
+        .requires("chart")
+        .takes_value(false);
+
+    let width = Arg::with_name("width")
+        .long("width")
+        .value_name("COLUMNS")
+        .help("Width of --chart output, in characters (default: the width of the terminal, or COLUMNS, or 80)")
+        .takes_value(true);
+
+    let assumetz = Arg::with_name("assumetz")
+        .long("assumetz")
+        .value_name("TZ")
+        .env("DATETIMESCAN_ASSUMETZ")
+        .help("Timezone (IANA name or offset) assumed for datetimes given without an offset (default=local timezone)")
+        .takes_value(true);
+
+    let now = Arg::with_name("now")
+        .long("now")
+        .value_name("NOW")
+        .env("DATETIMESCAN_NOW")
+        .help("Datetime to use as the present (default=system time)")
+        .takes_value(true);
+
     //  Subcommand arguments:
     let no_locations = Arg::with_name("no_locations")
         .long("no_locations")
@@ -51,16 +190,29 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
     let per_arg = Arg::with_name("per")
         .long("per")
         .value_name("INTERVAL")
-        .help("Count/Sum datetimes per interval (d/m/y/all) (default=all)")
+        .help("Count/Sum datetimes per interval (hour/day/ISO-week/month/quarter/year, or the cyclic weekday/hour_of_day) (default=all)")
         .takes_value(true)
-        .possible_values(&["d", "m", "y", "all"])
+        .possible_values(&["h", "d", "w", "m", "q", "y", "weekday", "hour_of_day", "all"])
         .default_value("all");
 
+    let fill = Arg::with_name("fill")
+        .long("fill")
+        .help("Include intervals without datetimes (as 0), from the first to the last interval, or across --range")
+        .takes_value(false);
+
     let allow_negative = Arg::with_name("allow_negative")
         .long("allow_negative")
-        .help("Set negative deltas to 0")
+        .help("Include negative deltas (alias for --negative break)")
+        .conflicts_with("negative")
         .takes_value(false);
 
+    let negative = Arg::with_name("negative")
+        .long("negative")
+        .value_name("POLICY")
+        .help("Handling of negative deltas (out-of-order datetimes): count as 0, break the split, drop the out-of-order datetime, or sort datetimes first (default=clamp)")
+        .takes_value(true)
+        .possible_values(&["clamp", "break", "drop", "sort"]);
+
     let validator_unsigned = |value: String| -> Result<(), String> {
         match value.parse::<u64>() {
             Ok(_num) => { Ok( () ) },
@@ -68,14 +220,85 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
         }
     };
 
+    let validator_timeout = move |value: String| -> Result<(), String> {
+        if value == "auto" {
+            return Ok(());
+        }
+        validator_unsigned(value)
+    };
+
     let timeout = Arg::with_name("timeout")
         .long("timeout")
         .value_name("TIMEOUT")
-        .help("Max positive delta not considered a split")
+        .help("Max positive delta not considered a split ('auto' to choose from the distribution of deltas, see suggest-timeout)")
         .takes_value(true)
-        .validator(validator_unsigned)
+        .validator(validator_timeout)
         .default_value("300");
 
+    let clip = Arg::with_name("clip")
+        .long("clip")
+        .help("Count only the part of each delta inside --time_of_day / --weekdays (instead of excluding datetimes outside them)")
+        .takes_value(false);
+
+    let apportion = Arg::with_name("apportion")
+        .long("apportion")
+        .help("Find splits on the whole timeline, then divide them at --per boundaries (instead of grouping datetimes before finding splits)")
+        .takes_value(false);
+
+    let model = Arg::with_name("model")
+        .long("model")
+        .value_name("MODEL")
+        .help("Time-accounting model: deltas > timeout are discarded, or capped at the timeout, or each datetime earns --credit, or each split is padded by --padding before and after (default=discard)")
+        .takes_value(true)
+        .possible_values(&["discard", "capped", "credit", "padded"])
+        .conflicts_with("apportion");
+
+    let credit = Arg::with_name("credit")
+        .long("credit")
+        .value_name("DURATION")
+        .help("Time earned by each datetime with --model credit (eg: 5m / 300)")
+        .takes_value(true)
+        .default_value("5m");
+
+    let padding = Arg::with_name("padding")
+        .long("padding")
+        .value_name("DURATION")
+        .help("Time added before and after each split with --model padded (eg: 5m / 300)")
+        .takes_value(true)
+        .default_value("5m");
+
+    let min_gap = Arg::with_name("min_gap")
+        .long("min_gap")
+        .value_name("DURATION")
+        .help("Exclude gaps shorter than duration (eg: 1h / 30m / 3600)")
+        .takes_value(true);
+
+    let top = Arg::with_name("top")
+        .long("top")
+        .value_name("N")
+        .help("Report only the N longest gaps, longest first")
+        .takes_value(true)
+        .validator(validator_unsigned);
+
+    let total = Arg::with_name("total")
+        .long("total")
+        .help("Report the total length of gaps per interval (each gap counted toward the interval it starts in)")
+        .takes_value(false);
+
+    let missing_per = Arg::with_name("per")
+        .long("per")
+        .value_name("INTERVAL")
+        .help("Report missing intervals of day/ISO-week/month/quarter/year")
+        .takes_value(true)
+        .possible_values(&["d", "w", "m", "q", "y"])
+        .default_value("d");
+
+    let exclude_file = Arg::with_name("exclude_file")
+        .long("exclude_file")
+        .value_name("FILE")
+        .help("File listing dates/ranges (one per line, as for --range) expected to be missing (eg: holidays), intervals entirely within which are not reported")
+        .takes_value(true);
+
     let unit = Arg::with_name("unit")
         .long("unit")
         .value_name("UNIT")
@@ -83,6 +306,35 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
         .possible_values(&["s", "m", "h", "hms"])
         .default_value("s");
 
+    let dst_tolerance = Arg::with_name("dst_tolerance")
+        .long("dst_tolerance")
+        .value_name("DURATION")
+        .help("Largest difference from one hour of a backwards jump reported as a DST repeat (eg: 15m / 900)")
+        .takes_value(true)
+        .default_value("15m");
+
+    let min_skew = Arg::with_name("min_skew")
+        .long("min_skew")
+        .value_name("DURATION")
+        .help("Longest backwards jump not reported as clock skew (eg: 1m / 60)")
+        .takes_value(true)
+        .default_value("1m");
+
+    let checks = Arg::with_name("checks")
+        .long("checks")
+        .value_name("CHECKS")
+        .help("Checks to make, comma separated (default=all)")
+        .takes_value(true)
+        .use_delimiter(true)
+        .possible_values(&["unparsable", "future", "unsorted", "duplicate", "gap", "mixed_offset"]);
+
+    let max_gap = Arg::with_name("max_gap")
+        .long("max_gap")
+        .value_name("DURATION")
+        .help("Longest delta between consecutive datetimes not reported as a gap (eg: 1d / 12h / 3600)")
+        .takes_value(true)
+        .default_value("7d");
+
     let parser = App::new("datetimescan")
         .version(env!("CARGO_PKG_VERSION"))
         .about("Utility for finding/analysing datetime strings in input")
@@ -90,9 +342,30 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
         .arg(output_arg.global(true))
         .arg(no_future.global(true))
         .arg(no_unsorted.global(true))
+        .arg(sort.global(true))
+        .arg(dedup.global(true))
         .arg(filter_start.global(true))
         .arg(filter_end.global(true))
+        .arg(last.global(true))
+        .arg(range.global(true))
+        .arg(range_bounds.global(true))
+        .arg(time_of_day.global(true))
+        .arg(weekdays.global(true))
+        .arg(filter_tz.global(true))
+        .arg(recurring_invert.global(true))
+        .arg(group_tz.global(true))
+        .arg(day_start.global(true))
+        .arg(verbose.global(true))
         .arg(filter_invert.global(true))
+        .arg(assumetz.global(true))
+        .arg(now.global(true))
+        .arg(format.global(true))
+        .arg(table.global(true))
+        .arg(average.global(true))
+        .arg(color.global(true))
+        .arg(chart.global(true))
+        .arg(ascii.global(true))
+        .arg(width.global(true))
 
         .subcommand(
             SubCommand::with_name("locate")
@@ -119,12 +392,15 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
             SubCommand::with_name("count")
                 .about("Count datetimes per interval")
                 .arg(per_arg.clone())
+                .arg(fill.clone())
             )
 
         .subcommand(
             SubCommand::with_name("deltas")
                 .about("Report seconds elapsed between each datetime match")
                 .arg(allow_negative.clone())
+                .arg(negative.clone())
+                .arg(unit.clone())
             )
 
         .subcommand(
@@ -133,6 +409,13 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
                 .arg(per_arg.clone())
                 .arg(timeout.clone())
                 .arg(unit.clone())
+                .arg(clip.clone())
+                .arg(apportion.clone())
+                .arg(negative.clone())
+                .arg(model.clone())
+                .arg(credit.clone())
+                .arg(padding.clone())
+                .arg(fill.clone())
             )
 
         .subcommand(
@@ -141,6 +424,62 @@ pub fn create_arg_parser() -> clap::App<'static, 'static>
                 .arg(per_arg.clone())
                 .arg(timeout.clone())
                 .arg(unit.clone())
+                .arg(clip.clone())
+                .arg(apportion.clone())
+                .arg(negative.clone())
+                .arg(model.clone())
+                .arg(credit.clone())
+                .arg(padding.clone())
+                .arg(fill.clone())
+            )
+
+        .subcommand(
+            SubCommand::with_name("gaps")
+                .about("Report idle periods where delta > timeout")
+                .arg(timeout.clone())
+                .arg(min_gap.clone())
+                .arg(top.clone())
+                .arg(total.clone())
+                .arg(per_arg.clone())
+                .arg(unit.clone())
+            )
+
+        .subcommand(
+            SubCommand::with_name("missing")
+                .about("Report intervals without datetimes")
+                .arg(missing_per.clone())
+                .arg(exclude_file.clone())
+            )
+
+        .subcommand(
+            SubCommand::with_name("stats")
+                .about("Report count/min/max/mean/median/stddev/p90/p95/p99 of deltas and of session lengths per interval")
+                .arg(timeout.clone())
+                .arg(per_arg.clone())
+                .arg(unit.clone())
+                .arg(allow_negative.clone())
+                .arg(negative.clone())
+            )
+
+        .subcommand(
+            SubCommand::with_name("suggest-timeout")
+                .about("Suggest a timeout from the natural break between intra-session and inter-session deltas")
+                .arg(unit.clone())
+            )
+
+        .subcommand(
+            SubCommand::with_name("anomalies")
+                .about("Report changes of UTC offset, and backwards jumps (DST repeats / clock skew), between consecutive datetimes")
+                .arg(dst_tolerance.clone())
+                .arg(min_skew.clone())
+                .arg(unit.clone())
+            )
+
+        .subcommand(
+            SubCommand::with_name("validate")
+                .about("Report issues with datetimes in input (unparsable/future/unsorted/duplicate/gap/mixed_offset), with their lines and columns (exits 1 if any are found)")
+                .arg(checks.clone())
+                .arg(max_gap.clone())
             )
 
         .subcommand(
@@ -2,14 +2,13 @@
 //  vim: set tabstop=4 modeline modelines=10:
 //  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
 //  {{{2
-//  Notes:
-//  {{{
-//  2023-07-18T20:16:44AEST should use an enum instead of y/m/d as a string?
-//  }}}
-
 //  Translation of 'tasklogReaderUtil/date_range' into Rust
 
-use chrono::{NaiveDate, Duration, Datelike};
+use crate::calendar::Calendar;
+use crate::period::Period;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Duration, Datelike};
+use std::io::BufRead;
 
 /// Attempts to parse a string into a `NaiveDate` based on its length.
 ///
@@ -50,6 +49,72 @@ pub fn parse_partial_date_str(s: &str) -> Option<NaiveDate> {
     }
 }
 
+/// Attempts to parse a partial datetime string into the first and last second it covers.
+///
+/// In addition to the formats accepted by `parse_partial_date_str` ("YYYY", "YYYY-MM", "YYYY-MM-DD"), accepts hour and minute precision datetimes as "YYYY-MM-DDTHH" / "YYYY-MM-DDTHH:MM" (or with a space instead of 'T').
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use datetimescan::date_range::parse_partial_datetime_bounds;
+/// let (first, last) = parse_partial_datetime_bounds("2023-05").unwrap();
+/// assert_eq!(first, NaiveDate::from_ymd_opt(2023, 5, 1).unwrap().and_hms_opt(0, 0, 0).unwrap());
+/// assert_eq!(last, NaiveDate::from_ymd_opt(2023, 5, 31).unwrap().and_hms_opt(23, 59, 59).unwrap());
+/// let (first, last) = parse_partial_datetime_bounds("2023-05-14T13").unwrap();
+/// assert_eq!(first, NaiveDate::from_ymd_opt(2023, 5, 14).unwrap().and_hms_opt(13, 0, 0).unwrap());
+/// assert_eq!(last, NaiveDate::from_ymd_opt(2023, 5, 14).unwrap().and_hms_opt(13, 59, 59).unwrap());
+/// ```
+///
+/// # Arguments
+/// * `s` - A string slice that holds the partial datetime to parse.
+///
+/// # Returns
+/// * `Some((NaiveDateTime, NaiveDateTime))` the first and last second of the period given by the string.
+/// * `None` if the string could not be parsed or was not in the expected format.
+pub fn parse_partial_datetime_bounds(s: &str) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let s = s.replacen(' ', "T", 1);
+    let (first, next) = match s.len() {
+        4 => {
+            let first = parse_partial_date_str(&s)?;
+            (first.and_time(NaiveTime::MIN), NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)?.and_time(NaiveTime::MIN))
+        },
+        7 => {
+            let first = parse_partial_date_str(&s)?;
+            let next = if first.month() == 12 {
+                NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)?
+            } else {
+                NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)?
+            };
+            (first.and_time(NaiveTime::MIN), next.and_time(NaiveTime::MIN))
+        },
+        10 => {
+            let first = parse_partial_date_str(&s)?.and_time(NaiveTime::MIN);
+            (first, first + Duration::days(1))
+        },
+        13 => {
+            let first = NaiveDateTime::parse_from_str(&format!("{}:00:00", s), "%Y-%m-%dT%H:%M:%S").ok()?;
+            (first, first + Duration::hours(1))
+        },
+        16 => {
+            let first = NaiveDateTime::parse_from_str(&format!("{}:00", s), "%Y-%m-%dT%H:%M:%S").ok()?;
+            (first, first + Duration::minutes(1))
+        },
+        _ => return None,
+    };
+    Some((first, next - Duration::seconds(1)))
+}
+
+/// Attempts to parse a partial date string ("YYYY" / "YYYY-MM" / "YYYY-MM-DD") into the first and last day it covers.
+fn parse_partial_date_bounds(s: &str) -> Option<(NaiveDate, NaiveDate)> {
+    parse_partial_date_str(s)?;
+    let (first, last) = parse_partial_datetime_bounds(s)?;
+    Some((first.date(), last.date()))
+}
+
+/// Range of dates, from `start` to `end` (inclusive)
+///
+/// A range without a start/end bound has `start=NaiveDate::MIN` / `end=NaiveDate::MAX`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DateRange {
     pub start: NaiveDate,
     pub end: NaiveDate,
@@ -76,101 +141,256 @@ impl DateRange
         parsed_dates.sort();
         let first = &parsed_dates[0];
         let last = &parsed_dates[parsed_dates.len()-1];
-        DateRange { start: first.clone(), end: last.clone(), }
+        DateRange { start: *first, end: *last, }
+    }
+
+    /// Parses a range given as 'START..END', 'START..', '..END', or a single 'DATE'
+    ///
+    /// Each of START / END / DATE may be a partial date ('YYYY' / 'YYYY-MM' / 'YYYY-MM-DD'). START is expanded to the first day it covers. If `inclusive`, END is expanded to the last day it covers, otherwise the range ends the day before END begins (half-open). A single DATE is the range of every day it covers.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use datetimescan::date_range::DateRange;
+    /// let range = DateRange::parse("2023-05..2023-07", true).unwrap();
+    /// assert_eq!(range.start, NaiveDate::from_ymd_opt(2023, 5, 1).unwrap());
+    /// assert_eq!(range.end, NaiveDate::from_ymd_opt(2023, 7, 31).unwrap());
+    /// let range = DateRange::parse("2023-05..2023-07", false).unwrap();
+    /// assert_eq!(range.end, NaiveDate::from_ymd_opt(2023, 6, 30).unwrap());
+    /// let range = DateRange::parse("..2023-06-15", true).unwrap();
+    /// assert_eq!(range.start, NaiveDate::MIN);
+    /// ```
+    ///
+    /// # Arguments
+    /// * `s` - The range to parse
+    /// * `inclusive` - Whether END is included in the range
+    ///
+    /// # Returns
+    /// `Some(DateRange)` if `s` could be parsed (and start <= end), otherwise `None`
+    pub fn parse(s: &str, inclusive: bool) -> Option<DateRange>
+    {
+        let s = s.trim();
+        let result = match s.split_once("..") {
+            Some((start_str, end_str)) => {
+                let start = if start_str.is_empty() {
+                    NaiveDate::MIN
+                } else {
+                    parse_partial_date_bounds(start_str)?.0
+                };
+                let end = if end_str.is_empty() {
+                    NaiveDate::MAX
+                } else if inclusive {
+                    parse_partial_date_bounds(end_str)?.1
+                } else {
+                    parse_partial_date_bounds(end_str)?.0.pred_opt()?
+                };
+                DateRange { start, end, }
+            },
+            None => {
+                let (start, end) = parse_partial_date_bounds(s)?;
+                DateRange { start, end, }
+            },
+        };
+        if result.start > result.end {
+            return None;
+        }
+        Some(result)
+    }
+
+    /// Does a given datetime fall between the start/end (inclusive) of the DateRange
+    ///
+    /// The date of `datetime` is taken in its own offset
+    pub fn contains(&self, datetime: &DateTime<FixedOffset>) -> bool
+    {
+        self.contains_in(datetime, &Calendar::default())
+    }
+
+    /// Does a given datetime fall between the start/end (inclusive) of the DateRange, taking its (logical) date in `calendar`
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{DateTime, NaiveTime};
+    /// use datetimescan::calendar::Calendar;
+    /// use datetimescan::date_range::DateRange;
+    /// let date_range = DateRange::new("2023-05-14", "2023-05-14");
+    /// let datetime = DateTime::parse_from_rfc3339("2023-05-15T01:30:00+10:00").unwrap();
+    /// assert!(!date_range.contains(&datetime));
+    /// let calendar = Calendar::default().with_day_start(NaiveTime::from_hms_opt(4, 0, 0).unwrap());
+    /// assert!(date_range.contains_in(&datetime, &calendar));
+    /// ```
+    pub fn contains_in(&self, datetime: &DateTime<FixedOffset>, calendar: &Calendar) -> bool
+    {
+        let date = calendar.date(datetime);
+        date >= self.start && date <= self.end
+    }
+
+    /// Get the range of dates in both `self` and `other` (`None` if they do not overlap)
+    pub fn intersection(&self, other: &DateRange) -> Option<DateRange>
+    {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start > end {
+            return None;
+        }
+        Some(DateRange { start, end, })
     }
 
-    /// Get a vec of all yearly/monthly/daily `NaiveDate`s between start and end dates (inclusive)
+    /// Get the range of dates in either `self` or `other` (`None` if they neither overlap nor are adjacent, since the result would not be a single range)
+    pub fn union(&self, other: &DateRange) -> Option<DateRange>
+    {
+        let (first, second) = if self.start <= other.start { (self, other) } else { (other, self) };
+        if first.end < second.start && first.end.succ_opt() != Some(second.start) {
+            return None;
+        }
+        Some(DateRange { start: first.start, end: first.end.max(second.end), })
+    }
+
+    /// Get a vec of all yearly/quarterly/monthly/weekly/daily `NaiveDate`s between start and end dates (inclusive)
     ///
-    /// For a monthly range, all days=1, and for a yearly range, all months=1 and days=1
+    /// Each date is the first day of its period, (for a monthly range, all days=1, for a quarterly range, all months=1/4/7/10 and days=1, for a yearly range, all months=1 and days=1, and for a weekly range, each date is the Monday starting the ISO week)
     /// 
     /// # Arguments
-    /// * `range_type` - A string specify yearly/monthly/daily (must be y/m/d)
+    /// * `period` - The `Period` to enumerate (must be `Year` / `Quarter` / `Month` / `Week` / `Day`)
     ///
     /// # Panics
-    /// Will panic if `range_type` is not (y/m/d)
+    /// Will panic if `period` is not one of the above
     ///
     /// # Returns
     /// `Vec<NaiveDate>` a vector of datetime objects
-    pub fn get_dates(&self, range_type: &str) -> Vec<NaiveDate> 
+    pub fn get_dates(&self, period: &Period) -> Vec<NaiveDate> 
     {
-        match range_type {
-            "y" | "Y" => self.get_years(),
-            "m" | "M" => self.get_months(),
-            "d" | "D" => self.get_days(),
-            _ => panic!("Invalid range_type=({:?}) for `DateRange` (must be y/m/d)", range_type),
+        match period {
+            Period::Day | Period::Week | Period::Month | Period::Quarter | Period::Year => {
+                self.get_period_starts(period).iter().map(|x| x.date()).collect()
+            },
+            _ => panic!("Invalid period=({}) for `DateRange::get_dates` (must be y/q/m/w/d)", period),
         }
     }
 
-    fn get_days(&self) -> Vec<NaiveDate> 
+    /// Get the keys (as per `Period::key()`) of every period between start and end dates (inclusive)
+    ///
+    /// For the cyclic `Weekday` / `HourOfDay` periods, every weekday (Mon..Sun) / hour (00..23) is given, and for `All`, the single key 'all'
+    /// 
+    /// # Example
+    /// ```
+    /// use datetimescan::date_range::DateRange;
+    /// use datetimescan::period::Period;
+    /// let date_range = DateRange::new("2023-05-30", "2023-06-02");
+    /// assert_eq!(date_range.get_period_keys(&Period::Week), vec!["2023-W22"]);
+    /// assert_eq!(date_range.get_period_keys(&Period::Month), vec!["2023-05", "2023-06"]);
+    /// assert_eq!(date_range.get_period_keys(&Period::Quarter), vec!["2023-Q2"]);
+    /// assert_eq!(date_range.get_period_keys(&Period::Hour).len(), 4 * 24);
+    /// ```
+    pub fn get_period_keys(&self, period: &Period) -> Vec<String>
     {
-        let mut result = Vec::new();
-        let mut date = self.start;
-        while date <= self.end {
-            result.push(date);
-            date += Duration::days(1);
+        match period {
+            Period::Weekday | Period::HourOfDay => period.cyclic_keys(),
+            Period::All => vec![period.key(&NaiveDateTime::MIN)],
+            _ => self.get_period_starts(period).iter().map(|x| period.key(x)).collect(),
         }
-        result
     }
 
-    fn get_months(&self) -> Vec<NaiveDate> 
+    /// Get the first (naive) datetime of every non-cyclic period overlapping the range
+    fn get_period_starts(&self, period: &Period) -> Vec<NaiveDateTime>
     {
+        let end = self.end.and_time(NaiveTime::MIN) + Duration::days(1);
         let mut result = Vec::new();
-        let mut date = self.start;
-        while date <= self.end {
-            result.push(NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap());
-            if date.month() == 12 {
-                date = NaiveDate::from_ymd_opt(date.year() + 1, 1, 1).unwrap();
-            } else {
-                date = NaiveDate::from_ymd_opt(date.year(), date.month() + 1, 1).unwrap();
-            }
+        let mut start = period.truncate(&self.start.and_time(NaiveTime::MIN));
+        while start < end {
+            result.push(start);
+            start = period.next(&start);
         }
         result
     }
 
-    fn get_years(&self) -> Vec<NaiveDate> 
+    /// Does every date in the DateRange fall within at least one of `ranges`
+    ///
+    /// # Example
+    /// ```
+    /// use datetimescan::date_range::DateRange;
+    /// let week = DateRange::new("2023-05-15", "2023-05-21");
+    /// let excluded = vec![DateRange::new("2023-05-13", "2023-05-18"), DateRange::new("2023-05-19", "2023-05-21")];
+    /// assert!(week.is_covered_by(&excluded));
+    /// assert!(!week.is_covered_by(&excluded[..1]));
+    /// ```
+    pub fn is_covered_by(&self, ranges: &[DateRange]) -> bool
     {
-        let mut result = Vec::new();
-        for y in self.start.year()..=self.end.year() {
-            result.push(NaiveDate::from_ymd_opt(y, 1, 1).unwrap());
+        let mut date = self.start;
+        while date <= self.end {
+            match ranges.iter().filter(|x| x.start <= date && date <= x.end).map(|x| x.end).max() {
+                Some(NaiveDate::MAX) => return true,
+                Some(end) => date = end.succ_opt().unwrap(),
+                None => return false,
+            }
         }
-        result
+        true
     }
 
     /// Does a given date (as string) fall between the start/end (inclusive) of the DateRange 
     pub fn is_date_in_range(&self, date_str: &str) -> bool
     {
         let date = parse_partial_date_str(date_str).expect("Invalid date_str for `DateRange::is_date_in_range`");
-        if date >= self.start && date <= self.end {
-            true
-        } else {
-            false
-        }
+        date >= self.start && date <= self.end
     }
 
-    /// Get missing dates from a list of date strings based on the specified date type.
-    pub fn get_missing_dates(search_dates_strs: &[&str], range_type: &str) -> Vec<String>
+    /// Get the keys (as per `Period::key()`) of periods between the first and last of a list of date strings that contain none of them
+    ///
+    /// # Example
+    /// ```
+    /// use datetimescan::date_range::DateRange;
+    /// use datetimescan::period::Period;
+    /// let missing = DateRange::get_missing_dates(&["2023-05-01", "2023-05-20"], &Period::Week);
+    /// assert_eq!(missing, vec!["2023-W19"]);
+    /// ```
+    pub fn get_missing_dates(search_dates_strs: &[&str], period: &Period) -> Vec<String>
     {
         let date_range = DateRange::new_from_str_range(search_dates_strs);
-        let search_dates = search_dates_strs
+        let search_keys = search_dates_strs
             .iter()
             .map(|x| parse_partial_date_str(x)
             .expect("Invalid date_str in search_dates_strs for `DateRange::get_missing_dates`"))
+            .map(|x| period.key(&x.and_time(NaiveTime::MIN)))
             .collect::<Vec<_>>();
-        let range_dates = date_range.get_dates(range_type);
-        let date_format = match range_type {
-            "y" | "Y" => "%Y",
-            "m" | "M" => "%Y-%m",
-            "d" | "D" => "%Y-%m-%d",
-            _ => panic!("Invalid range_type=({:?}) for `DateRange` (must be y/m/d)", range_type),
-        };
-        let mut missing_dates = vec![];
-        for x in range_dates {
-            if !search_dates.contains(&x) {
-                missing_dates.push(x.format(date_format).to_string());
-            }
-        }
-        missing_dates
+        date_range.get_period_keys(period)
+            .into_iter()
+            .filter(|x| !search_keys.contains(x))
+            .collect()
     } 
 
 }
 
+/// Parses a list of ranges, one per line, each as accepted by `DateRange::parse()` (eg: a list of holidays)
+///
+/// Blank lines are ignored, as is anything following a '#'
+///
+/// # Example
+/// ```
+/// use datetimescan::date_range::{parse_date_ranges, DateRange};
+/// let input = "# holidays\n2023-04-07..2023-04-10\n\n2023-04-25  # anzac day\n";
+/// let ranges = parse_date_ranges(input.as_bytes(), true).unwrap();
+/// assert_eq!(ranges, vec![DateRange::new("2023-04-07", "2023-04-10"), DateRange::new("2023-04-25", "2023-04-25")]);
+/// assert_eq!(parse_date_ranges("2023-04-07\nEaster".as_bytes(), true), Err("invalid range=(Easter) on line 2".to_string()));
+/// ```
+///
+/// # Arguments
+/// * `reader` - The input to read ranges from
+/// * `inclusive` - Whether the END of each range is included in it
+///
+/// # Returns
+/// `Ok(Vec<DateRange>)` the ranges, in the order given, or `Err(String)` describing the first line which is not a valid range (or could not be read)
+pub fn parse_date_ranges<R: BufRead>(reader: R, inclusive: bool) -> Result<Vec<DateRange>, String>
+{
+    let mut result = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("failed to read line {} ({})", i+1, e))?;
+        let range_str = line.split('#').next().unwrap().trim();
+        if range_str.is_empty() {
+            continue;
+        }
+        let range = DateRange::parse(range_str, inclusive)
+            .ok_or_else(|| format!("invalid range=({}) on line {}", range_str, i+1))?;
+        result.push(range);
+    }
+    Ok(result)
+}
+
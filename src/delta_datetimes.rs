@@ -3,10 +3,10 @@
 //  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
 //  {{{2
 use chrono::{DateTime, FixedOffset};
+use std::str::FromStr;
 
 //  Notes:
 //  {{{
-//  2023-05-18T21:30:25AEST (do we need to) determine datetimes corresponding to start/end of each split?
 //  2023-05-18T21:31:41AEST splits -> whether to use i64/u64(?)
 //  2023-05-19T22:35:20AEST remove 'current_sum' from `split_deltas()` (how to sum vector and  compare result against 0_u64 as a condition of the if-statement?)
 //  }}}
@@ -16,7 +16,7 @@ use chrono::{DateTime, FixedOffset};
 /// # Arguments
 /// * `datetimes` - A reference to `Vec<DateTime<FixedOffset>>`
 /// * `allow_negatives` - A boolean value indicating whether negative differences 
-///     should be preserved. If `false`, any negative difference is replaced with `0`.
+///   should be preserved. If `false`, any negative difference is replaced with `0`.
 ///
 /// # Returns
 /// A vector of integers representing the difference in seconds between each consecutive 
@@ -83,6 +83,232 @@ pub fn datetime_difference_seconds(dt1: DateTime<FixedOffset>, dt2: DateTime<Fix
 }
 
 
+/// Handling of negative deltas - datetimes earlier than the one before them (out of order)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NegativePolicy {
+    /// Count negative deltas as 0
+    Clamp,
+    /// Keep negative deltas, ending the session (split) at each
+    Break,
+    /// Drop the out-of-order datetimes (the fewest datetimes that leave the rest in order), so each delta is against the last datetime kept
+    Drop,
+    /// Sort datetimes before finding deltas
+    Sort,
+}
+
+impl FromStr for NegativePolicy
+{
+    type Err = String;
+
+    /// Parses a policy given as clamp/break/drop/sort
+    fn from_str(s: &str) -> Result<NegativePolicy, String>
+    {
+        match s.to_ascii_lowercase().as_str() {
+            "clamp" => Ok(NegativePolicy::Clamp),
+            "break" => Ok(NegativePolicy::Break),
+            "drop" => Ok(NegativePolicy::Drop),
+            "sort" => Ok(NegativePolicy::Sort),
+            _ => Err(format!("unsupported negative=({}) (must be clamp/break/drop/sort)", s)),
+        }
+    }
+}
+
+/// Orders items (each with a datetime) as per the `Drop` / `Sort` policies, leaving them unchanged for `Clamp` / `Break`
+///
+/// # Arguments
+/// * `items` - The items, in the order of the input
+/// * `policy` - The policy for negative deltas
+/// * `datetime` - Function giving the datetime of an item
+///
+/// # Returns
+/// The items without out-of-order datetimes (`Drop`, keeping the longest already-ordered subsequence), or sorted by datetime (`Sort`, keeping the order of equal datetimes)
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::{order_datetimes, NegativePolicy};
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:05:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:06:00+00:00").unwrap(),
+/// ];
+/// let result = order_datetimes(datetimes.clone(), &NegativePolicy::Drop, |x| *x);
+/// assert_eq!(result, vec![datetimes[0], datetimes[1], datetimes[3]]);
+/// let result = order_datetimes(datetimes.clone(), &NegativePolicy::Sort, |x| *x);
+/// assert_eq!(result, vec![datetimes[0], datetimes[2], datetimes[1], datetimes[3]]);
+/// ```
+pub fn order_datetimes<T, F>(items: Vec<T>, policy: &NegativePolicy, datetime: F) -> Vec<T>
+    where F: Fn(&T) -> DateTime<FixedOffset>
+{
+    match policy {
+        NegativePolicy::Clamp | NegativePolicy::Break => items,
+        NegativePolicy::Drop => {
+            let ordered = ordered_subsequence(&items, &datetime);
+            let mut result: Vec<T> = Vec::with_capacity(ordered.len());
+            for (i, item) in items.into_iter().enumerate() {
+                if ordered.binary_search(&i).is_ok() {
+                    result.push(item);
+                } else {
+                    log::debug!("order_datetimes(), drop out-of-order datetime=({})", datetime(&item));
+                }
+            }
+            result
+        },
+        NegativePolicy::Sort => sort_datetimes(items, datetime).0,
+    }
+}
+
+/// Splits items (each with a datetime) into runs, as per the policy: for `Break`, a new run begins at each out-of-order datetime, otherwise there is a single run
+///
+/// # Returns
+/// The runs, in order (empty if there are no items)
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::{split_datetimes_runs, NegativePolicy};
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:05:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(),
+/// ];
+/// let result = split_datetimes_runs(&datetimes, &NegativePolicy::Break, |x| *x);
+/// assert_eq!(result, vec![datetimes[..2].to_vec(), datetimes[2..].to_vec()]);
+/// assert_eq!(split_datetimes_runs(&datetimes, &NegativePolicy::Clamp, |x| *x).len(), 1);
+/// ```
+pub fn split_datetimes_runs<T, F>(items: &[T], policy: &NegativePolicy, datetime: F) -> Vec<Vec<T>>
+    where T: Clone, F: Fn(&T) -> DateTime<FixedOffset>
+{
+    let mut result: Vec<Vec<T>> = vec![];
+    for (i, item) in items.iter().enumerate() {
+        let is_break = *policy == NegativePolicy::Break && i > 0 && datetime(item) < datetime(&items[i-1]);
+        match result.last_mut() {
+            Some(run) if !is_break => run.push(item.clone()),
+            _ => result.push(vec![item.clone()]),
+        }
+    }
+    result
+}
+
+/// Calculates deltas between consecutive datetimes (as per `delta_datetimes`), handling negative deltas as per the policy
+///
+/// Negative deltas are kept for `Break`, and counted as 0 for `Clamp`. Datetimes are ordered by `order_datetimes` for `Drop` / `Sort`, (so there are no negative deltas).
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::{delta_datetimes_with_policy, NegativePolicy};
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:05:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:06:00+00:00").unwrap(),
+/// ];
+/// assert_eq!(delta_datetimes_with_policy(&datetimes, &NegativePolicy::Clamp), vec![300, 0, 240]);
+/// assert_eq!(delta_datetimes_with_policy(&datetimes, &NegativePolicy::Break), vec![300, -180, 240]);
+/// assert_eq!(delta_datetimes_with_policy(&datetimes, &NegativePolicy::Drop), vec![300, 60]);
+/// assert_eq!(delta_datetimes_with_policy(&datetimes, &NegativePolicy::Sort), vec![120, 180, 60]);
+/// ```
+pub fn delta_datetimes_with_policy(datetimes: &[DateTime<FixedOffset>], policy: &NegativePolicy) -> Vec<i64>
+{
+    let datetimes = order_datetimes(datetimes.to_vec(), policy, |x| *x);
+    delta_datetimes(&datetimes, *policy == NegativePolicy::Break)
+}
+
+/// Sorts items (each with a datetime) chronologically, keeping the order of equal datetimes
+///
+/// # Returns
+/// A tuple of the sorted items, and the number of items reordered (the fewest items that must be moved to sort them, ie: those not in the longest already-ordered subsequence)
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::sort_datetimes;
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:05:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:06:00+00:00").unwrap(),
+/// ];
+/// let (result, reordered) = sort_datetimes(datetimes.clone(), |x| *x);
+/// assert_eq!(result, vec![datetimes[0], datetimes[2], datetimes[1], datetimes[3]]);
+/// assert_eq!(reordered, 1);
+/// ```
+pub fn sort_datetimes<T, F>(items: Vec<T>, datetime: F) -> (Vec<T>, usize)
+    where F: Fn(&T) -> DateTime<FixedOffset>
+{
+    let reordered = items.len() - ordered_subsequence(&items, &datetime).len();
+    log::trace!("sort_datetimes(), reordered=({})", reordered);
+    let mut result = items;
+    result.sort_by_key(|x| datetime(x));
+    (result, reordered)
+}
+
+/// Finds the longest already-ordered (non-decreasing) subsequence of items (each with a datetime), (the earliest such subsequence, if there are several)
+///
+/// # Returns
+/// The indexes of the items in the subsequence, in order
+fn ordered_subsequence<T, F>(items: &[T], datetime: F) -> Vec<usize>
+    where F: Fn(&T) -> DateTime<FixedOffset>
+{
+    let datetimes: Vec<DateTime<FixedOffset>> = items.iter().map(datetime).collect();
+    //  working from the end, heads[i] is the index of the latest first datetime of any ordered subsequence of length i+1, and next[j] the index after j in the subsequence beginning at j
+    let mut heads: Vec<usize> = vec![];
+    let mut next: Vec<Option<usize>> = vec![None; datetimes.len()];
+    for (j, x) in datetimes.iter().enumerate().rev() {
+        let i = heads.partition_point(|head| datetimes[*head] >= *x);
+        next[j] = i.checked_sub(1).map(|i| heads[i]);
+        if i == heads.len() {
+            heads.push(j);
+        } else {
+            heads[i] = j;
+        }
+    }
+    let mut result: Vec<usize> = Vec::with_capacity(heads.len());
+    let mut current = heads.last().copied();
+    while let Some(j) = current {
+        result.push(j);
+        current = next[j];
+    }
+    log::trace!("ordered_subsequence(), result=({:?})", result);
+    result
+}
+
+/// Removes items (each with a datetime) whose datetime is within `within` seconds of the last item kept (with `within` as 0, only identical instants are removed)
+///
+/// Datetimes are compared as instants, (the same time given in different offsets is identical)
+///
+/// # Returns
+/// The items kept, in order
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::dedup_datetimes;
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T10:00:00+10:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:20+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:01:00+00:00").unwrap(),
+/// ];
+/// assert_eq!(dedup_datetimes(datetimes.clone(), 0, |x| *x), vec![datetimes[0], datetimes[2], datetimes[3]]);
+/// assert_eq!(dedup_datetimes(datetimes.clone(), 30, |x| *x), vec![datetimes[0], datetimes[3]]);
+/// ```
+pub fn dedup_datetimes<T, F>(items: Vec<T>, within: u64, datetime: F) -> Vec<T>
+    where F: Fn(&T) -> DateTime<FixedOffset>
+{
+    let mut result: Vec<T> = Vec::with_capacity(items.len());
+    for item in items {
+        if result.last().is_none_or(|last| datetime_difference_seconds(datetime(last), datetime(&item)).unsigned_abs() > within) {
+            result.push(item);
+        } else {
+            log::debug!("dedup_datetimes(), remove duplicate datetime=({})", datetime(&item));
+        }
+    }
+    result
+}
+
 /// Splits the provided list of deltas into periods of continuous activity.
 ///
 /// A new period is started whenever a delta is encountered that is either negative or exceeds the specified timeout. The function then returns a list of the total accumulated time for each period of continuous activity.
@@ -92,7 +318,7 @@ pub fn datetime_difference_seconds(dt1: DateTime<FixedOffset>, dt2: DateTime<Fix
 /// * `timeout` - A u64 representing the maximum allowed difference between subsequent datetimes for them to be considered part of the same period of continuous activity.
 ///
 /// # Returns
-/// A vector of u64s where each u64 represents the total length of a period of continuous activity, in seconds.
+/// A vector of u64s where each u64 represents the total length of a period of continuous activity, in seconds (periods with a length of 0 are excluded).
 ///
 /// # Example
 /// ```
@@ -105,31 +331,639 @@ pub fn split_deltas(deltas: &Vec<i64>, timeout: u64) -> Vec<u64>
 {
     log::debug!("split_deltas(), timeout=({}), deltas=({:?})", timeout, deltas);
     let mut result = vec![];
-    let mut splits: Vec<Vec<u64>> = vec![];
-    let mut current_split: Vec<u64> = vec![];
+    let mut current_sum: u64 = 0;
     for delta in deltas {
         if *delta < 0 || (*delta as u64) > timeout {
-            if !current_split.is_empty() {
-                let current_sum: u64 = current_split.iter().sum();
-                if current_sum > 0 {
-                    splits.push(current_split);
-                }
+            if current_sum > 0 {
+                result.push(current_sum);
             }
-            current_split = vec![];
+            current_sum = 0;
         } else {
-            current_split.push(*delta as u64);
+            current_sum += *delta as u64;
         }
     }
-    if !current_split.is_empty() {
-        let current_sum: u64 = current_split.iter().sum();
-        if current_sum > 0 {
-            splits.push(current_split);
+    if current_sum > 0 {
+        result.push(current_sum);
+    }
+    log::debug!("split_deltas(), result=({:?})", result);
+    result
+}
+
+
+/// Splits the provided datetimes into periods of continuous activity (as per `split_deltas`), counting only part of each delta
+///
+/// Each delta (between consecutive datetimes) is counted as `clip(previous, current)` seconds, (eg: the part of the delta falling inside working hours), while the decision to start a new period is still made on the full delta. Negative deltas are counted as 0 and do not start a new period.
+///
+/// These are the durations of the sessions given by `sessions_clipped`.
+///
+/// # Arguments
+/// * `datetimes` - A reference to a vector of `DateTime<FixedOffset>`
+/// * `timeout` - The maximum allowed difference between subsequent datetimes for them to be considered part of the same period of continuous activity.
+/// * `clip` - Function giving the number of seconds between two datetimes which count towards a period
+///
+/// # Returns
+/// A vector of u64s where each u64 represents the total counted length of a period of continuous activity, in seconds (periods with a length of 0 are excluded).
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::split_datetimes_clipped;
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:04:00+00:00").unwrap(),
+/// ];
+/// let halved = |a: &DateTime<_>, b: &DateTime<_>| (*b - *a).num_seconds() as u64 / 2;
+/// assert_eq!(split_datetimes_clipped(&datetimes, 300, halved), vec![120]);
+/// ```
+pub fn split_datetimes_clipped<F>(datetimes: &Vec<DateTime<FixedOffset>>, timeout: u64, clip: F) -> Vec<u64>
+    where F: Fn(&DateTime<FixedOffset>, &DateTime<FixedOffset>) -> u64
+{
+    log::debug!("split_datetimes_clipped(), timeout=({}), datetimes=({:?})", timeout, datetimes);
+    let result: Vec<u64> = session_ranges(datetimes, timeout, |x| *x, clip)
+        .iter()
+        .map(|(_, _, duration)| *duration)
+        .filter(|x| *x > 0)
+        .collect();
+    log::debug!("split_datetimes_clipped(), result=({:?})", result);
+    result
+}
+
+/// Splits the provided datetimes into periods of continuous activity (as per `split_datetimes_clipped`), giving the consecutive pairs of datetimes making up each period
+///
+/// Only pairs with a positive delta are included. Negative deltas do not start a new period. Periods are the sessions given by `sessions_clipped`.
+///
+/// # Arguments
+/// * `datetimes` - A reference to a vector of `DateTime<FixedOffset>`
+/// * `timeout` - The maximum allowed difference between subsequent datetimes for them to be considered part of the same period of continuous activity.
+///
+/// # Returns
+/// A vector with an element for each period of continuous activity, listing the `(previous, current)` datetimes of each delta in that period (periods without a positive delta are excluded).
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::split_datetimes_pairs;
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T01:00:00+00:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-11T01:01:00+00:00").unwrap(),
+/// ];
+/// let result = split_datetimes_pairs(&datetimes, 300);
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0], vec![(datetimes[0], datetimes[1])]);
+/// assert_eq!(result[1], vec![(datetimes[2], datetimes[3])]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn split_datetimes_pairs(datetimes: &Vec<DateTime<FixedOffset>>, timeout: u64) -> Vec<Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>>
+{
+    log::debug!("split_datetimes_pairs(), timeout=({}), datetimes=({:?})", timeout, datetimes);
+    let result: Vec<Vec<_>> = session_ranges(datetimes, timeout, |x| *x, |_, _| 0)
+        .iter()
+        .map(|(start_idx, end_idx, _)| {
+            datetimes[*start_idx..=*end_idx].windows(2)
+                .filter(|pair| pair[1] > pair[0])
+                .map(|pair| (pair[0], pair[1]))
+                .collect::<Vec<_>>()
+        })
+        .filter(|x| !x.is_empty())
+        .collect();
+    log::debug!("split_datetimes_pairs(), result=({:?})", result);
+    result
+}
+
+/// Splits the provided items into sessions (as per `sessions_clipped`), giving the index (0-indexed) of the first and last item of each session, and its duration
+///
+/// This is the one implementation of finding periods of continuous activity - `split_datetimes_clipped`, `split_datetimes_pairs` and `sessions_clipped` are derived from it.
+fn session_ranges<T, F, C>(items: &[T], timeout: u64, datetime: F, clip: C) -> Vec<(usize, usize, u64)>
+    where F: Fn(&T) -> DateTime<FixedOffset>, C: Fn(&DateTime<FixedOffset>, &DateTime<FixedOffset>) -> u64
+{
+    let mut result: Vec<(usize, usize, u64)> = vec![];
+    for i in 0..items.len() {
+        let delta = match i {
+            0 => None,
+            _ => Some(datetime_difference_seconds(datetime(&items[i-1]), datetime(&items[i]))),
+        };
+        match (result.last_mut(), delta) {
+            (Some((_, end_idx, duration)), Some(delta)) if delta <= 0 || (delta as u64) <= timeout => {
+                if delta > 0 {
+                    *duration += clip(&datetime(&items[i-1]), &datetime(&items[i]));
+                }
+                *end_idx = i;
+            },
+            _ => result.push((i, i, 0)),
         }
     }
-    for split in &splits {
-        result.push(split.iter().sum());
+    result
+}
+
+/// A period of continuous activity (a split), and where it came from in the input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    /// Earliest datetime of the session
+    pub start: DateTime<FixedOffset>,
+    /// Latest datetime of the session
+    pub end: DateTime<FixedOffset>,
+    /// Length of the session in seconds (the sum of its positive deltas)
+    pub duration: u64,
+    /// Number of datetimes in the session
+    pub events: usize,
+    /// Line number (1-indexed) of the first datetime of the session
+    pub first_line: usize,
+    /// Line number (1-indexed) of the last datetime of the session
+    pub last_line: usize,
+}
+
+/// Splits the provided datetimes into sessions - periods of continuous activity
+///
+/// A new session is started whenever a positive delta exceeds `timeout`. Negative deltas count as 0 and do not start a new session. Every datetime belongs to a session, so sessions with a single datetime (or only negative/zero deltas) have a duration of 0.
+///
+/// # Arguments
+/// * `datetimes_and_lines` - Each datetime, and the line number it was found on
+/// * `timeout` - The maximum allowed difference between subsequent datetimes for them to be considered part of the same session.
+///
+/// # Returns
+/// A vector of `Session`, in the order of the input
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::sessions;
+/// let datetimes_and_lines = vec![
+///     (DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(), 1),
+///     (DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(), 3),
+///     (DateTime::parse_from_rfc3339("2023-05-11T01:00:00+00:00").unwrap(), 4),
+/// ];
+/// let result = sessions(&datetimes_and_lines, 300);
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].duration, 120);
+/// assert_eq!((result[0].first_line, result[0].last_line, result[0].events), (1, 3, 2));
+/// assert_eq!(result[1].duration, 0);
+/// ```
+pub fn sessions(datetimes_and_lines: &[(DateTime<FixedOffset>, usize)], timeout: u64) -> Vec<Session>
+{
+    sessions_clipped(datetimes_and_lines, timeout, |a, b| datetime_difference_seconds(*a, *b).max(0) as u64)
+}
+
+/// Splits the provided datetimes into sessions (as per `sessions`), counting each positive delta as `clip(previous, current)` seconds, (eg: the part of the delta falling inside working hours)
+pub fn sessions_clipped<F>(datetimes_and_lines: &[(DateTime<FixedOffset>, usize)], timeout: u64, clip: F) -> Vec<Session>
+    where F: Fn(&DateTime<FixedOffset>, &DateTime<FixedOffset>) -> u64
+{
+    log::debug!("sessions_clipped(), timeout=({}), datetimes_and_lines=({:?})", timeout, datetimes_and_lines);
+    let result: Vec<Session> = session_ranges(datetimes_and_lines, timeout, |x| x.0, clip)
+        .into_iter()
+        .map(|(start_idx, end_idx, duration)| {
+            let session = &datetimes_and_lines[start_idx..=end_idx];
+            Session {
+                start: session.iter().map(|x| x.0).min().unwrap(),
+                end: session.iter().map(|x| x.0).max().unwrap(),
+                duration,
+                events: session.len(),
+                first_line: datetimes_and_lines[start_idx].1,
+                last_line: datetimes_and_lines[end_idx].1,
+            }
+        })
+        .collect();
+    log::debug!("sessions_clipped(), result=({:?})", result);
+    result
+}
+
+/// Time-accounting model, determining how much time each session (split) counts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    /// The sum of deltas within the session (deltas > timeout, between sessions, are discarded)
+    Discard,
+    /// As for `Discard`, with each delta > timeout counted as the timeout (toward the session before it)
+    Capped,
+    /// Each datetime earns a fixed number of seconds, regardless of deltas
+    Credit(u64),
+    /// As for `Discard`, with the given number of seconds of padding before and after each session (not overlapping neighbouring sessions' padding)
+    Padded(u64),
+}
+
+/// Sets the duration of each session to the time it counts for under `model`
+///
+/// Sessions are expected to be consecutive, as given by `sessions` / `sessions_clipped`, with their durations as given by those functions (the `Discard` model, which leaves them unchanged).
+///
+/// # Arguments
+/// * `sessions` - The sessions, in order
+/// * `model` - The time-accounting model
+/// * `timeout` - The timeout the sessions were found with
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::{sessions, apply_model, Model};
+/// let datetimes_and_lines = vec![
+///     (DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(), 1),
+///     (DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(), 2),
+///     (DateTime::parse_from_rfc3339("2023-05-11T01:00:00+00:00").unwrap(), 3),
+/// ];
+/// let mut result = sessions(&datetimes_and_lines, 300);
+/// apply_model(&mut result, &Model::Capped, 300);
+/// assert_eq!(result.iter().map(|x| x.duration).collect::<Vec<_>>(), vec![420, 0]);
+/// let mut result = sessions(&datetimes_and_lines, 300);
+/// apply_model(&mut result, &Model::Credit(600), 300);
+/// assert_eq!(result.iter().map(|x| x.duration).collect::<Vec<_>>(), vec![1200, 600]);
+/// ```
+pub fn apply_model(sessions: &mut [Session], model: &Model, timeout: u64)
+{
+    let count = sessions.len();
+    match model {
+        Model::Discard => {},
+        Model::Capped => {
+            for session in sessions.iter_mut().take(count.saturating_sub(1)) {
+                session.duration += timeout;
+            }
+        },
+        Model::Credit(credit) => {
+            for session in sessions.iter_mut() {
+                session.duration = session.events as u64 * credit;
+            }
+        },
+        Model::Padded(padding) => {
+            let mut leading = *padding;
+            for i in 0..count {
+                let (trailing, next_leading) = match sessions.get(i + 1) {
+                    Some(next) => {
+                        let gap = datetime_difference_seconds(sessions[i].end, next.start).max(0) as u64;
+                        let next_leading = (*padding).min(gap / 2);
+                        ((*padding).min(gap - next_leading), next_leading)
+                    },
+                    None => (*padding, 0),
+                };
+                sessions[i].duration += leading + trailing;
+                leading = next_leading;
+            }
+        },
     }
-    log::debug!("split_deltas(), result=({:?})", result);
+    log::trace!("apply_model(), model=({:?}), result=({:?})", model, sessions);
+}
+
+/// A period of inactivity - a delta between consecutive datetimes longer than the timeout
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gap {
+    /// Datetime before the gap
+    pub start: DateTime<FixedOffset>,
+    /// Datetime after the gap
+    pub end: DateTime<FixedOffset>,
+    /// Length of the gap in seconds
+    pub duration: u64,
+    /// Line number (1-indexed) of the datetime before the gap
+    pub start_line: usize,
+    /// Line number (1-indexed) of the datetime after the gap
+    pub end_line: usize,
+}
+
+/// Finds the gaps between sessions (as per `sessions`) - each positive delta that exceeds `timeout`
+///
+/// # Arguments
+/// * `datetimes_and_lines` - Each datetime, and the line number it was found on
+/// * `timeout` - The maximum difference between subsequent datetimes not considered a gap
+///
+/// # Returns
+/// A vector of `Gap`, in the order of the input
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::gaps;
+/// let datetimes_and_lines = vec![
+///     (DateTime::parse_from_rfc3339("2023-05-11T00:00:00+00:00").unwrap(), 1),
+///     (DateTime::parse_from_rfc3339("2023-05-11T00:02:00+00:00").unwrap(), 3),
+///     (DateTime::parse_from_rfc3339("2023-05-11T01:00:00+00:00").unwrap(), 4),
+/// ];
+/// let result = gaps(&datetimes_and_lines, 300);
+/// assert_eq!(result.len(), 1);
+/// assert_eq!(result[0].duration, 3480);
+/// assert_eq!((result[0].start_line, result[0].end_line), (3, 4));
+/// ```
+pub fn gaps(datetimes_and_lines: &[(DateTime<FixedOffset>, usize)], timeout: u64) -> Vec<Gap>
+{
+    log::debug!("gaps(), timeout=({}), datetimes_and_lines=({:?})", timeout, datetimes_and_lines);
+    let mut result = vec![];
+    for pair in datetimes_and_lines.windows(2) {
+        let ((start, start_line), (end, end_line)) = (pair[0], pair[1]);
+        let delta = datetime_difference_seconds(start, end);
+        if delta > 0 && (delta as u64) > timeout {
+            result.push(Gap { start, end, duration: delta as u64, start_line, end_line, });
+        }
+    }
+    log::debug!("gaps(), result=({:?})", result);
+    result
+}
+
+/// Kind of `Anomaly` found between consecutive datetimes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// The UTC offset changes (eg: travel, DST, or a misconfigured machine)
+    OffsetChange,
+    /// A backwards jump of about one hour (eg: local times repeated after DST ends, recorded without the change of offset)
+    DstRepeat,
+    /// Any other large backwards jump (eg: clock skew between machines)
+    Skew,
+}
+
+impl AnomalyKind {
+    /// Name of the kind, as reported
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnomalyKind::OffsetChange => "offset_change",
+            AnomalyKind::DstRepeat => "dst_repeat",
+            AnomalyKind::Skew => "skew",
+        }
+    }
+}
+
+/// A suspicious change between consecutive datetimes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    /// Datetime before the change
+    pub start: DateTime<FixedOffset>,
+    /// Datetime after the change
+    pub end: DateTime<FixedOffset>,
+    /// Delta between `start` and `end` in seconds (negative for backwards jumps)
+    pub delta: i64,
+    /// Line number (1-indexed) of the datetime before the change
+    pub start_line: usize,
+    /// Line number (1-indexed) of the datetime after the change
+    pub end_line: usize,
+}
+
+/// Finds anomalies between consecutive datetimes - changes of UTC offset, and backwards jumps
+///
+/// A negative delta within `dst_tolerance` seconds of one hour is a `DstRepeat`, and any other negative delta longer than `min_skew` seconds is a `Skew`. A change of offset is reported (as an `OffsetChange`) in addition to any backwards jump between the same datetimes.
+///
+/// # Arguments
+/// * `datetimes_and_lines` - Each datetime, and the line number it was found on
+/// * `dst_tolerance` - The largest difference from -3600s of a delta reported as a `DstRepeat`
+/// * `min_skew` - The longest backwards jump not reported as a `Skew`
+///
+/// # Returns
+/// A vector of `Anomaly`, in the order of the input
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::delta_datetimes::{anomalies, AnomalyKind};
+/// let datetimes_and_lines = vec![
+///     (DateTime::parse_from_rfc3339("2023-04-02T02:50:00+11:00").unwrap(), 1),
+///     (DateTime::parse_from_rfc3339("2023-04-02T02:05:00+11:00").unwrap(), 2),
+///     (DateTime::parse_from_rfc3339("2023-04-02T02:10:00+10:00").unwrap(), 3),
+///     (DateTime::parse_from_rfc3339("2023-04-02T02:00:00+10:00").unwrap(), 4),
+/// ];
+/// let result = anomalies(&datetimes_and_lines, 900, 60);
+/// let found: Vec<(AnomalyKind, i64, usize)> = result.iter().map(|x| (x.kind, x.delta, x.end_line)).collect();
+/// assert_eq!(found, vec![
+///     (AnomalyKind::DstRepeat, -2700, 2),
+///     (AnomalyKind::OffsetChange, 3900, 3),
+///     (AnomalyKind::Skew, -600, 4),
+/// ]);
+/// ```
+pub fn anomalies(datetimes_and_lines: &[(DateTime<FixedOffset>, usize)], dst_tolerance: u64, min_skew: u64) -> Vec<Anomaly>
+{
+    log::debug!("anomalies(), dst_tolerance=({}), min_skew=({})", dst_tolerance, min_skew);
+    let mut result = vec![];
+    for pair in datetimes_and_lines.windows(2) {
+        let ((start, start_line), (end, end_line)) = (pair[0], pair[1]);
+        let delta = datetime_difference_seconds(start, end);
+        let anomaly = |kind| Anomaly { kind, start, end, delta, start_line, end_line, };
+        if start.offset() != end.offset() {
+            result.push(anomaly(AnomalyKind::OffsetChange));
+        }
+        if delta < 0 && (delta + 3600).unsigned_abs() <= dst_tolerance {
+            result.push(anomaly(AnomalyKind::DstRepeat));
+        } else if delta < 0 && delta.unsigned_abs() > min_skew {
+            result.push(anomaly(AnomalyKind::Skew));
+        }
+    }
+    log::debug!("anomalies(), result=({:?})", result);
+    result
+}
+
+/// Summary statistics of a list of values (eg: deltas or session lengths, in seconds)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// Number of values
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// 50th percentile (as per `percentile()`)
+    pub median: f64,
+    /// Population standard deviation
+    pub stddev: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+/// Calculates summary statistics (see `Stats`) of the provided values
+///
+/// # Arguments
+/// * `values` - The values to summarise, in any order
+///
+/// # Returns
+/// `Some(Stats)`, or `None` if `values` is empty
+///
+/// # Example
+/// ```
+/// use datetimescan::delta_datetimes::stats;
+/// let result = stats(&[60.0, 10.0, 20.0, 30.0]).unwrap();
+/// assert_eq!((result.count, result.min, result.max, result.mean, result.median), (4, 10.0, 60.0, 30.0, 25.0));
+/// assert_eq!(stats(&[]), None);
+/// ```
+pub fn stats(values: &[f64]) -> Option<Stats>
+{
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let count = sorted.len();
+    let mean = sorted.iter().sum::<f64>() / count as f64;
+    let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count as f64;
+    let result = Stats {
+        count,
+        min: sorted[0],
+        max: sorted[count-1],
+        mean,
+        median: percentile(&sorted, 50.0),
+        stddev: variance.sqrt(),
+        p90: percentile(&sorted, 90.0),
+        p95: percentile(&sorted, 95.0),
+        p99: percentile(&sorted, 99.0),
+    };
+    log::trace!("stats(), result=({:?})", result);
+    Some(result)
+}
+
+/// Calculates the `p`th percentile of sorted values, interpolating linearly between the closest ranks
+///
+/// # Arguments
+/// * `sorted` - The values, sorted in ascending order
+/// * `p` - The percentile (0 to 100)
+///
+/// # Panics
+/// Panics if `sorted` is empty
+///
+/// # Example
+/// ```
+/// use datetimescan::delta_datetimes::percentile;
+/// let sorted = [10.0, 20.0, 30.0, 40.0, 50.0];
+/// assert_eq!(percentile(&sorted, 50.0), 30.0);
+/// assert_eq!(percentile(&sorted, 90.0), 46.0);
+/// assert_eq!(percentile(&sorted, 100.0), 50.0);
+/// ```
+pub fn percentile(sorted: &[f64], p: f64) -> f64
+{
+    let rank = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Timeout (in seconds) used when no natural break is found in the distribution of deltas
+pub const DEFAULT_TIMEOUT: u64 = 300;
+
+/// Minimum number of positive deltas required to look for a natural break in their distribution
+const SUGGEST_TIMEOUT_MIN_DELTAS: usize = 10;
+
+/// A timeout chosen from the distribution of deltas (see `suggest_timeout()`), and how it was chosen
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutSuggestion {
+    /// Suggested timeout in seconds
+    pub timeout: u64,
+    /// Number of positive deltas in each bin of a log2 histogram (see `histogram_bin_bounds()`)
+    pub histogram: Vec<usize>,
+    /// Bins of the peak of intra-session deltas, the valley, and the peak of inter-session deltas (`None` if no valley was found, and `timeout` is `DEFAULT_TIMEOUT`)
+    pub valley: Option<(usize, usize, usize)>,
+}
+
+impl TimeoutSuggestion
+{
+    /// Describe how the timeout was chosen
+    ///
+    /// # Example
+    /// ```
+    /// use datetimescan::delta_datetimes::suggest_timeout;
+    /// let suggestion = suggest_timeout(&[30, 60]);
+    /// assert_eq!(suggestion.rationale(), "too few deltas (2 positive, need 10) to find a valley, using default timeout");
+    /// ```
+    pub fn rationale(&self) -> String
+    {
+        let total: usize = self.histogram.iter().sum();
+        let bin_str = |i: usize| {
+            let (lower, upper) = histogram_bin_bounds(i);
+            format!("({}s, {}s]", lower, upper)
+        };
+        match self.valley {
+            Some((intra, valley, inter)) => format!(
+                "valley at {} between peaks of intra-session deltas at {} and inter-session deltas at {}, in log2 histogram of {} positive deltas",
+                bin_str(valley), bin_str(intra), bin_str(inter), total),
+            None if total < SUGGEST_TIMEOUT_MIN_DELTAS => format!(
+                "too few deltas ({} positive, need {}) to find a valley, using default timeout",
+                total, SUGGEST_TIMEOUT_MIN_DELTAS),
+            None => format!(
+                "no valley between peaks in log2 histogram of {} positive deltas, using default timeout",
+                total),
+        }
+    }
+}
+
+/// Get the bounds (in seconds) of a bin of the log2 histogram used by `suggest_timeout()`
+///
+/// # Returns
+/// `(lower, upper)`, where the bin holds deltas in `(lower, upper]`: bin 0 holds deltas of 1 second, and bin `i` holds deltas in `(2^(i-1), 2^i]`
+///
+/// # Example
+/// ```
+/// use datetimescan::delta_datetimes::histogram_bin_bounds;
+/// assert_eq!(histogram_bin_bounds(0), (0, 1));
+/// assert_eq!(histogram_bin_bounds(9), (256, 512));
+/// ```
+pub fn histogram_bin_bounds(bin: usize) -> (u64, u64)
+{
+    match bin {
+        0 => (0, 1),
+        _ => (1 << (bin - 1), 1 << bin),
+    }
+}
+
+/// Counts positive deltas in a log2 histogram, (bin `i` counting deltas in the range given by `histogram_bin_bounds(i)`)
+///
+/// # Arguments
+/// * `deltas` - Deltas between consecutive datetimes in seconds, negative/zero deltas are ignored
+///
+/// # Returns
+/// The count of each bin, up to the last non-empty bin
+///
+/// # Example
+/// ```
+/// use datetimescan::delta_datetimes::delta_histogram;
+/// assert_eq!(delta_histogram(&[1, 2, 3, 4, 5, 0, -7]), vec![1, 1, 2, 1]);
+/// ```
+pub fn delta_histogram(deltas: &[i64]) -> Vec<usize>
+{
+    let mut histogram: Vec<usize> = vec![];
+    for &delta in deltas.iter().filter(|&&x| x > 0) {
+        let bin = (64 - (delta as u64 - 1).leading_zeros()) as usize;
+        if histogram.len() <= bin {
+            histogram.resize(bin + 1, 0);
+        }
+        histogram[bin] += 1;
+    }
+    histogram
+}
+
+/// Suggests a timeout separating intra-session from inter-session deltas
+///
+/// Positive deltas are counted in a log2 histogram (see `histogram_bin_bounds()`). The tallest bin is taken as the peak of intra-session deltas, and the tallest local peak after it (separated from it by a bin at most half its height) as the peak of inter-session deltas. The timeout is the upper bound of the lowest bin between them (the middle one, if several are equally low), so deltas in the valley are treated as continuous.
+/// If there are fewer than 10 positive deltas, or there is no such second peak, `DEFAULT_TIMEOUT` is suggested.
+///
+/// # Arguments
+/// * `deltas` - Deltas between consecutive datetimes in seconds (as per `delta_datetimes`), negative/zero deltas are ignored
+///
+/// # Returns
+/// A `TimeoutSuggestion`, with the histogram and bins used to choose the timeout
+///
+/// # Example
+/// ```
+/// use datetimescan::delta_datetimes::suggest_timeout;
+/// let mut deltas = vec![40; 10];
+/// deltas.extend([5000, 6000, 7000]);
+/// let suggestion = suggest_timeout(&deltas);
+/// assert_eq!(suggestion.timeout, 1024);
+/// assert_eq!(suggestion.valley, Some((6, 10, 13)));
+/// ```
+pub fn suggest_timeout(deltas: &[i64]) -> TimeoutSuggestion
+{
+    let histogram = delta_histogram(deltas);
+    let total: usize = histogram.iter().sum();
+    let valley = if total < SUGGEST_TIMEOUT_MIN_DELTAS { None } else { find_histogram_valley(&histogram) };
+    let timeout = match valley {
+        Some((_, valley, _)) => histogram_bin_bounds(valley).1,
+        None => DEFAULT_TIMEOUT,
+    };
+    let result = TimeoutSuggestion { timeout, histogram, valley, };
+    log::debug!("suggest_timeout(), result=({:?})", result);
     result
 }
 
+/// Find the (intra-session peak, valley, inter-session peak) bins of a histogram, as per `suggest_timeout()`
+fn find_histogram_valley(histogram: &[usize]) -> Option<(usize, usize, usize)>
+{
+    let count_at = |i: usize| histogram.get(i).copied().unwrap_or(0);
+    let intra = (0..histogram.len()).rev().max_by_key(|&i| histogram[i])?;
+    let inter = (intra+2..histogram.len())
+        .filter(|&j| histogram[j] >= count_at(j - 1) && histogram[j] >= count_at(j + 1))
+        .filter(|&j| histogram[intra+1..j].iter().any(|&x| x * 2 <= histogram[j]))
+        .rev()
+        .max_by_key(|&j| histogram[j])?;
+    let lowest = *histogram[intra+1..inter].iter().min()?;
+    let valleys: Vec<usize> = (intra+1..inter).filter(|&i| histogram[i] == lowest).collect();
+    Some((intra, valleys[valleys.len() / 2], inter))
+}
+
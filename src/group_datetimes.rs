@@ -2,77 +2,135 @@
 //  vim: set tabstop=4 modeline modelines=10:
 //  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
 //  {{{2
+use crate::calendar::Calendar;
+use crate::delta_datetimes;
+use crate::period::Period;
+
 use chrono::{DateTime, FixedOffset};
 use std::collections::HashMap;
 
 //  Notes:
 //  {{{
-//  2023-05-14T22:56:21AEST we don't need empty vectors to indicate days/months/years between the first and last datetime chronologically with no matching datetimes (do we?)
-//  2023-05-14T22:58:32AEST should be using an enum not a string for 'interval'?
 //  2023-05-14T23:01:54AEST we could return a HashMap<String, Vec<usize>> with the indices of the matches instead of the actual DateTimes?
 //  }}}
 
-/// Groups `DateTime<FixedOffset>` objects from the given vector by day, month, or year
+/// Groups `DateTime<FixedOffset>` objects from the given vector by the given `Period`
 ///
 /// # Arguments
 /// * `datetimes` - A vector of `DateTime<FixedOffset>` objects.
-/// * `interval` - A string that determines the grouping interval ("d", "m", or "y")
+/// * `period` - The `Period` to group by
 ///
 /// # Returns
-/// A `HashMap` where the keys are strings representing the date (YYYY-MM-DD/YYYY-MM/YYYY) 
-/// and values are a vector of the `DateTime<FixedOffset>` objects that fall within that interval
+/// A `HashMap` where the keys are strings representing the period (as per `Period::key()`, eg: YYYY-MM-DD/YYYY-Www/Mon/all)
+/// and values are a vector of the `DateTime<FixedOffset>` objects that fall within that period
 ///
-/// # Panics
-/// The function will panic if `interval` is not "d", "m", or "y".
+/// Each datetime is keyed in its own offset. The cyclic periods (`Weekday` and `HourOfDay`) aggregate across the whole input, (eg: every datetime on a Monday is grouped under "Mon").
 ///
 /// # Examples
 /// ```
 /// use datetimescan::group_datetimes::group_datetimes;
+/// use datetimescan::period::Period;
 /// use chrono::DateTime;
 /// let dt1 = DateTime::parse_from_rfc3339("2023-05-14T12:00:00+00:00").unwrap();
 /// let dt2 = DateTime::parse_from_rfc3339("2023-05-15T12:00:00+00:00").unwrap();
 /// let datetimes = vec![dt1, dt2];
-/// let result = group_datetimes(&datetimes, "d");
+/// let result = group_datetimes(&datetimes, &Period::Day);
 /// assert_eq!(result.get(&"2023-05-14".to_string()).unwrap().len(), 1);
+/// let result = group_datetimes(&datetimes, &Period::Week);
+/// assert_eq!(result.get(&"2023-W19".to_string()).unwrap().len(), 1);
+/// assert_eq!(result.get(&"2023-W20".to_string()).unwrap().len(), 1);
 /// ```
-pub fn group_datetimes(datetimes: &Vec<DateTime<FixedOffset>>, interval: &str) -> HashMap<String, Vec<DateTime<FixedOffset>>>
+pub fn group_datetimes(datetimes: &Vec<DateTime<FixedOffset>>, period: &Period) -> HashMap<String, Vec<DateTime<FixedOffset>>>
 {
-    log::trace!("group_datetimes(), interval=({}), datetimes=({:?})", interval, datetimes);
-    let result = if interval.eq_ignore_ascii_case("d") {
-        group_datetimes_by_format(datetimes, "%Y-%m-%d")
-    } else if interval.eq_ignore_ascii_case("m") {
-        group_datetimes_by_format(datetimes, "%Y-%m")
-    } else if interval.eq_ignore_ascii_case("y") {
-        group_datetimes_by_format(datetimes, "%Y")
-    } else if interval.eq_ignore_ascii_case("all") {
-        all_datetimes_as_map(datetimes)
-    } else {
-        panic!("unsupported interval=({}) (must be d/m/y)", interval);
-    };
-    log::trace!("group_datetimes(), result=({:?})", result);
-    result
+    group_datetimes_with_calendar(datetimes, period, &Calendar::default())
 }
 
-fn group_datetimes_by_format(datetimes: &Vec<DateTime<FixedOffset>>, format: &str) -> HashMap<String, Vec<DateTime<FixedOffset>>>
+/// Groups `DateTime<FixedOffset>` objects from the given vector by the given `Period`, as determined in the given `Calendar`
+///
+/// As per `group_datetimes()`, except each datetime is keyed in `calendar` (eg: converted to the calendar's timezone), so that the same instant is always grouped in the same period regardless of the offset it was written with
+///
+/// # Examples
+/// ```
+/// use datetimescan::group_datetimes::group_datetimes_with_calendar;
+/// use datetimescan::calendar::Calendar;
+/// use datetimescan::period::Period;
+/// use datetimescan::timezone::TimeZoneSpec;
+/// use chrono::DateTime;
+/// let dt1 = DateTime::parse_from_rfc3339("2023-05-14T23:30:00+10:00").unwrap();
+/// let dt2 = DateTime::parse_from_rfc3339("2023-05-14T14:00:00+00:00").unwrap();
+/// let datetimes = vec![dt1, dt2];
+/// let calendar = Calendar::new(TimeZoneSpec::parse("+1000"));
+/// let result = group_datetimes_with_calendar(&datetimes, &Period::Day, &calendar);
+/// assert_eq!(result.get(&"2023-05-15".to_string()).unwrap().len(), 1);
+/// assert_eq!(result.get(&"2023-05-14".to_string()).unwrap().len(), 1);
+/// ```
+pub fn group_datetimes_with_calendar(datetimes: &Vec<DateTime<FixedOffset>>, period: &Period, calendar: &Calendar) -> HashMap<String, Vec<DateTime<FixedOffset>>>
 {
+    log::trace!("group_datetimes_with_calendar(), period=({}), calendar=({:?}), datetimes=({:?})", period, calendar, datetimes);
     let mut result = HashMap::new();
     for datetime in datetimes {
-        let date_string = datetime.format(format).to_string();
-        result.entry(date_string)
+        result.entry(calendar.key(datetime, period))
             .or_insert_with(Vec::new)
             .push(*datetime);
     }
+    log::trace!("group_datetimes_with_calendar(), result=({:?})", result);
     result
 }
 
-fn all_datetimes_as_map(datetimes: &Vec<DateTime<FixedOffset>>) -> HashMap<String, Vec<DateTime<FixedOffset>>>
+/// Get splits (periods of continuous activity) per period, where each split is found on the whole timeline and then apportioned to the periods it overlaps
+///
+/// Each delta of a split is divided exactly at the period boundaries of `calendar`, and the seconds of each part (as given by `measure(part_start, part_end)`) are credited to the period containing it. A split spanning several periods therefore contributes a (partial) split to each of them, and the totals over all periods add up to the total of the ungrouped splits.
+///
+/// # Arguments
+/// * `datetimes` - A vector of `DateTime<FixedOffset>` objects (in order)
+/// * `timeout` - The maximum delta considered continuous (see `delta_datetimes::split_datetimes_pairs()`)
+/// * `period` - The `Period` to group by
+/// * `calendar` - The `Calendar` period boundaries are found in
+/// * `measure` - Function giving the number of seconds between two datetimes which count towards a split
+///
+/// # Returns
+/// A `HashMap` where the keys are the periods (as per `Period::key()`) and values are the (non-zero) parts of each split falling within that period, in seconds
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::calendar::Calendar;
+/// use datetimescan::group_datetimes::group_splits_apportioned;
+/// use datetimescan::period::Period;
+/// let datetimes = vec![
+///     DateTime::parse_from_rfc3339("2023-05-14T23:50:00+10:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-14T23:55:00+10:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-15T00:00:00+10:00").unwrap(),
+///     DateTime::parse_from_rfc3339("2023-05-15T00:02:00+10:00").unwrap(),
+/// ];
+/// let seconds = |a: &DateTime<_>, b: &DateTime<_>| (*b - *a).num_seconds() as u64;
+/// let result = group_splits_apportioned(&datetimes, 300, &Period::Day, &Calendar::default(), seconds);
+/// assert_eq!(result.get("2023-05-14"), Some(&vec![600]));
+/// assert_eq!(result.get("2023-05-15"), Some(&vec![120]));
+/// ```
+pub fn group_splits_apportioned<F>(datetimes: &Vec<DateTime<FixedOffset>>, timeout: u64, period: &Period, calendar: &Calendar, measure: F) -> HashMap<String, Vec<u64>>
+    where F: Fn(&DateTime<FixedOffset>, &DateTime<FixedOffset>) -> u64
 {
-    let mut result = HashMap::new();
-    for datetime in datetimes {
-        result.entry("all".to_string())
-            .or_insert_with(Vec::new)
-            .push(*datetime);
+    log::trace!("group_splits_apportioned(), timeout=({}), period=({}), calendar=({:?})", timeout, period, calendar);
+    let mut result: HashMap<String, Vec<u64>> = HashMap::new();
+    for split in delta_datetimes::split_datetimes_pairs(datetimes, timeout) {
+        let mut split_per_period: Vec<(String, u64)> = vec![];
+        for (previous, current) in split {
+            for (key, part_start, part_end) in calendar.split_at_boundaries(&previous, &current, period) {
+                let seconds = measure(&part_start, &part_end);
+                match split_per_period.iter_mut().find(|(x, _)| *x == key) {
+                    Some((_, total)) => *total += seconds,
+                    None => split_per_period.push((key, seconds)),
+                }
+            }
+        }
+        for (key, seconds) in split_per_period {
+            if seconds > 0 {
+                result.entry(key).or_default().push(seconds);
+            }
+        }
     }
+    log::trace!("group_splits_apportioned(), result=({:?})", result);
     result
 }
 
@@ -7,4 +7,11 @@ pub mod convert_seconds;
 pub mod printer;
 pub mod create_arg_parser;
 pub mod date_range;
-
+pub mod clock;
+pub mod relative_datetime;
+pub mod timezone;
+pub mod recurring_filter;
+pub mod period;
+pub mod calendar;
+pub mod validate;
+pub mod render;
@@ -24,7 +24,8 @@ fn main()
     let parser = create_arg_parser::create_arg_parser();
     let matches = parser.get_matches();
     log::trace!("main(), matches=({:?})", matches);
-    subcommands::run(&matches);
-    log::trace!("main(), DONE");
+    let exit_code = subcommands::run(&matches);
+    log::trace!("main(), DONE, exit_code=({})", exit_code);
+    std::process::exit(exit_code);
 }
 
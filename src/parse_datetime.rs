@@ -2,7 +2,9 @@
 //  vim: set tabstop=4 modeline modelines=10:
 //  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
 //  {{{2
-use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone};
+use crate::timezone::TimeZoneSpec;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use std::collections::HashMap;
 
 /// Parses a vector of strings into `DateTime<FixedOffset>` objects.
@@ -31,11 +33,19 @@ use std::collections::HashMap;
 /// assert!(parsed.is_some());
 /// ```
 pub fn parse_datetimes(datetimes_strs: &Vec<String>) -> Option<Vec<DateTime<FixedOffset>>>
+{
+    parse_datetimes_with_tz(datetimes_strs, &TimeZoneSpec::Local)
+}
+
+/// Parses a vector of strings into `DateTime<FixedOffset>` objects, resolving datetimes without an offset in `tz`
+///
+/// See `parse_datetimes`
+pub fn parse_datetimes_with_tz(datetimes_strs: &Vec<String>, tz: &TimeZoneSpec) -> Option<Vec<DateTime<FixedOffset>>>
 {
     log::trace!("parse_datetimes(), datetimes_str=({:?})", datetimes_strs);
     let mut result = Vec::with_capacity(datetimes_strs.len());
     for datetime_str in datetimes_strs {
-        let loop_result = parse_datetime(datetime_str);
+        let loop_result = parse_datetime_with_tz(datetime_str, tz);
         loop_result?;
         result.push(loop_result.unwrap());
     }
@@ -48,7 +58,7 @@ pub fn parse_datetimes(datetimes_strs: &Vec<String>) -> Option<Vec<DateTime<Fixe
 ///
 /// The input datetime string can be in various formats, such as RFC3339, with or without
 /// timezone offset, and with or without 'T' separator between date and time.
-/// If no timezone offset is provided, the local timezone offset will be used.
+/// If no timezone offset is provided, the datetime is resolved in the local timezone (see `parse_datetime_with_tz`).
 ///
 /// (Only currently supported timezones-as-string are: UTC, AEST, AEDT)
 ///
@@ -68,6 +78,27 @@ pub fn parse_datetimes(datetimes_strs: &Vec<String>) -> Option<Vec<DateTime<Fixe
 /// * `Some(DateTime<FixedOffset>)` if the input string can be parsed successfully
 /// * `None` if the input string cannot be parsed
 pub fn parse_datetime(datetime_str: &str) -> Option<DateTime<FixedOffset>> 
+{
+    parse_datetime_with_tz(datetime_str, &TimeZoneSpec::Local)
+}
+
+/// Parses a datetime string and returns a `DateTime<FixedOffset>` object, resolving datetimes without an offset in `tz`, with the offset in effect at that datetime (see `TimeZoneSpec::from_local()`)
+///
+/// See `parse_datetime`
+///
+/// # Examples
+/// ```
+/// use datetimescan::parse_datetime::parse_datetime_with_tz;
+/// use datetimescan::timezone::TimeZoneSpec;
+/// use chrono::DateTime;
+///
+/// let tz = TimeZoneSpec::parse("Australia/Sydney").unwrap();
+/// let datetime = parse_datetime_with_tz("2023-05-08T18:30:00", &tz).unwrap();
+/// assert_eq!(datetime, DateTime::parse_from_rfc3339("2023-05-08T18:30:00+10:00").unwrap());
+/// let datetime = parse_datetime_with_tz("2023-01-08T18:30:00", &tz).unwrap();
+/// assert_eq!(datetime, DateTime::parse_from_rfc3339("2023-01-08T18:30:00+11:00").unwrap());
+/// ```
+pub fn parse_datetime_with_tz(datetime_str: &str, tz: &TimeZoneSpec) -> Option<DateTime<FixedOffset>> 
 {
     fn map_tzcode_to_tzoffset(datetime_str: &str) -> String
     {
@@ -97,18 +128,12 @@ pub fn parse_datetime(datetime_str: &str) -> Option<DateTime<FixedOffset>>
         .or_else(|| {
             NaiveDateTime::parse_from_str(&datetime_str, "%Y-%m-%dT%H:%M:%S")
                 .ok()
-                .map(|naive_datetime| {
-                    let local_offset = *Local::now().offset();
-                    local_offset.from_local_datetime(&naive_datetime).unwrap()
-                })
+                .map(|naive_datetime| tz.from_local(&naive_datetime))
         })
         .or_else(|| {
             NaiveDateTime::parse_from_str(&datetime_str, "%Y-%m-%d %H:%M:%S")
                 .ok()
-                .map(|naive_datetime| {
-                    let local_offset = *Local::now().offset();
-                    local_offset.from_local_datetime(&naive_datetime).unwrap()
-                })
+                .map(|naive_datetime| tz.from_local(&naive_datetime))
         });
         if result.is_none() {
             log::error!("parse_datetime(), failed to parse datetime_str=({})", datetime_str);
@@ -0,0 +1,258 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Interval datetimes are grouped by
+///
+/// Calendar periods (`Hour` .. `Year`) each cover a single stretch of time, and are keyed as 'YYYY-MM-DDTHH' / 'YYYY-MM-DD' / 'YYYY-Www' (ISO week) / 'YYYY-MM' / 'YYYY-Qq' / 'YYYY'.
+/// Cyclic periods (`Weekday` / `HourOfDay`) recur, and are keyed as 'Mon'..'Sun' / '00'..'23'.
+/// `All` is a single period covering everything, keyed as 'all'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Weekday,
+    HourOfDay,
+    All,
+}
+
+impl FromStr for Period
+{
+    type Err = String;
+
+    /// Parses a period given as h/d/w/m/q/y/weekday/hour_of_day/all
+    ///
+    /// # Example
+    /// ```
+    /// use datetimescan::period::Period;
+    /// assert_eq!("w".parse::<Period>(), Ok(Period::Week));
+    /// assert_eq!("hour_of_day".parse::<Period>(), Ok(Period::HourOfDay));
+    /// assert!("fortnight".parse::<Period>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Period, String>
+    {
+        match s.to_ascii_lowercase().as_str() {
+            "h" => Ok(Period::Hour),
+            "d" => Ok(Period::Day),
+            "w" => Ok(Period::Week),
+            "m" => Ok(Period::Month),
+            "q" => Ok(Period::Quarter),
+            "y" => Ok(Period::Year),
+            "weekday" => Ok(Period::Weekday),
+            "hour_of_day" => Ok(Period::HourOfDay),
+            "all" => Ok(Period::All),
+            _ => Err(format!("unsupported period=({}) (must be h/d/w/m/q/y/weekday/hour_of_day/all)", s)),
+        }
+    }
+}
+
+impl fmt::Display for Period
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let s = match self {
+            Period::Hour => "h",
+            Period::Day => "d",
+            Period::Week => "w",
+            Period::Month => "m",
+            Period::Quarter => "q",
+            Period::Year => "y",
+            Period::Weekday => "weekday",
+            Period::HourOfDay => "hour_of_day",
+            Period::All => "all",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl Period
+{
+    /// Whether the period recurs (`Weekday` / `HourOfDay`), rather than covering a single stretch of time
+    pub fn is_cyclic(&self) -> bool
+    {
+        matches!(self, Period::Weekday | Period::HourOfDay)
+    }
+
+    /// Get the key of the period containing the (naive local) datetime `local`
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDateTime;
+    /// use datetimescan::period::Period;
+    /// let local = NaiveDateTime::parse_from_str("2023-05-14T13:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+    /// assert_eq!(Period::Hour.key(&local), "2023-05-14T13");
+    /// assert_eq!(Period::Week.key(&local), "2023-W19");
+    /// assert_eq!(Period::Quarter.key(&local), "2023-Q2");
+    /// assert_eq!(Period::Weekday.key(&local), "Sun");
+    /// ```
+    pub fn key(&self, local: &NaiveDateTime) -> String
+    {
+        match self {
+            Period::Hour => local.format("%Y-%m-%dT%H").to_string(),
+            Period::Day => local.format("%Y-%m-%d").to_string(),
+            Period::Week => local.format("%G-W%V").to_string(),
+            Period::Month => local.format("%Y-%m").to_string(),
+            Period::Quarter => format!("{}-Q{}", local.year(), local.month0() / 3 + 1),
+            Period::Year => local.format("%Y").to_string(),
+            Period::Weekday => local.format("%a").to_string(),
+            Period::HourOfDay => local.format("%H").to_string(),
+            Period::All => "all".to_string(),
+        }
+    }
+
+    /// Get the first (naive local) datetime of the period with key `key`
+    ///
+    /// # Returns
+    /// `None` if `key` is not a valid key for this period, or if the period is cyclic / `All` (which have no single start)
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use datetimescan::period::Period;
+    /// let start = NaiveDate::from_ymd_opt(2023, 4, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// assert_eq!(Period::Quarter.parse_key("2023-Q2"), Some(start));
+    /// assert_eq!(Period::Week.parse_key("2023-W01"), Some(NaiveDate::from_ymd_opt(2023, 1, 2).unwrap().and_hms_opt(0, 0, 0).unwrap()));
+    /// assert_eq!(Period::Month.parse_key("2023-13"), None);
+    /// ```
+    pub fn parse_key(&self, key: &str) -> Option<NaiveDateTime>
+    {
+        let date = match self {
+            Period::Hour => {
+                let (date, hour) = key.split_once('T')?;
+                if hour.len() != 2 {
+                    return None;
+                }
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                return date.and_hms_opt(hour.parse().ok()?, 0, 0);
+            },
+            Period::Day => NaiveDate::parse_from_str(key, "%Y-%m-%d").ok()?,
+            Period::Week => {
+                let (year, week) = key.split_once("-W")?;
+                NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)?
+            },
+            Period::Month => NaiveDate::parse_from_str(&format!("{}-01", key), "%Y-%m-%d").ok()?,
+            Period::Quarter => {
+                let (year, quarter) = key.split_once("-Q")?;
+                let quarter: u32 = quarter.parse().ok()?;
+                if !(1..=4).contains(&quarter) {
+                    return None;
+                }
+                NaiveDate::from_ymd_opt(year.parse().ok()?, quarter * 3 - 2, 1)?
+            },
+            Period::Year => NaiveDate::from_ymd_opt(key.parse().ok()?, 1, 1)?,
+            Period::Weekday | Period::HourOfDay | Period::All => return None,
+        };
+        Some(date.and_time(NaiveTime::MIN))
+    }
+
+    /// Get the first (naive local) datetime of the period containing `local`
+    ///
+    /// Cyclic periods are truncated to the start of the day (`Weekday`) / hour (`HourOfDay`) containing `local`, and `All` to `NaiveDateTime::MIN`
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use datetimescan::period::Period;
+    /// let local = NaiveDate::from_ymd_opt(2023, 5, 14).unwrap().and_hms_opt(13, 30, 0).unwrap();
+    /// assert_eq!(Period::Week.truncate(&local), NaiveDate::from_ymd_opt(2023, 5, 8).unwrap().and_hms_opt(0, 0, 0).unwrap());
+    /// assert_eq!(Period::Hour.truncate(&local), NaiveDate::from_ymd_opt(2023, 5, 14).unwrap().and_hms_opt(13, 0, 0).unwrap());
+    /// ```
+    pub fn truncate(&self, local: &NaiveDateTime) -> NaiveDateTime
+    {
+        let date = local.date();
+        let date = match self {
+            Period::Hour | Period::HourOfDay => return date.and_hms_opt(local.hour(), 0, 0).unwrap(),
+            Period::Day | Period::Weekday => date,
+            Period::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Period::Month => date.with_day(1).unwrap(),
+            Period::Quarter => NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1).unwrap(),
+            Period::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap(),
+            Period::All => return NaiveDateTime::MIN,
+        };
+        date.and_time(NaiveTime::MIN)
+    }
+
+    /// Get the first (naive local) datetime of the period after the one starting at `start` (as given by `truncate()`)
+    ///
+    /// For `All`, this is `NaiveDateTime::MAX`
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use datetimescan::period::Period;
+    /// let start = NaiveDate::from_ymd_opt(2022, 10, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// assert_eq!(Period::Quarter.next(&start), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap());
+    /// ```
+    pub fn next(&self, start: &NaiveDateTime) -> NaiveDateTime
+    {
+        match self {
+            Period::Hour | Period::HourOfDay => *start + Duration::hours(1),
+            Period::Day | Period::Weekday => *start + Duration::days(1),
+            Period::Week => *start + Duration::days(7),
+            Period::Month => add_months(start, 1),
+            Period::Quarter => add_months(start, 3),
+            Period::Year => add_months(start, 12),
+            Period::All => NaiveDateTime::MAX,
+        }
+    }
+
+    /// Get every key of a cyclic period, in order ('Mon'..'Sun' / '00'..'23')
+    ///
+    /// # Returns
+    /// An empty vector if the period is not cyclic
+    pub fn cyclic_keys(&self) -> Vec<String>
+    {
+        match self {
+            Period::Weekday => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].iter().map(|x| x.to_string()).collect(),
+            Period::HourOfDay => (0..24).map(|hour| format!("{:02}", hour)).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Compare two keys of this period chronologically
+    ///
+    /// Keys are compared as strings, except for `Weekday` keys, which are ordered Mon..Sun
+    pub fn cmp_keys(&self, a: &str, b: &str) -> Ordering
+    {
+        match self {
+            Period::Weekday => {
+                let index = |key: &str| key.parse::<Weekday>().map(|x| x.num_days_from_monday()).unwrap_or(7);
+                index(a).cmp(&index(b)).then_with(|| a.cmp(b))
+            },
+            _ => a.cmp(b),
+        }
+    }
+
+    /// Sort keys of this period chronologically (see `cmp_keys()`)
+    ///
+    /// # Example
+    /// ```
+    /// use datetimescan::period::Period;
+    /// let mut keys = vec!["Sun".to_string(), "Mon".to_string(), "Wed".to_string()];
+    /// Period::Weekday.sort_keys(&mut keys);
+    /// assert_eq!(keys, vec!["Mon", "Wed", "Sun"]);
+    /// ```
+    pub fn sort_keys(&self, keys: &mut [String])
+    {
+        keys.sort_by(|a, b| self.cmp_keys(a, b));
+    }
+}
+
+/// Add a number of months to a datetime that is the start of a month
+fn add_months(start: &NaiveDateTime, months: i32) -> NaiveDateTime
+{
+    let index = start.year() * 12 + start.month0() as i32 + months;
+    NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1)
+        .unwrap()
+        .and_time(NaiveTime::MIN)
+}
+
@@ -4,13 +4,15 @@
 //  {{{2
 
 use crate::convert_seconds::ConvertSeconds;
+use crate::delta_datetimes;
+use crate::period::Period;
+use crate::render::{Anomalies, CountsPerPeriod, Deltas, Field, Gaps, Issues, Locations, MissingPeriods, Records, Render, SessionsPerPeriod, SplitsPerPeriod, StatsPerPeriod, SumsPerPeriod, TimeoutReport};
 
 use clap::ArgMatches;
-use chrono::{DateTime, FixedOffset};
-use std::collections::HashMap;
+use std::str::FromStr;
 
 use std::fs::File;
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::io::BufWriter;
 
 #[allow(unused_imports)]
@@ -43,6 +45,118 @@ pub fn get_printer_writer(matches: &ArgMatches) -> Option<Box<dyn Write>> {
     }
 }
 
+/// Get the output format (see `Format`) from the 'format' `matches` argument (default=text)
+///
+/// # Panics
+/// Panics if 'format' is not a valid format
+pub fn get_printer_format(matches: &ArgMatches) -> Format {
+    matches.value_of("format").unwrap_or("text")
+        .parse().unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Output format, (see `get_renderer()`)
+///
+/// Formats other than `Text` output records with the fields documented for each subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Human readable text
+    #[default]
+    Text,
+    /// A JSON array of objects
+    Json,
+    /// A JSON object per line
+    Ndjson,
+    /// Comma separated values, with a header row
+    Csv,
+    /// Tab separated values, with a header row
+    Tsv,
+}
+
+impl FromStr for Format
+{
+    type Err = String;
+
+    /// Parses a format given as text/json/ndjson/csv/tsv
+    fn from_str(s: &str) -> Result<Format, String>
+    {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("unsupported format=({}) (must be text/json/ndjson/csv/tsv)", s)),
+        }
+    }
+}
+
+/// Whether to colour output, given by 'color' in `matches` (auto/always/never, default=auto)
+///
+/// For 'auto', output is coloured only if it is written to stdout, stdout is a terminal, and the NO_COLOR environment variable is not set (or empty)
+///
+/// # Panics
+/// Panics if 'color' is not auto/always/never
+pub fn get_printer_color(matches: &ArgMatches, to_stdout: bool) -> bool {
+    match matches.value_of("color").unwrap_or("auto") {
+        "auto" => to_stdout && std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|x| x.is_empty()),
+        "always" => true,
+        "never" => false,
+        s => panic!("invalid color=({})", s),
+    }
+}
+
+/// Get the width of chart output, given by 'width' in `matches`, or else the width of the terminal if output is written to stdout and stdout is a terminal, or else the COLUMNS environment variable (default=80)
+///
+/// # Panics
+/// Panics if 'width' is not a positive integer
+pub fn get_printer_width(matches: &ArgMatches, to_stdout: bool) -> usize {
+    if let Some(width_str) = matches.value_of("width") {
+        return width_str.parse::<usize>().ok()
+            .filter(|x| *x > 0)
+            .unwrap_or_else(|| panic!("invalid width=({})", width_str));
+    }
+    if to_stdout {
+        if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size_of(std::io::stdout()).filter(|(x, _)| x.0 > 0) {
+            return width as usize;
+        }
+    }
+    std::env::var("COLUMNS").ok()
+        .and_then(|x| x.parse::<usize>().ok())
+        .filter(|x| *x > 0)
+        .unwrap_or(80)
+}
+
+/// Get the `Render` for the output format (see `Format`) given by 'format' in `matches`, writing to `output` (or stdout if `None`)
+///
+/// # Panics
+/// Panics if 'format' is not a valid format
+pub fn get_renderer<'a>(matches: &ArgMatches, output: Option<&'a mut dyn Write>) -> Box<dyn Render + 'a> {
+    let to_stdout = output.is_none();
+    let color = get_printer_color(matches, to_stdout);
+    let printer = Printer::new(output);
+    let format = get_printer_format(matches);
+    for mode in ["table", "chart"] {
+        if matches.is_present(mode) && format != Format::Text {
+            panic!("invalid {} with format=({:?}) ({} requires format=text)", mode, format, mode);
+        }
+    }
+    if matches.is_present("table") {
+        return Box::new(TablePrinter::new(printer, matches.is_present("average"), color));
+    }
+    if matches.is_present("chart") {
+        return Box::new(ChartPrinter::new(printer, get_printer_width(matches, to_stdout), matches.is_present("ascii")));
+    }
+    match format {
+        Format::Text => Box::new(printer),
+        Format::Json => Box::new(JsonPrinter::new(printer, false)),
+        Format::Ndjson => Box::new(JsonPrinter::new(printer, true)),
+        Format::Csv => Box::new(CsvPrinter::new(printer, ',')),
+        Format::Tsv => Box::new(CsvPrinter::new(printer, '\t')),
+    }
+}
+
+/// `Render` which outputs results as human readable text
+#[derive(Default)]
 pub struct Printer<'a> {
     output: Option<&'a mut dyn Write>,
 }
@@ -50,88 +164,64 @@ pub struct Printer<'a> {
 impl<'a> Printer<'a> {
 
     pub fn new(output: Option<&'a mut dyn Write>) -> Printer<'a> {
-        Printer { output, }
+        Printer { output }
     }
 
-    pub fn default() -> Printer<'a> {
-        Printer { output: None, }
+    /// Output `line`, (followed by a newline)
+    pub(crate) fn print_line(&mut self, line: &str) {
+        out!(self.output, "{}", line);
     }
 
-    pub fn print_datetimes_no_locations(
-        &mut self,
-        datetimes_and_locations: &Vec<(String, usize, usize)>,
-    ) {
-        for (datetime, _, _) in datetimes_and_locations {
-            out!(self.output, "{}", datetime);
+    /// Output `value` for `interval`, prefixed by the interval key and `separator` (unless `period` is `Period::All`)
+    fn print_keyed(&mut self, period: &Period, interval: &str, separator: &str, value: &str) {
+        if *period == Period::All {
+            out!(self.output, "{}", value);
+        } else {
+            out!(self.output, "{}{}{}", interval, separator, value);
         }
     }
 
-    pub fn print_datetimes_and_locations(
-        &mut self,
-        datetimes_and_locations: &Vec<(String, usize, usize)>,
-    ) {
-        let ofs = "\t".to_string();
-        for (datetime, line_number, position) in datetimes_and_locations {
-            out!(
-                self.output,
-                "{}{}{}{}{}",
-                datetime,
-                ofs,
-                line_number,
-                ofs,
-                position
-            );
+    /// Output the tab-separated `header` of a table with a row per interval, (prefixed by 'interval' unless `period` is `Period::All`)
+    fn print_keyed_header(&mut self, period: &Period, header: &[&str]) {
+        self.print_keyed(period, "interval", "\t", &header.join("\t"));
+    }
+}
+
+impl Render for Printer<'_> {
+    fn render_locations(&mut self, result: &Locations) {
+        for (datetime, line_number, position) in &result.datetimes_and_locations {
+            if result.with_locations {
+                out!(self.output, "{}\t{}\t{}", datetime, line_number, position);
+            } else {
+                out!(self.output, "{}", datetime);
+            }
         }
     }
 
-    pub fn print_deltas(&mut self, deltas: &Vec<i64>) {
-        for delta in deltas {
-            out!(self.output, "{}", delta);
+    fn render_deltas(&mut self, result: &Deltas) {
+        for delta in &result.deltas {
+            out!(self.output, "{}", delta.convert_seconds(&result.unit));
         }
     }
 
-    pub fn print_counts_datetimes_grouped(
-        &mut self,
-        datetimes_grouped: &HashMap<String, Vec<DateTime<FixedOffset>>>,
-    ) {
-        let mut intervals: Vec<String> = datetimes_grouped.keys().cloned().collect();
-        intervals.sort();
-        if intervals.len() == 1 && intervals[0] == "all" {
-            out!(
-                self.output,
-                "{}",
-                datetimes_grouped.get("all").unwrap().len()
-            );
-        } else {
-            for interval in &intervals {
-                out!(
-                    self.output,
-                    "{}: {}",
-                    interval,
-                    datetimes_grouped.get(interval).unwrap().len()
-                );
-            }
+    fn render_counts(&mut self, result: &CountsPerPeriod) {
+        for (interval, count) in &result.counts {
+            self.print_keyed(&result.period, interval, ": ", &count.to_string());
         }
     }
 
-    pub fn print_splits_per_interval(
-        &mut self,
-        splits_per_interval: &HashMap<String, Vec<u64>>,
-        unit: &str,
-    ) {
-        let mut intervals: Vec<String> = splits_per_interval.keys().cloned().collect();
-        intervals.sort();
-        if intervals.len() == 1 && intervals[0] == "all" {
-            for split in splits_per_interval.get("all").unwrap() {
-                out!(self.output, "{}", split.convert_seconds(unit));
-            }
-        } else {
-            for interval in &intervals {
-                let splits = splits_per_interval
-                    .get(interval)
-                    .unwrap()
+    fn render_splits(&mut self, result: &SplitsPerPeriod) {
+        for (interval, splits) in &result.splits {
+            if result.period == Period::All {
+                for split in splits {
+                    out!(self.output, "{}", split.convert_seconds(&result.unit));
+                }
+            } else if splits.is_empty() {
+                out!(self.output, "{}: {}", interval, 0_u64.convert_seconds(&result.unit));
+            } else {
+                let splits = splits
                     .iter()
-                    .map(|x| x.convert_seconds(unit))
+                    .map(|x| x.convert_seconds(&result.unit))
                     .collect::<Vec<String>>()
                     .join(", ");
                 out!(self.output, "{}: {}", interval, splits);
@@ -139,27 +229,438 @@ impl<'a> Printer<'a> {
         }
     }
 
-    pub fn print_sum_splits_per_interval(
-        &mut self,
-        sum_splits_per_interval: &HashMap<String, u64>,
-        unit: &str,
-    ) {
-        let mut intervals: Vec<String> = sum_splits_per_interval.keys().cloned().collect();
-        intervals.sort();
-        if intervals.len() == 1 && intervals[0] == "all" {
-            let sum_in_output_unit = sum_splits_per_interval
-                .get("all")
-                .unwrap()
-                .convert_seconds(unit);
-            out!(self.output, "{}", sum_in_output_unit);
-        } else {
-            for interval in &intervals {
-                let sum_in_output_unit = sum_splits_per_interval
-                    .get(interval)
-                    .unwrap()
-                    .convert_seconds(unit);
-                out!(self.output, "{}: {}", interval, sum_in_output_unit);
+    fn render_sums(&mut self, result: &SumsPerPeriod) {
+        for (interval, sum) in &result.sums {
+            self.print_keyed(&result.period, interval, ": ", &sum.convert_seconds(&result.unit));
+        }
+    }
+
+    fn render_sessions(&mut self, result: &SessionsPerPeriod) {
+        if result.sessions.is_empty() {
+            return;
+        }
+        self.print_keyed_header(&result.period, &["start", "end", "duration", "events", "first_line", "last_line"]);
+        for (interval, sessions) in &result.sessions {
+            for session in sessions {
+                let row = [
+                    session.start.to_rfc3339(),
+                    session.end.to_rfc3339(),
+                    session.duration.convert_seconds(&result.unit),
+                    session.events.to_string(),
+                    session.first_line.to_string(),
+                    session.last_line.to_string(),
+                ].join("\t");
+                self.print_keyed(&result.period, interval, "\t", &row);
+            }
+        }
+    }
+
+    fn render_stats(&mut self, result: &StatsPerPeriod) {
+        if result.stats.is_empty() {
+            return;
+        }
+        self.print_keyed_header(&result.period, &["measure", "count", "min", "max", "mean", "median", "stddev", "p90", "p95", "p99"]);
+        for (interval, measures) in &result.stats {
+            for (measure, stats) in measures {
+                let mut row = vec![measure.to_string()];
+                match stats {
+                    Some(stats) => {
+                        row.push(stats.count.to_string());
+                        for value in [stats.min, stats.max, stats.mean, stats.median, stats.stddev, stats.p90, stats.p95, stats.p99] {
+                            row.push(value.convert_seconds(&result.unit));
+                        }
+                    },
+                    None => {
+                        row.push("0".to_string());
+                        row.extend(std::iter::repeat_n("-".to_string(), 8));
+                    },
+                }
+                self.print_keyed(&result.period, interval, "\t", &row.join("\t"));
+            }
+        }
+    }
+
+    fn render_gaps(&mut self, result: &Gaps) {
+        if result.gaps.is_empty() {
+            return;
+        }
+        out!(self.output, "{}", ["start", "end", "duration", "start_line", "end_line"].join("\t"));
+        for gap in &result.gaps {
+            let row = [
+                gap.start.to_rfc3339(),
+                gap.end.to_rfc3339(),
+                gap.duration.convert_seconds(&result.unit),
+                gap.start_line.to_string(),
+                gap.end_line.to_string(),
+            ].join("\t");
+            out!(self.output, "{}", row);
+        }
+    }
+
+    fn render_missing(&mut self, result: &MissingPeriods) {
+        for interval in &result.intervals {
+            out!(self.output, "{}", interval);
+        }
+    }
+
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport) {
+        let suggestion = &result.suggestion;
+        out!(self.output, "timeout: {}", suggestion.timeout.convert_seconds(&result.unit));
+        out!(self.output, "sessions: {}", result.sessions_count);
+        out!(self.output, "rationale: {}", suggestion.rationale());
+        if suggestion.histogram.is_empty() {
+            return;
+        }
+        out!(self.output, "histogram:");
+        let max_count = suggestion.histogram.iter().max().copied().unwrap_or(0).max(1);
+        let first_bin = suggestion.histogram.iter().position(|&x| x > 0).unwrap_or(0);
+        for (bin, count) in suggestion.histogram.iter().enumerate().skip(first_bin) {
+            let (_, upper) = delta_datetimes::histogram_bin_bounds(bin);
+            let mut row = vec![format!("<={}", upper.convert_seconds(&result.unit)), count.to_string(), "#".repeat((count * 40).div_ceil(max_count))];
+            if matches!(suggestion.valley, Some((_, valley, _)) if valley == bin) {
+                row.push("<- timeout".to_string());
+            }
+            out!(self.output, "{}", row.join("\t").trim_end());
+        }
+    }
+
+    fn render_anomalies(&mut self, result: &Anomalies) {
+        if result.anomalies.is_empty() {
+            return;
+        }
+        out!(self.output, "{}", ["kind", "start", "end", "delta", "start_line", "end_line"].join("\t"));
+        for anomaly in &result.anomalies {
+            let row = [
+                anomaly.kind.name().to_string(),
+                anomaly.start.to_rfc3339(),
+                anomaly.end.to_rfc3339(),
+                anomaly.delta.convert_seconds(&result.unit),
+                anomaly.start_line.to_string(),
+                anomaly.end_line.to_string(),
+            ].join("\t");
+            out!(self.output, "{}", row);
+        }
+    }
+
+    fn render_issues(&mut self, result: &Issues) {
+        if result.issues.is_empty() {
+            return;
+        }
+        out!(self.output, "{}", ["line", "column", "check", "datetime", "detail"].join("\t"));
+        for issue in &result.issues {
+            let row = [
+                issue.line.to_string(),
+                issue.column.to_string(),
+                issue.check.name().to_string(),
+                issue.datetime.clone(),
+                issue.detail.clone(),
+            ];
+            out!(self.output, "{}", row.join("\t"));
+        }
+    }
+}
+
+/// `Render` which outputs results (see `Records`) as JSON - either an array of objects, or an object per line (NDJSON)
+pub struct JsonPrinter<'a> {
+    printer: Printer<'a>,
+    lines: bool,
+}
+
+impl<'a> JsonPrinter<'a> {
+
+    /// # Arguments
+    /// * `printer` - The `Printer` written to
+    /// * `lines` - Whether to output an object per line (NDJSON), instead of an array
+    pub fn new(printer: Printer<'a>, lines: bool) -> JsonPrinter<'a> {
+        JsonPrinter { printer, lines }
+    }
+
+    fn print_records<R: Records>(&mut self, result: &R) {
+        let fields = result.fields();
+        let records = result.records();
+        let as_json = |record: &Vec<Field>| -> String {
+            let members: Vec<String> = fields.iter().zip(record.iter())
+                .map(|(name, value)| format!("{}:{}", json_string(name), json_value(value)))
+                .collect();
+            format!("{{{}}}", members.join(","))
+        };
+        if self.lines {
+            for record in &records {
+                self.printer.print_line(&as_json(record));
+            }
+            return;
+        }
+        if records.is_empty() {
+            self.printer.print_line("[]");
+            return;
+        }
+        self.printer.print_line("[");
+        for (i, record) in records.iter().enumerate() {
+            let separator = if i + 1 < records.len() { "," } else { "" };
+            self.printer.print_line(&format!("{}{}", as_json(record), separator));
+        }
+        self.printer.print_line("]");
+    }
+}
+
+impl Render for JsonPrinter<'_> {
+    fn render_locations(&mut self, result: &Locations) { self.print_records(result); }
+    fn render_deltas(&mut self, result: &Deltas) { self.print_records(result); }
+    fn render_counts(&mut self, result: &CountsPerPeriod) { self.print_records(result); }
+    fn render_splits(&mut self, result: &SplitsPerPeriod) { self.print_records(result); }
+    fn render_sums(&mut self, result: &SumsPerPeriod) { self.print_records(result); }
+    fn render_sessions(&mut self, result: &SessionsPerPeriod) { self.print_records(result); }
+    fn render_stats(&mut self, result: &StatsPerPeriod) { self.print_records(result); }
+    fn render_gaps(&mut self, result: &Gaps) { self.print_records(result); }
+    fn render_missing(&mut self, result: &MissingPeriods) { self.print_records(result); }
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport) { self.print_records(result); }
+    fn render_anomalies(&mut self, result: &Anomalies) { self.print_records(result); }
+    fn render_issues(&mut self, result: &Issues) { self.print_records(result); }
+}
+
+/// `Render` which outputs results (see `Records`) as delimiter separated values (CSV / TSV), with a header row
+pub struct CsvPrinter<'a> {
+    printer: Printer<'a>,
+    delimiter: char,
+}
+
+impl<'a> CsvPrinter<'a> {
+
+    /// # Arguments
+    /// * `printer` - The `Printer` written to
+    /// * `delimiter` - Separator of fields, ',' (values are quoted as needed) or '\t' (tabs and line breaks in values are replaced by spaces)
+    pub fn new(printer: Printer<'a>, delimiter: char) -> CsvPrinter<'a> {
+        CsvPrinter { printer, delimiter }
+    }
+
+    fn print_records<R: Records>(&mut self, result: &R) {
+        let delimiter = self.delimiter.to_string();
+        self.printer.print_line(&result.fields().join(&delimiter));
+        for record in result.records() {
+            let row: Vec<String> = record.iter()
+                .map(|x| if self.delimiter == ',' { csv_string(x.text()) } else { x.text().replace(['\t', '\n', '\r'], " ") })
+                .collect();
+            self.printer.print_line(&row.join(&delimiter));
+        }
+    }
+}
+
+impl Render for CsvPrinter<'_> {
+    fn render_locations(&mut self, result: &Locations) { self.print_records(result); }
+    fn render_deltas(&mut self, result: &Deltas) { self.print_records(result); }
+    fn render_counts(&mut self, result: &CountsPerPeriod) { self.print_records(result); }
+    fn render_splits(&mut self, result: &SplitsPerPeriod) { self.print_records(result); }
+    fn render_sums(&mut self, result: &SumsPerPeriod) { self.print_records(result); }
+    fn render_sessions(&mut self, result: &SessionsPerPeriod) { self.print_records(result); }
+    fn render_stats(&mut self, result: &StatsPerPeriod) { self.print_records(result); }
+    fn render_gaps(&mut self, result: &Gaps) { self.print_records(result); }
+    fn render_missing(&mut self, result: &MissingPeriods) { self.print_records(result); }
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport) { self.print_records(result); }
+    fn render_anomalies(&mut self, result: &Anomalies) { self.print_records(result); }
+    fn render_issues(&mut self, result: &Issues) { self.print_records(result); }
+}
+
+/// `Render` which outputs counts / sums per interval as an aligned table, with a percent-of-total column, a total row, and optionally an average per non-empty interval
+///
+/// Other results are output as text (by the `Printer` it wraps)
+pub struct TablePrinter<'a> {
+    printer: Printer<'a>,
+    average: bool,
+    color: bool,
+}
+
+impl<'a> TablePrinter<'a> {
+
+    /// # Arguments
+    /// * `printer` - The `Printer` written to, (and which outputs other results)
+    /// * `average` - Whether to include a row with the average per non-empty interval
+    /// * `color` - Whether to colour the table (header and total rows in bold, intervals without a value dimmed) with ANSI escapes
+    pub fn new(printer: Printer<'a>, average: bool, color: bool) -> TablePrinter<'a> {
+        TablePrinter { printer, average, color }
+    }
+
+    /// Output a table of `values` per interval, each formatted by `format_value`
+    fn print_table<F>(&mut self, value_name: &str, values: &[(String, f64)], format_value: F)
+    where
+        F: Fn(f64) -> String,
+    {
+        let total = values.iter().fold(0.0, |total, (_, x)| total + x);
+        let percent = |x: f64| if total > 0.0 { format!("{:.1}%", x / total * 100.0) } else { "-".to_string() };
+        let mut rows: Vec<(Vec<String>, &str)> = vec![(vec!["interval".to_string(), value_name.to_string(), "percent".to_string()], "1")];
+        for (interval, value) in values {
+            let style = if *value == 0.0 { "2" } else { "" };
+            rows.push((vec![interval.clone(), format_value(*value), percent(*value)], style));
+        }
+        rows.push((vec!["total".to_string(), format_value(total), percent(total)], "1"));
+        if self.average {
+            let non_empty = values.iter().filter(|(_, x)| *x > 0.0).count();
+            let average = if non_empty > 0 { format_value(total / non_empty as f64) } else { "-".to_string() };
+            rows.push((vec!["average".to_string(), average, String::new()], "1"));
+        }
+        let widths: Vec<usize> = (0..3).map(|i| rows.iter().map(|(row, _)| row[i].chars().count()).max().unwrap_or(0)).collect();
+        for (row, style) in rows {
+            let line = format!("{:<w0$}  {:>w1$}  {:>w2$}", row[0], row[1], row[2], w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+            let line = line.trim_end();
+            if self.color && !style.is_empty() {
+                self.printer.print_line(&format!("\x1b[{}m{}\x1b[0m", style, line));
+            } else {
+                self.printer.print_line(line);
             }
         }
     }
 }
+
+/// Formats a count (or an average of counts) to 2 decimal places, omitting them for whole numbers
+fn format_count(x: f64) -> String
+{
+    let result = format!("{:.2}", x);
+    result.strip_suffix(".00").map(|x| x.to_string()).unwrap_or(result)
+}
+
+impl Render for TablePrinter<'_> {
+    fn render_locations(&mut self, result: &Locations) { self.printer.render_locations(result); }
+    fn render_deltas(&mut self, result: &Deltas) { self.printer.render_deltas(result); }
+    fn render_counts(&mut self, result: &CountsPerPeriod) {
+        let values: Vec<(String, f64)> = result.counts.iter().map(|(interval, count)| (interval.clone(), *count as f64)).collect();
+        self.print_table("count", &values, format_count);
+    }
+    fn render_splits(&mut self, result: &SplitsPerPeriod) { self.printer.render_splits(result); }
+    fn render_sums(&mut self, result: &SumsPerPeriod) {
+        let values: Vec<(String, f64)> = result.sums.iter().map(|(interval, sum)| (interval.clone(), *sum as f64)).collect();
+        self.print_table("sum", &values, |x| x.convert_seconds(&result.unit));
+    }
+    fn render_sessions(&mut self, result: &SessionsPerPeriod) { self.printer.render_sessions(result); }
+    fn render_stats(&mut self, result: &StatsPerPeriod) { self.printer.render_stats(result); }
+    fn render_gaps(&mut self, result: &Gaps) { self.printer.render_gaps(result); }
+    fn render_missing(&mut self, result: &MissingPeriods) { self.printer.render_missing(result); }
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport) { self.printer.render_timeout_suggestion(result); }
+    fn render_anomalies(&mut self, result: &Anomalies) { self.printer.render_anomalies(result); }
+    fn render_issues(&mut self, result: &Issues) { self.printer.render_issues(result); }
+}
+
+/// Eighths of a Unicode block, (for the remainder of a bar)
+const CHART_PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Narrowest bar drawn by `ChartPrinter`, regardless of width
+const CHART_MIN_BAR_WIDTH: usize = 10;
+
+/// `Render` which outputs counts / sums per interval as horizontal bars (scaled so the largest value fills the width), and deltas as a log2 histogram (see `delta_datetimes::delta_histogram()`)
+///
+/// Other results are output as text (by the `Printer` it wraps)
+pub struct ChartPrinter<'a> {
+    printer: Printer<'a>,
+    width: usize,
+    ascii: bool,
+}
+
+impl<'a> ChartPrinter<'a> {
+
+    /// # Arguments
+    /// * `printer` - The `Printer` written to, (and which outputs other results)
+    /// * `width` - Width of each line (label, bar, and value), in characters, (bars are at least `CHART_MIN_BAR_WIDTH` wide)
+    /// * `ascii` - Whether to draw bars with '#' (instead of Unicode blocks, with eighths for partial blocks)
+    pub fn new(printer: Printer<'a>, width: usize, ascii: bool) -> ChartPrinter<'a> {
+        ChartPrinter { printer, width, ascii }
+    }
+
+    /// Draw a bar `fraction` (0..=1) of `bar_width` characters long
+    fn bar(&self, fraction: f64, bar_width: usize) -> String {
+        if self.ascii {
+            return "#".repeat((fraction * bar_width as f64).round() as usize);
+        }
+        let eighths = (fraction * (bar_width * 8) as f64).round() as usize;
+        let mut result = "█".repeat(eighths / 8);
+        if !eighths.is_multiple_of(8) {
+            result.push(CHART_PARTIAL_BLOCKS[eighths % 8]);
+        }
+        result
+    }
+
+    /// Output a bar for each of `bars` - a label, a value, and the value as text
+    fn print_bars(&mut self, bars: &[(String, f64, String)]) {
+        let label_width = bars.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or(0);
+        let value_width = bars.iter().map(|(_, _, text)| text.chars().count()).max().unwrap_or(0);
+        let bar_width = self.width.saturating_sub(label_width + value_width + 2).max(CHART_MIN_BAR_WIDTH);
+        let max_value = bars.iter().map(|(_, value, _)| *value).fold(0.0, f64::max);
+        for (label, value, text) in bars {
+            let fraction = if max_value > 0.0 { value / max_value } else { 0.0 };
+            let bar = self.bar(fraction, bar_width);
+            self.printer.print_line(&format!("{:<lw$} {:<bw$} {:>vw$}", label, bar, text, lw = label_width, bw = bar_width, vw = value_width));
+        }
+    }
+}
+
+impl Render for ChartPrinter<'_> {
+    fn render_locations(&mut self, result: &Locations) { self.printer.render_locations(result); }
+    fn render_deltas(&mut self, result: &Deltas) {
+        let histogram = delta_datetimes::delta_histogram(&result.deltas);
+        let first_bin = histogram.iter().position(|&x| x > 0).unwrap_or(0);
+        let bars: Vec<(String, f64, String)> = histogram.iter().enumerate().skip(first_bin)
+            .map(|(bin, count)| {
+                let (_, upper) = delta_datetimes::histogram_bin_bounds(bin);
+                (format!("<={}", upper.convert_seconds(&result.unit)), *count as f64, count.to_string())
+            })
+            .collect();
+        self.print_bars(&bars);
+    }
+    fn render_counts(&mut self, result: &CountsPerPeriod) {
+        let bars: Vec<(String, f64, String)> = result.counts.iter()
+            .map(|(interval, count)| (interval.clone(), *count as f64, count.to_string()))
+            .collect();
+        self.print_bars(&bars);
+    }
+    fn render_splits(&mut self, result: &SplitsPerPeriod) { self.printer.render_splits(result); }
+    fn render_sums(&mut self, result: &SumsPerPeriod) {
+        let bars: Vec<(String, f64, String)> = result.sums.iter()
+            .map(|(interval, sum)| (interval.clone(), *sum as f64, sum.convert_seconds(&result.unit)))
+            .collect();
+        self.print_bars(&bars);
+    }
+    fn render_sessions(&mut self, result: &SessionsPerPeriod) { self.printer.render_sessions(result); }
+    fn render_stats(&mut self, result: &StatsPerPeriod) { self.printer.render_stats(result); }
+    fn render_gaps(&mut self, result: &Gaps) { self.printer.render_gaps(result); }
+    fn render_missing(&mut self, result: &MissingPeriods) { self.printer.render_missing(result); }
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport) { self.printer.render_timeout_suggestion(result); }
+    fn render_anomalies(&mut self, result: &Anomalies) { self.printer.render_anomalies(result); }
+    fn render_issues(&mut self, result: &Issues) { self.printer.render_issues(result); }
+}
+
+/// A field as a JSON value (`Field::Num` unquoted)
+fn json_value(field: &Field) -> String
+{
+    match field {
+        Field::Str(x) => json_string(x),
+        Field::Num(x) => x.clone(),
+        Field::Null => "null".to_string(),
+    }
+}
+
+/// Quote and escape a string as a JSON string
+fn json_string(s: &str) -> String
+{
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Quote a string as a CSV field, if it contains a comma, quote, or line break
+fn csv_string(s: &str) -> String
+{
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
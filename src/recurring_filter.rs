@@ -0,0 +1,224 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use crate::timezone::TimeZoneSpec;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// Filter for recurring windows of time, (eg: working hours 09:00-17:30 mon-fri)
+///
+/// A datetime is inside the filter if its time-of-day is inside `time_of_day` (start inclusive, end exclusive, wrapping past midnight if start > end) and its day is one of `weekdays`. Either constraint may be `None` to allow anything.
+/// Datetimes are evaluated in `tz`, or in their own offset if `tz` is `None`.
+/// If `invert` is set, a datetime is inside the filter when it would otherwise be outside it.
+#[derive(Debug, Clone, Default)]
+pub struct RecurringFilter {
+    pub time_of_day: Option<(NaiveTime, NaiveTime)>,
+    pub weekdays: Option<Vec<Weekday>>,
+    pub tz: Option<TimeZoneSpec>,
+    pub invert: bool,
+}
+
+impl RecurringFilter
+{
+    /// Whether the filter excludes anything
+    pub fn is_active(&self) -> bool
+    {
+        self.time_of_day.is_some() || self.weekdays.is_some()
+    }
+
+    /// Does `datetime` fall inside the filter
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::DateTime;
+    /// use datetimescan::recurring_filter::{RecurringFilter, parse_time_of_day_range, parse_weekdays};
+    /// let filter = RecurringFilter {
+    ///     time_of_day: parse_time_of_day_range("09:00-17:30"),
+    ///     weekdays: parse_weekdays("mon-fri"),
+    ///     ..Default::default()
+    /// };
+    /// //  2023-05-24 is a Wednesday
+    /// assert!(filter.contains(&DateTime::parse_from_rfc3339("2023-05-24T09:00:00+10:00").unwrap()));
+    /// assert!(!filter.contains(&DateTime::parse_from_rfc3339("2023-05-24T17:30:00+10:00").unwrap()));
+    /// assert!(!filter.contains(&DateTime::parse_from_rfc3339("2023-05-27T10:00:00+10:00").unwrap()));
+    /// ```
+    pub fn contains(&self, datetime: &DateTime<FixedOffset>) -> bool
+    {
+        let local = self.to_local(datetime);
+        let weekday_ok = match &self.weekdays {
+            Some(weekdays) => weekdays.contains(&local.weekday()),
+            None => true,
+        };
+        let time_ok = match self.time_of_day {
+            Some((start, end)) if start < end => local.time() >= start && local.time() < end,
+            Some((start, end)) => local.time() >= start || local.time() < end,
+            None => true,
+        };
+        (weekday_ok && time_ok) != self.invert
+    }
+
+    /// Get the number of seconds between `start` and `end` which fall inside the filter
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::DateTime;
+    /// use datetimescan::recurring_filter::{RecurringFilter, parse_time_of_day_range};
+    /// let filter = RecurringFilter { time_of_day: parse_time_of_day_range("09:00-17:00"), ..Default::default() };
+    /// let start = DateTime::parse_from_rfc3339("2023-05-24T08:50:00+10:00").unwrap();
+    /// let end = DateTime::parse_from_rfc3339("2023-05-24T09:05:00+10:00").unwrap();
+    /// assert_eq!(filter.overlap_seconds(&start, &end), 300);
+    /// ```
+    pub fn overlap_seconds(&self, start: &DateTime<FixedOffset>, end: &DateTime<FixedOffset>) -> u64
+    {
+        if start >= end {
+            return 0;
+        }
+        if !self.is_active() {
+            return if self.invert { 0 } else { (*end - *start).num_seconds() as u64 };
+        }
+        let mut result = 0;
+        let mut day = self.to_local(start).date();
+        let last_day = self.to_local(end).date();
+        while day <= last_day {
+            for (window_start, window_end) in self.windows_on(day) {
+                let window_start = self.datetime_of_local(&window_start, start);
+                let window_end = self.datetime_of_local(&window_end, start);
+                let overlap_start = window_start.max(*start);
+                let overlap_end = window_end.min(*end);
+                if overlap_end > overlap_start {
+                    result += (overlap_end - overlap_start).num_seconds() as u64;
+                }
+            }
+            day += Duration::days(1);
+        }
+        result
+    }
+
+    /// Get the local intervals (start inclusive, end exclusive) of `day` which are inside the filter
+    fn windows_on(&self, day: NaiveDate) -> Vec<(NaiveDateTime, NaiveDateTime)>
+    {
+        let midnight = day.and_time(NaiveTime::MIN);
+        let next_midnight = midnight + Duration::days(1);
+        let weekday_ok = match &self.weekdays {
+            Some(weekdays) => weekdays.contains(&day.weekday()),
+            None => true,
+        };
+        let windows = if !weekday_ok {
+            vec![]
+        } else {
+            match self.time_of_day {
+                Some((start, end)) if start < end => vec![(day.and_time(start), day.and_time(end))],
+                Some((start, end)) => vec![(midnight, day.and_time(end)), (day.and_time(start), next_midnight)],
+                None => vec![(midnight, next_midnight)],
+            }
+        };
+        if !self.invert {
+            return windows;
+        }
+        let mut result = vec![];
+        let mut previous_end = midnight;
+        for (window_start, window_end) in windows {
+            if window_start > previous_end {
+                result.push((previous_end, window_start));
+            }
+            previous_end = window_end;
+        }
+        if previous_end < next_midnight {
+            result.push((previous_end, next_midnight));
+        }
+        result
+    }
+
+    fn to_local(&self, datetime: &DateTime<FixedOffset>) -> NaiveDateTime
+    {
+        match &self.tz {
+            Some(tz) => tz.to_local(datetime),
+            None => datetime.naive_local(),
+        }
+    }
+
+    /// Get the datetime for `local`, (in the offset of `reference` if `tz` is `None`)
+    fn datetime_of_local(&self, local: &NaiveDateTime, reference: &DateTime<FixedOffset>) -> DateTime<FixedOffset>
+    {
+        match &self.tz {
+            Some(tz) => tz.from_local(local),
+            None => TimeZoneSpec::Fixed(*reference.offset()).from_local(local),
+        }
+    }
+}
+
+/// Parses a time-of-day range given as 'HH:MM-HH:MM' (the end may be before the start, in which case the range wraps past midnight, (eg: '18:00-00:00' for the rest of the day))
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use datetimescan::recurring_filter::parse_time_of_day_range;
+/// let (start, end) = parse_time_of_day_range("09:00-17:30").unwrap();
+/// assert_eq!(start, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+/// assert_eq!(end, NaiveTime::from_hms_opt(17, 30, 0).unwrap());
+/// assert!(parse_time_of_day_range("22:00-06:00").is_some());
+/// assert!(parse_time_of_day_range("09:00").is_none());
+/// ```
+pub fn parse_time_of_day_range(s: &str) -> Option<(NaiveTime, NaiveTime)>
+{
+    let (start, end) = s.trim().split_once('-')?;
+    let start = parse_time_of_day(start)?;
+    let end = parse_time_of_day(end)?;
+    if start == end {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Parses a time-of-day given as 'HH:MM' / 'HH:MM:SS' / 'HH'
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use datetimescan::recurring_filter::parse_time_of_day;
+/// assert_eq!(parse_time_of_day("04:30"), NaiveTime::from_hms_opt(4, 30, 0));
+/// assert_eq!(parse_time_of_day("4"), NaiveTime::from_hms_opt(4, 0, 0));
+/// assert_eq!(parse_time_of_day("25:00"), None);
+/// ```
+pub fn parse_time_of_day(s: &str) -> Option<NaiveTime>
+{
+    let s = s.trim();
+    NaiveTime::parse_from_str(s, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M:%S"))
+        .ok()
+        .or_else(|| NaiveTime::from_hms_opt(s.parse().ok()?, 0, 0))
+}
+
+/// Parses a list of weekdays, given as comma separated names or ranges (eg: 'mon-fri' / 'sat,sun' / 'mon,wed-fri' / 'fri-mon')
+///
+/// # Examples
+/// ```
+/// use chrono::Weekday;
+/// use datetimescan::recurring_filter::parse_weekdays;
+/// assert_eq!(parse_weekdays("mon-wed"), Some(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed]));
+/// assert_eq!(parse_weekdays("sat,sun"), Some(vec![Weekday::Sat, Weekday::Sun]));
+/// assert_eq!(parse_weekdays("fri-mon"), Some(vec![Weekday::Fri, Weekday::Sat, Weekday::Sun, Weekday::Mon]));
+/// assert_eq!(parse_weekdays("someday"), None);
+/// ```
+pub fn parse_weekdays(s: &str) -> Option<Vec<Weekday>>
+{
+    let mut result = vec![];
+    for part in s.split(',') {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let last = last.trim().parse::<Weekday>().ok()?;
+                let mut day = first.trim().parse::<Weekday>().ok()?;
+                result.push(day);
+                while day != last {
+                    day = day.succ();
+                    result.push(day);
+                }
+            },
+            None => result.push(part.trim().parse::<Weekday>().ok()?),
+        }
+    }
+    result.dedup();
+    Some(result)
+}
+
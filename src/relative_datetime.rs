@@ -0,0 +1,146 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use crate::clock::Clock;
+use crate::timezone::TimeZoneSpec;
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime};
+
+/// Parses a duration string into a number of seconds.
+///
+/// A duration is one or more `<number><unit>` pairs, where unit is one of 's' (seconds), 'm' (minutes), 'h' (hours), 'd' (days), or 'w' (weeks). A plain number is taken as seconds.
+///
+/// # Examples
+/// ```
+/// use datetimescan::relative_datetime::parse_duration_seconds;
+/// assert_eq!(parse_duration_seconds("300"), Some(300));
+/// assert_eq!(parse_duration_seconds("7d"), Some(7 * 86400));
+/// assert_eq!(parse_duration_seconds("1h30m"), Some(5400));
+/// assert_eq!(parse_duration_seconds("2x"), None);
+/// ```
+///
+/// # Arguments
+/// * `s` - A string slice that holds the duration to parse.
+///
+/// # Returns
+/// * `Some(i64)` the duration in seconds
+/// * `None` if the string could not be parsed
+pub fn parse_duration_seconds(s: &str) -> Option<i64>
+{
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    if let Ok(seconds) = s.parse::<i64>() {
+        return Some(seconds);
+    }
+    let mut result: i64 = 0;
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+        } else {
+            if number.is_empty() {
+                return None;
+            }
+            let unit_seconds = match c.to_ascii_lowercase() {
+                's' => 1,
+                'm' => 60,
+                'h' => 3600,
+                'd' => 86400,
+                'w' => 7 * 86400,
+                _ => return None,
+            };
+            result = result.checked_add(number.parse::<i64>().ok()?.checked_mul(unit_seconds)?)?;
+            number.clear();
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(result)
+}
+
+/// Parses a relative or natural-language datetime expression into the first and last second it covers.
+///
+/// Expressions are resolved against `clock.now()`, with days / weeks / months / years as in `tz` (with the offset in effect at their start).
+/// Supported expressions:
+/// * `now`
+/// * `-<duration>` / `+<duration>` (see `parse_duration_seconds`), eg: '-7d', '-2w', '-90m'
+/// * `today` / `yesterday`
+/// * `this week` / `last week` / `this month` / `last month` / `this year` / `last year`
+/// * `ytd` (from the start of this year until now)
+///
+/// Words may be separated by spaces, '_', or '-'. Weeks start on Monday (as per ISO-8601). Instants ('now', '-7d') have the same first and last second.
+///
+/// # Examples
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::clock::FixedClock;
+/// use datetimescan::relative_datetime::parse_relative_bounds;
+/// use datetimescan::timezone::TimeZoneSpec;
+/// let clock = FixedClock::new(DateTime::parse_from_rfc3339("2023-05-24T12:00:00+10:00").unwrap());
+/// let tz = TimeZoneSpec::parse("+10:00").unwrap();
+/// let (first, last) = parse_relative_bounds("yesterday", &clock, &tz).unwrap();
+/// assert_eq!(first, DateTime::parse_from_rfc3339("2023-05-23T00:00:00+10:00").unwrap());
+/// assert_eq!(last, DateTime::parse_from_rfc3339("2023-05-23T23:59:59+10:00").unwrap());
+/// let (first, last) = parse_relative_bounds("-7d", &clock, &tz).unwrap();
+/// assert_eq!(first, DateTime::parse_from_rfc3339("2023-05-17T12:00:00+10:00").unwrap());
+/// assert_eq!(first, last);
+/// ```
+///
+/// # Arguments
+/// * `expr` - The expression to parse.
+/// * `clock` - The `Clock` the expression is relative to.
+/// * `tz` - The timezone days are found in.
+///
+/// # Returns
+/// * `Some((DateTime<FixedOffset>, DateTime<FixedOffset>))` the first and last second covered by the expression
+/// * `None` if the expression could not be parsed
+pub fn parse_relative_bounds(expr: &str, clock: &dyn Clock, tz: &TimeZoneSpec) -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)>
+{
+    let now = clock.now();
+    let expr = expr.trim().to_lowercase();
+    if let Some(duration_str) = expr.strip_prefix('-') {
+        let instant = now.checked_sub_signed(Duration::try_seconds(parse_duration_seconds(duration_str)?)?)?;
+        return Some((instant, instant));
+    }
+    if let Some(duration_str) = expr.strip_prefix('+') {
+        let instant = now.checked_add_signed(Duration::try_seconds(parse_duration_seconds(duration_str)?)?)?;
+        return Some((instant, instant));
+    }
+
+    let today = tz.to_local(&now).date();
+    let days = |first: NaiveDate, next: NaiveDate| -> Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        let first = tz.from_local(&first.and_time(NaiveTime::MIN));
+        let next = tz.from_local(&next.and_time(NaiveTime::MIN));
+        Some((first, next - Duration::seconds(1)))
+    };
+    let start_of_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let start_of_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)?;
+    let start_of_year = NaiveDate::from_ymd_opt(today.year(), 1, 1)?;
+
+    let expr = expr.replace(['_', '-'], " ").split_whitespace().collect::<Vec<&str>>().join(" ");
+    match expr.as_str() {
+        "now" => Some((now, now)),
+        "today" => days(today, today + Duration::days(1)),
+        "yesterday" => days(today - Duration::days(1), today),
+        "this week" => days(start_of_week, start_of_week + Duration::days(7)),
+        "last week" => days(start_of_week - Duration::days(7), start_of_week),
+        "this month" => days(start_of_month, add_months(start_of_month, 1)?),
+        "last month" => days(add_months(start_of_month, -1)?, start_of_month),
+        "this year" => days(start_of_year, add_months(start_of_year, 12)?),
+        "last year" => days(add_months(start_of_year, -12)?, start_of_year),
+        "ytd" => Some((days(start_of_year, today)?.0, now)),
+        _ => None,
+    }
+}
+
+/// Add (or subtract) a number of months to a date that is the first day of a month
+fn add_months(first_of_month: NaiveDate, months: i32) -> Option<NaiveDate>
+{
+    let index = first_of_month.year() * 12 + first_of_month.month0() as i32 + months;
+    NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1)
+}
+
@@ -0,0 +1,493 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+//! Typed results of subcommands, and the `Render` trait deciding how they are output (see `printer` for the text/json/csv implementations)
+
+use crate::convert_seconds::ConvertSeconds;
+use crate::delta_datetimes::{Anomaly, Gap, Session, Stats, TimeoutSuggestion};
+use crate::period::Period;
+use crate::validate::Issue;
+
+use std::collections::HashMap;
+
+/// Output of subcommand results
+///
+/// Each subcommand calls one method with its result. Implementations decide how (and whether) results are output: `printer::Printer` as text, `printer::JsonPrinter` / `printer::CsvPrinter` as records, and `Capture` keeps them.
+pub trait Render {
+    fn render_locations(&mut self, result: &Locations);
+    fn render_deltas(&mut self, result: &Deltas);
+    fn render_counts(&mut self, result: &CountsPerPeriod);
+    fn render_splits(&mut self, result: &SplitsPerPeriod);
+    fn render_sums(&mut self, result: &SumsPerPeriod);
+    fn render_sessions(&mut self, result: &SessionsPerPeriod);
+    fn render_stats(&mut self, result: &StatsPerPeriod);
+    fn render_gaps(&mut self, result: &Gaps);
+    fn render_missing(&mut self, result: &MissingPeriods);
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport);
+    fn render_anomalies(&mut self, result: &Anomalies);
+    fn render_issues(&mut self, result: &Issues);
+}
+
+/// Get the values of `map` as a list, ordered by their keys (as per `Period::sort_keys()`)
+fn sorted_by_period<T: Clone>(map: &HashMap<String, T>, period: &Period) -> Vec<(String, T)>
+{
+    let mut keys: Vec<String> = map.keys().cloned().collect();
+    period.sort_keys(&mut keys);
+    keys.into_iter().map(|key| { let value = map.get(&key).unwrap().clone(); (key, value) }).collect()
+}
+
+/// Datetime matches (subcommand 'locate')
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locations {
+    /// Each datetime string, its line number (1-indexed), and position on that line
+    pub datetimes_and_locations: Vec<(String, usize, usize)>,
+    /// Whether the line numbers / positions are output
+    pub with_locations: bool,
+}
+
+/// Deltas between consecutive datetimes, in seconds (subcommand 'deltas')
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deltas {
+    /// Unit in which deltas are output (see `ConvertSeconds`)
+    pub unit: String,
+    pub deltas: Vec<i64>,
+}
+
+/// Number of datetimes per interval (subcommand 'count')
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountsPerPeriod {
+    pub period: Period,
+    /// Each interval key (as per `Period::key()`) and its count, in order
+    pub counts: Vec<(String, usize)>,
+}
+
+impl CountsPerPeriod {
+    pub fn new(counts_per_interval: &HashMap<String, usize>, period: &Period) -> CountsPerPeriod {
+        CountsPerPeriod { period: *period, counts: sorted_by_period(counts_per_interval, period), }
+    }
+}
+
+/// Splits (in seconds) per interval (subcommand 'splits')
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitsPerPeriod {
+    pub period: Period,
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+    /// Each interval key and its splits, in order
+    pub splits: Vec<(String, Vec<u64>)>,
+}
+
+impl SplitsPerPeriod {
+    pub fn new(splits_per_interval: &HashMap<String, Vec<u64>>, period: &Period, unit: &str) -> SplitsPerPeriod {
+        SplitsPerPeriod { period: *period, unit: unit.to_string(), splits: sorted_by_period(splits_per_interval, period), }
+    }
+}
+
+/// A total (in seconds) per interval (subcommand 'sum', and 'gaps --total')
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumsPerPeriod {
+    pub period: Period,
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+    /// Each interval key and its total, in order
+    pub sums: Vec<(String, u64)>,
+}
+
+impl SumsPerPeriod {
+    pub fn new(sums_per_interval: &HashMap<String, u64>, period: &Period, unit: &str) -> SumsPerPeriod {
+        SumsPerPeriod { period: *period, unit: unit.to_string(), sums: sorted_by_period(sums_per_interval, period), }
+    }
+}
+
+/// Sessions per interval (subcommand 'splits --verbose')
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionsPerPeriod {
+    pub period: Period,
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+    /// Each interval key and its sessions, in order
+    pub sessions: Vec<(String, Vec<Session>)>,
+}
+
+impl SessionsPerPeriod {
+    pub fn new(sessions_per_interval: &HashMap<String, Vec<Session>>, period: &Period, unit: &str) -> SessionsPerPeriod {
+        SessionsPerPeriod { period: *period, unit: unit.to_string(), sessions: sorted_by_period(sessions_per_interval, period), }
+    }
+}
+
+/// Each measure (deltas / sessions), and its statistics (`None` if it has no values)
+pub type MeasureStats = Vec<(String, Option<Stats>)>;
+
+/// Summary statistics of each measure (deltas / sessions) per interval (subcommand 'stats')
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsPerPeriod {
+    pub period: Period,
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+    /// Each interval key and the statistics of its measures, in order
+    pub stats: Vec<(String, MeasureStats)>,
+}
+
+impl StatsPerPeriod {
+    pub fn new(stats_per_interval: &HashMap<String, MeasureStats>, period: &Period, unit: &str) -> StatsPerPeriod {
+        StatsPerPeriod { period: *period, unit: unit.to_string(), stats: sorted_by_period(stats_per_interval, period), }
+    }
+}
+
+/// Idle periods (subcommand 'gaps')
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gaps {
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+    pub gaps: Vec<Gap>,
+}
+
+/// Keys of intervals without datetimes, in order (subcommand 'missing')
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingPeriods {
+    pub intervals: Vec<String>,
+}
+
+/// A suggested timeout, and the number of sessions it results in (subcommand 'suggest-timeout')
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutReport {
+    pub suggestion: TimeoutSuggestion,
+    pub sessions_count: usize,
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+}
+
+/// Offset changes and backwards jumps (subcommand 'anomalies')
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomalies {
+    /// Unit in which durations are output (see `ConvertSeconds`)
+    pub unit: String,
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Issues found with datetimes in input (subcommand 'validate')
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issues {
+    pub issues: Vec<Issue>,
+}
+
+/// Value of a field of a record (see `Records`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    /// Text
+    Str(String),
+    /// Number, already formatted
+    Num(String),
+    /// No value (eg: a statistic of a measure without values)
+    Null,
+}
+
+impl Field {
+    /// The value, as text
+    pub fn text(&self) -> &str {
+        match self {
+            Field::Str(x) | Field::Num(x) => x,
+            Field::Null => "",
+        }
+    }
+
+    /// A duration in seconds, converted to `unit` (text for 'hms', otherwise a number)
+    pub fn duration<T: ConvertSeconds>(seconds: T, unit: &str) -> Field {
+        let value = seconds.convert_seconds(unit);
+        if unit == "hms" { Field::Str(value) } else { Field::Num(value) }
+    }
+}
+
+/// A result which can be output as records - rows, each with a value for each named field (see `printer::JsonPrinter` / `printer::CsvPrinter`)
+pub trait Records {
+    /// Names of the fields of each record
+    fn fields(&self) -> Vec<&'static str>;
+    /// The records, each with a value for each of `fields()`
+    fn records(&self) -> Vec<Vec<Field>>;
+}
+
+impl Records for Locations {
+    fn fields(&self) -> Vec<&'static str> {
+        if self.with_locations { vec!["datetime", "line", "position"] } else { vec!["datetime"] }
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.datetimes_and_locations.iter()
+            .map(|(datetime, line, position)| {
+                let mut record = vec![Field::Str(datetime.clone())];
+                if self.with_locations {
+                    record.push(Field::Num(line.to_string()));
+                    record.push(Field::Num(position.to_string()));
+                }
+                record
+            })
+            .collect()
+    }
+}
+
+impl Records for Deltas {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["delta", "unit"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.deltas.iter()
+            .map(|delta| vec![Field::duration(*delta, &self.unit), Field::Str(self.unit.clone())])
+            .collect()
+    }
+}
+
+impl Records for CountsPerPeriod {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["interval", "count"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.counts.iter()
+            .map(|(interval, count)| vec![Field::Str(interval.clone()), Field::Num(count.to_string())])
+            .collect()
+    }
+}
+
+impl Records for SplitsPerPeriod {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["interval", "duration", "unit"]
+    }
+
+    /// A record for each split, (or with a duration of 0 for an interval without splits)
+    fn records(&self) -> Vec<Vec<Field>> {
+        let mut result = vec![];
+        for (interval, splits) in &self.splits {
+            let durations = if splits.is_empty() { vec![0_u64] } else { splits.clone() };
+            for duration in durations {
+                result.push(vec![Field::Str(interval.clone()), Field::duration(duration, &self.unit), Field::Str(self.unit.clone())]);
+            }
+        }
+        result
+    }
+}
+
+impl Records for SumsPerPeriod {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["interval", "sum", "unit"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.sums.iter()
+            .map(|(interval, sum)| vec![Field::Str(interval.clone()), Field::duration(*sum, &self.unit), Field::Str(self.unit.clone())])
+            .collect()
+    }
+}
+
+impl Records for SessionsPerPeriod {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["interval", "start", "end", "duration", "events", "first_line", "last_line", "unit"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        let mut result = vec![];
+        for (interval, sessions) in &self.sessions {
+            for session in sessions {
+                result.push(vec![
+                    Field::Str(interval.clone()),
+                    Field::Str(session.start.to_rfc3339()),
+                    Field::Str(session.end.to_rfc3339()),
+                    Field::duration(session.duration, &self.unit),
+                    Field::Num(session.events.to_string()),
+                    Field::Num(session.first_line.to_string()),
+                    Field::Num(session.last_line.to_string()),
+                    Field::Str(self.unit.clone()),
+                ]);
+            }
+        }
+        result
+    }
+}
+
+impl Records for StatsPerPeriod {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["interval", "measure", "count", "min", "max", "mean", "median", "stddev", "p90", "p95", "p99", "unit"]
+    }
+
+    /// A record for each measure of each interval, (with null statistics for a measure without values)
+    fn records(&self) -> Vec<Vec<Field>> {
+        let mut result = vec![];
+        for (interval, measures) in &self.stats {
+            for (measure, stats) in measures {
+                let mut record = vec![Field::Str(interval.clone()), Field::Str(measure.clone())];
+                match stats {
+                    Some(stats) => {
+                        record.push(Field::Num(stats.count.to_string()));
+                        for value in [stats.min, stats.max, stats.mean, stats.median, stats.stddev, stats.p90, stats.p95, stats.p99] {
+                            record.push(Field::duration(value, &self.unit));
+                        }
+                    },
+                    None => {
+                        record.push(Field::Num("0".to_string()));
+                        record.extend(vec![Field::Null; 8]);
+                    },
+                }
+                record.push(Field::Str(self.unit.clone()));
+                result.push(record);
+            }
+        }
+        result
+    }
+}
+
+impl Records for Gaps {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["start", "end", "duration", "start_line", "end_line", "unit"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.gaps.iter()
+            .map(|gap| vec![
+                Field::Str(gap.start.to_rfc3339()),
+                Field::Str(gap.end.to_rfc3339()),
+                Field::duration(gap.duration, &self.unit),
+                Field::Num(gap.start_line.to_string()),
+                Field::Num(gap.end_line.to_string()),
+                Field::Str(self.unit.clone()),
+            ])
+            .collect()
+    }
+}
+
+impl Records for MissingPeriods {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["interval"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.intervals.iter().map(|interval| vec![Field::Str(interval.clone())]).collect()
+    }
+}
+
+impl Records for TimeoutReport {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["timeout", "sessions", "rationale", "unit"]
+    }
+
+    /// A single record, (the histogram is only output as text)
+    fn records(&self) -> Vec<Vec<Field>> {
+        vec![vec![
+            Field::duration(self.suggestion.timeout, &self.unit),
+            Field::Num(self.sessions_count.to_string()),
+            Field::Str(self.suggestion.rationale()),
+            Field::Str(self.unit.clone()),
+        ]]
+    }
+}
+
+impl Records for Anomalies {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["kind", "start", "end", "delta", "start_line", "end_line", "unit"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.anomalies.iter()
+            .map(|anomaly| vec![
+                Field::Str(anomaly.kind.name().to_string()),
+                Field::Str(anomaly.start.to_rfc3339()),
+                Field::Str(anomaly.end.to_rfc3339()),
+                Field::duration(anomaly.delta, &self.unit),
+                Field::Num(anomaly.start_line.to_string()),
+                Field::Num(anomaly.end_line.to_string()),
+                Field::Str(self.unit.clone()),
+            ])
+            .collect()
+    }
+}
+
+impl Records for Issues {
+    fn fields(&self) -> Vec<&'static str> {
+        vec!["line", "column", "check", "datetime", "detail"]
+    }
+
+    fn records(&self) -> Vec<Vec<Field>> {
+        self.issues.iter()
+            .map(|issue| vec![
+                Field::Num(issue.line.to_string()),
+                Field::Num(issue.column.to_string()),
+                Field::Str(issue.check.name().to_string()),
+                Field::Str(issue.datetime.clone()),
+                Field::Str(issue.detail.clone()),
+            ])
+            .collect()
+    }
+}
+
+/// A result passed to `Capture`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rendered {
+    Locations(Locations),
+    Deltas(Deltas),
+    Counts(CountsPerPeriod),
+    Splits(SplitsPerPeriod),
+    Sums(SumsPerPeriod),
+    Sessions(SessionsPerPeriod),
+    Stats(StatsPerPeriod),
+    Gaps(Gaps),
+    Missing(MissingPeriods),
+    TimeoutSuggestion(TimeoutReport),
+    Anomalies(Anomalies),
+    Issues(Issues),
+}
+
+/// `Render` which keeps each result (instead of outputting it), eg: for tests, or library users wanting structured results
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use datetimescan::period::Period;
+/// use datetimescan::render::{Capture, CountsPerPeriod, Render, Rendered};
+/// let mut capture = Capture::default();
+/// let counts = CountsPerPeriod::new(&HashMap::from([("2023-05-14".to_string(), 3)]), &Period::Day);
+/// capture.render_counts(&counts);
+/// assert_eq!(capture.rendered, vec![Rendered::Counts(counts)]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Capture {
+    pub rendered: Vec<Rendered>,
+}
+
+impl Render for Capture {
+    fn render_locations(&mut self, result: &Locations) {
+        self.rendered.push(Rendered::Locations(result.clone()));
+    }
+    fn render_deltas(&mut self, result: &Deltas) {
+        self.rendered.push(Rendered::Deltas(result.clone()));
+    }
+    fn render_counts(&mut self, result: &CountsPerPeriod) {
+        self.rendered.push(Rendered::Counts(result.clone()));
+    }
+    fn render_splits(&mut self, result: &SplitsPerPeriod) {
+        self.rendered.push(Rendered::Splits(result.clone()));
+    }
+    fn render_sums(&mut self, result: &SumsPerPeriod) {
+        self.rendered.push(Rendered::Sums(result.clone()));
+    }
+    fn render_sessions(&mut self, result: &SessionsPerPeriod) {
+        self.rendered.push(Rendered::Sessions(result.clone()));
+    }
+    fn render_stats(&mut self, result: &StatsPerPeriod) {
+        self.rendered.push(Rendered::Stats(result.clone()));
+    }
+    fn render_gaps(&mut self, result: &Gaps) {
+        self.rendered.push(Rendered::Gaps(result.clone()));
+    }
+    fn render_missing(&mut self, result: &MissingPeriods) {
+        self.rendered.push(Rendered::Missing(result.clone()));
+    }
+    fn render_timeout_suggestion(&mut self, result: &TimeoutReport) {
+        self.rendered.push(Rendered::TimeoutSuggestion(result.clone()));
+    }
+    fn render_anomalies(&mut self, result: &Anomalies) {
+        self.rendered.push(Rendered::Anomalies(result.clone()));
+    }
+    fn render_issues(&mut self, result: &Issues) {
+        self.rendered.push(Rendered::Issues(result.clone()));
+    }
+}
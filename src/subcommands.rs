@@ -11,15 +11,23 @@
 //  2023-05-27T20:08:17AEST 'failed to parse datetimes_strs', don't we want an error for which datetime(s) specifically failed?
 //  2023-06-12T22:19:14AEST just as we have made 'printer' a parameter to the actual subcommand functions, should we provide input stream as a parameter as well?
 //  }}}
-//  Ongoing: 2023-05-20T23:47:11AEST explain the size of the difference between the sum of 'splits' and 'sum' for textWithIsoDatetimes-2.txt -> 2256 for 'splits' and 2445 for 'sum'
 
 use crate::search_datetimes;
 use crate::parse_datetime;
 use crate::delta_datetimes;
 use crate::group_datetimes;
 use crate::printer;
+use crate::render::{self, Render};
+use crate::clock::{self, Clock};
+use crate::date_range::{self, DateRange};
+use crate::relative_datetime;
+use crate::recurring_filter::{self, RecurringFilter};
+use crate::timezone::{self, TimeZoneSpec};
+use crate::period::Period;
+use crate::calendar::Calendar;
+use crate::validate::{self, Check, LocatedDatetime};
 
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
 use clap::ArgMatches;
 use std::fs::File;
 use std::io::{self, BufReader};
@@ -30,99 +38,220 @@ use std::collections::HashMap;
 use log::{error, warn, info, debug, trace};
 
 /// Pass ArgParse matches to the function implementing the subcommand specified
-pub fn run(matches: &ArgMatches) {
+///
+/// # Returns
+/// The exit code for the process (1 if 'validate' found issues, otherwise 0)
+pub fn run(matches: &ArgMatches) -> i32 {
     let mut writer = printer::get_printer_writer(matches);
-    let mut printer = match writer.as_deref_mut() {
-        Some(w) => printer::Printer::new(Some(w)),
-        None => printer::Printer::default(),
+    let mut renderer = match writer.as_deref_mut() {
+        Some(w) => printer::get_renderer(matches, Some(w)),
+        None => printer::get_renderer(matches, None),
     };
+    let renderer = renderer.as_mut();
     match matches.subcommand() {
-        ("locate", Some(matches)) => locate(matches, &mut printer),
-        ("parse", Some(matches)) => parse(matches, &mut printer),
-        ("convert", Some(matches)) => convert(matches, &mut printer),
-        ("filter", Some(matches)) => filter(matches, &mut printer),
-        ("count", Some(matches)) => count(matches, &mut printer),
-        ("deltas", Some(matches)) => deltas(matches, &mut printer),
-        ("splits", Some(matches)) => splits(matches, &mut printer),
-        ("sum", Some(matches)) => sum(matches, &mut printer),
-        ("groupsum", Some(matches)) => groupsum(matches, &mut printer),
-        ("wpm", Some(matches)) => wpm(matches, &mut printer),
+        ("locate", Some(matches)) => locate(matches, renderer),
+        ("parse", Some(matches)) => parse(matches, renderer),
+        ("convert", Some(matches)) => convert(matches, renderer),
+        ("filter", Some(matches)) => filter(matches, renderer),
+        ("count", Some(matches)) => count(matches, renderer),
+        ("deltas", Some(matches)) => deltas(matches, renderer),
+        ("splits", Some(matches)) => splits(matches, renderer),
+        ("sum", Some(matches)) => sum(matches, renderer),
+        ("gaps", Some(matches)) => gaps(matches, renderer),
+        ("missing", Some(matches)) => missing(matches, renderer),
+        ("stats", Some(matches)) => stats(matches, renderer),
+        ("suggest-timeout", Some(matches)) => suggest_timeout(matches, renderer),
+        ("anomalies", Some(matches)) => anomalies(matches, renderer),
+        ("validate", Some(matches)) => if !validate(matches, renderer) { return 1; },
+        ("groupsum", Some(matches)) => groupsum(matches, renderer),
+        ("wpm", Some(matches)) => wpm(matches, renderer),
         _ => panic!("No subcommand was used. Use --help for more information."),
     }
+    0
 }
 
 /// Implement subcommand 'locate'
 /// List datetime matches and their locations
-pub fn locate(matches: &ArgMatches, printer: &mut printer::Printer)
+pub fn locate(matches: &ArgMatches, renderer: &mut dyn Render)
 {
     let datetimes_and_locations = get_datetimes_and_locations(matches);
-    if matches.is_present("no_locations") {
-        printer.print_datetimes_no_locations(&datetimes_and_locations);
-    } else {
-        printer.print_datetimes_and_locations(&datetimes_and_locations);
-    }
+    let with_locations = !matches.is_present("no_locations");
+    renderer.render_locations(&render::Locations { datetimes_and_locations, with_locations, });
 }
 
 #[allow(unused_variables)]
-pub fn parse(matches: &ArgMatches, printer: &mut printer::Printer)
+pub fn parse(matches: &ArgMatches, renderer: &mut dyn Render)
 {
     unimplemented!("UNIMPLEMENTED");
 }
 
 /// Implement subcommand 'count'
-/// Output count of datetimes per interval (y/m/d)
-pub fn count(matches: &ArgMatches, printer: &mut printer::Printer)
+/// Output count of datetimes per interval (see `Period`)
+/// If 'fill' is specified, intervals without datetimes are included (see `fill_intervals()`)
+pub fn count(matches: &ArgMatches, renderer: &mut dyn Render)
 {
-    let datetimes_grouped = get_datetimes_grouped(matches);
-    printer.print_counts_datetimes_grouped(&datetimes_grouped);
+    let period = parse_period(matches);
+    let mut datetimes_grouped = get_datetimes_grouped(matches);
+    if matches.is_present("fill") {
+        fill_intervals(matches, &mut datetimes_grouped);
+    }
+    let counts_per_interval: HashMap<String, usize> = datetimes_grouped.iter().map(|(k, v)| (k.clone(), v.len())).collect();
+    renderer.render_counts(&render::CountsPerPeriod::new(&counts_per_interval, &period));
 }
 
 #[allow(unused_variables)]
-pub fn convert(matches: &ArgMatches, printer: &mut printer::Printer) 
+pub fn convert(matches: &ArgMatches, renderer: &mut dyn Render) 
 {
     unimplemented!("UNIMPLEMENTED");
 }
 
 #[allow(unused_variables)]
-pub fn filter(matches: &ArgMatches, printer: &mut printer::Printer) 
+pub fn filter(matches: &ArgMatches, renderer: &mut dyn Render) 
 {
     unimplemented!("UNIMPLEMENTED");
 }
 
 /// Implement subcommand 'deltas'
-/// Output seconds elapsed between each datetime match
-pub fn deltas(matches: &ArgMatches, printer: &mut printer::Printer)
+/// Output seconds elapsed between each datetime match (in 'unit')
+pub fn deltas(matches: &ArgMatches, renderer: &mut dyn Render)
 {
+    let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
     let deltas = get_deltas(matches);
-    printer.print_deltas(&deltas);
+    renderer.render_deltas(&render::Deltas { unit: unit.to_string(), deltas, });
 }
 
 /// Implement subcommand 'splits'
 /// Output splits - the duration of continuous (each delta <= timeout) deltas 
-pub fn splits(matches: &ArgMatches, printer: &mut printer::Printer) 
+/// If 'verbose' is specified, output each split as a `Session`, with its start/end datetimes, number of datetimes, and first/last line numbers
+/// If 'fill' is specified, intervals without splits are included (see `fill_intervals()`)
+pub fn splits(matches: &ArgMatches, renderer: &mut dyn Render) 
 {
     let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
-    let splits_per_interval = get_splits_per_interval(matches);
-    printer.print_splits_per_interval(&splits_per_interval, unit);
+    let period = parse_period(matches);
+    if matches.is_present("verbose") {
+        let sessions_per_interval = get_sessions_per_interval(matches);
+        renderer.render_sessions(&render::SessionsPerPeriod::new(&sessions_per_interval, &period, unit));
+        return;
+    }
+    let mut splits_per_interval = get_splits_per_interval(matches);
+    if matches.is_present("fill") {
+        fill_intervals(matches, &mut splits_per_interval);
+    }
+    renderer.render_splits(&render::SplitsPerPeriod::new(&splits_per_interval, &period, unit));
 }
 
 /// Implement subcommand 'sum'
-/// Output the sum of splits for given interval (y/m/d)
-pub fn sum(matches: &ArgMatches, printer: &mut printer::Printer) 
+/// Output the sum of splits for given interval (see `Period`)
+/// If 'fill' is specified, intervals without splits are included (see `fill_intervals()`)
+pub fn sum(matches: &ArgMatches, renderer: &mut dyn Render) 
+{
+    let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
+    let period = parse_period(matches);
+    let mut sum_splits_per_interval = get_sum_splits_per_interval(matches);
+    if matches.is_present("fill") {
+        fill_intervals(matches, &mut sum_splits_per_interval);
+    }
+    renderer.render_sums(&render::SumsPerPeriod::new(&sum_splits_per_interval, &period, unit));
+}
+
+/// Implement subcommand 'gaps'
+/// Output gaps - each delta > timeout, with the datetimes and lines either side of it
+/// If 'total' is specified, output the total length of gaps for each interval instead
+pub fn gaps(matches: &ArgMatches, renderer: &mut dyn Render) 
+{
+    let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
+    let gaps = get_gaps(matches);
+    if matches.is_present("total") {
+        let period = parse_period(matches);
+        let calendar = parse_calendar(matches);
+        let mut total_per_interval: HashMap<String, u64> = HashMap::new();
+        for gap in &gaps {
+            *total_per_interval.entry(calendar.key(&gap.start, &period)).or_default() += gap.duration;
+        }
+        renderer.render_sums(&render::SumsPerPeriod::new(&total_per_interval, &period, unit));
+    } else {
+        renderer.render_gaps(&render::Gaps { unit: unit.to_string(), gaps, });
+    }
+}
+
+/// Implement subcommand 'missing'
+/// Output intervals without datetimes, from the first to the last interval, or across 'range' (see `get_missing_intervals()`)
+pub fn missing(matches: &ArgMatches, renderer: &mut dyn Render) 
+{
+    let missing_intervals = get_missing_intervals(matches);
+    renderer.render_missing(&render::MissingPeriods { intervals: missing_intervals, });
+}
+
+/// Implement subcommand 'stats'
+/// Output summary statistics (see `delta_datetimes::Stats`) of deltas, and of session lengths (splits), per interval
+pub fn stats(matches: &ArgMatches, renderer: &mut dyn Render) 
+{
+    let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
+    let period = parse_period(matches);
+    let stats_per_interval = get_stats_per_interval(matches);
+    renderer.render_stats(&render::StatsPerPeriod::new(&stats_per_interval, &period, unit));
+}
+
+/// Implement subcommand 'suggest-timeout'
+/// Output a timeout chosen from the distribution of deltas (see `delta_datetimes::suggest_timeout()`), the number of sessions it results in, and the histogram it was chosen from
+pub fn suggest_timeout(matches: &ArgMatches, renderer: &mut dyn Render) 
+{
+    let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
+    let datetimes_with_lines = get_datetimes_parsed_with_lines(matches);
+    let datetimes_parsed: Vec<DateTime<FixedOffset>> = datetimes_with_lines.iter().map(|(x, _)| *x).collect();
+    let deltas = delta_datetimes::delta_datetimes(&datetimes_parsed, false);
+    let suggestion = delta_datetimes::suggest_timeout(&deltas);
+    let sessions_count = delta_datetimes::sessions(&datetimes_with_lines, suggestion.timeout).len();
+    renderer.render_timeout_suggestion(&render::TimeoutReport { suggestion, sessions_count, unit: unit.to_string(), });
+}
+
+/// Implement subcommand 'anomalies'
+/// Output changes of UTC offset, and backwards jumps (DST repeats / clock skew), between consecutive datetimes (see `delta_datetimes::anomalies()`), with their line numbers
+pub fn anomalies(matches: &ArgMatches, renderer: &mut dyn Render) 
 {
     let unit = matches.value_of("unit").expect("expect argument 'unit' in `matches`");
-    let sum_splits_per_interval = get_sum_splits_per_interval(matches);
-    printer.print_sum_splits_per_interval(&sum_splits_per_interval, unit);
+    let anomalies = get_anomalies(matches);
+    renderer.render_anomalies(&render::Anomalies { unit: unit.to_string(), anomalies, });
+}
+
+/// Implement subcommand 'validate'
+/// Output every issue found with the datetimes in input (see `validate::validate()`), with its line and column, as a tab-separated table (or as records, as per 'format')
+/// Checks are given by 'checks' (default=all), and gaps longer than 'max_gap' (a duration, see `relative_datetime::parse_duration_seconds()`) are reported
+///
+/// Datetimes are not filtered (arguments like --filter_* / --range have no effect)
+///
+/// # Returns
+/// Whether input is valid (no issues were found)
+///
+/// # Panics
+/// Panics if reading input fails, or 'checks' / 'max_gap' are invalid
+pub fn validate(matches: &ArgMatches, renderer: &mut dyn Render) -> bool
+{
+    let tz = timezone::get_assumed_timezone(matches);
+    let clock = clock::get_clock(matches);
+    let checks: Vec<Check> = match matches.values_of("checks") {
+        Some(checks_strs) => checks_strs.map(|x| x.parse().unwrap_or_else(|e| panic!("{}", e))).collect(),
+        None => Check::ALL.to_vec(),
+    };
+    let max_gap_str = matches.value_of("max_gap").expect("expect argument 'max_gap' in `matches`");
+    let max_gap = relative_datetime::parse_duration_seconds(max_gap_str)
+        .filter(|x| *x >= 0)
+        .unwrap_or_else(|| panic!("invalid max_gap=({})", max_gap_str)) as u64;
+    let datetimes_and_locations = get_datetimes_and_locations(matches);
+    let issues = validate::validate(&datetimes_and_locations, &checks, &tz, clock.as_ref(), max_gap);
+    let is_valid = issues.is_empty();
+    renderer.render_issues(&render::Issues { issues, });
+    is_valid
 }
 
 #[allow(unused_variables)]
-pub fn groupsum(matches: &ArgMatches, printer: &mut printer::Printer) 
+pub fn groupsum(matches: &ArgMatches, renderer: &mut dyn Render) 
 {
     unimplemented!("UNIMPLEMENTED");
 }
 
 #[allow(unused_variables)]
-pub fn wpm(matches: &ArgMatches, printer: &mut printer::Printer) 
+pub fn wpm(matches: &ArgMatches, renderer: &mut dyn Render) 
 {
     unimplemented!("UNIMPLEMENTED");
 }
@@ -161,6 +290,7 @@ fn get_datetimes_and_locations(matches: &ArgMatches) -> Vec<(String, usize, usiz
 ///
 /// # Arguments
 /// * `matches`: Reference to command line arguments parsed by `clap::ArgMatches`.
+///
 /// Start and end of filter range are optionally given as 'filter_start' and 'filter_end'
 /// If 'filter_invert' is specified, datetimes inside the filter range are excluded instead of those outside the range
 /// If 'no_future' is specified, panic if any datetimes > now are encountered
@@ -174,7 +304,66 @@ fn get_datetimes_and_locations(matches: &ArgMatches) -> Vec<(String, usize, usiz
 fn get_datetimes_parsed(matches: &ArgMatches) -> Vec<DateTime<FixedOffset>>
 {
     let (datetimes_parsed, _, _) = get_datetimes_parsed_with_strs_and_positions(matches);
-    datetimes_parsed
+    sort_dedup_datetimes(matches, datetimes_parsed, |x| *x)
+}
+
+/// Get all parsed datetimes in given input which pass filters, and the line number (1-indexed) each was found on
+///
+/// Implemented by `get_datetimes_parsed_with_strs_and_positions()`
+///
+/// Returns
+/// `Vec<(DateTime<FixedOffset>, usize)>` all parsed datetimes in input which pass filter, and their line numbers
+///
+/// Panics
+/// `get_datetimes_parsed_with_strs_and_positions()` may panic if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+fn get_datetimes_parsed_with_lines(matches: &ArgMatches) -> Vec<(DateTime<FixedOffset>, usize)>
+{
+    let (datetimes_parsed, datetimes_and_locations, indexes_filter) = get_datetimes_parsed_with_strs_and_positions(matches);
+    let lines = datetimes_and_locations.iter()
+        .zip(indexes_filter.iter())
+        .filter(|(_, &include)| include)
+        .map(|((_, line, _), _)| *line);
+    sort_dedup_datetimes(matches, datetimes_parsed.into_iter().zip(lines).collect(), |x| x.0)
+}
+
+/// Sort and/or de-duplicate items (each with a datetime), as per the 'sort' / 'dedup' `matches` arguments
+///
+/// If 'sort' is specified, items are sorted chronologically (see `delta_datetimes::sort_datetimes()`)
+/// If 'dedup' is specified, items within the given number of seconds (default=0, identical instants) of the last item kept are removed, after sorting (see `delta_datetimes::dedup_datetimes()`)
+/// The number of datetimes reordered / removed is logged (at level info)
+///
+/// # Panics
+/// Panics if 'dedup' is not a valid duration
+fn sort_dedup_datetimes<T, F>(matches: &ArgMatches, items: Vec<T>, datetime: F) -> Vec<T>
+    where F: Fn(&T) -> DateTime<FixedOffset>
+{
+    let mut result = items;
+    if matches.is_present("sort") {
+        let (sorted, reordered) = delta_datetimes::sort_datetimes(result, &datetime);
+        log::info!("sort_dedup_datetimes(), sort, reordered=({}), count=({})", reordered, sorted.len());
+        result = sorted;
+    }
+    if matches.is_present("dedup") {
+        let within = parse_dedup(matches);
+        let count = result.len();
+        result = delta_datetimes::dedup_datetimes(result, within, &datetime);
+        log::info!("sort_dedup_datetimes(), dedup, removed=({}), count=({})", count - result.len(), count);
+    }
+    result
+}
+
+/// Get the 'dedup' `matches` argument, as a duration in seconds (see `relative_datetime::parse_duration_seconds()`) (default=0)
+///
+/// # Panics
+/// Panics if 'dedup' is not a valid (non-negative) duration
+fn parse_dedup(matches: &ArgMatches) -> u64
+{
+    match matches.value_of("dedup") {
+        None => 0,
+        Some(dedup_str) => relative_datetime::parse_duration_seconds(dedup_str)
+            .filter(|x| *x >= 0)
+            .unwrap_or_else(|| panic!("invalid dedup=({})", dedup_str)) as u64,
+    }
 }
 
 /// Locate all datetimes in given input, and filter them by datetime range. Returns list of parsed-and-filtered datetimes, list of all located datetimes and their positions, and a list of which located datetimes passed the filter
@@ -184,8 +373,10 @@ fn get_datetimes_parsed(matches: &ArgMatches) -> Vec<DateTime<FixedOffset>>
 ///
 /// # Arguments
 /// * `matches`: Reference to command line arguments parsed by `clap::ArgMatches`.
+///
 /// Start and end of filter range are optionally given as 'filter_start' and 'filter_end'
 /// If 'filter_invert' is specified, datetimes inside the filter range are excluded instead of those outside the range
+/// Datetimes outside the recurring filter ('time_of_day' / 'weekdays', see `parse_recurring_filter()`) are excluded, unless 'clip' is specified
 /// If 'no_future' is specified, panic if any datetimes > now are encountered
 /// If 'no_unsorted' is specified, panic if the located datetimes are not in order
 ///
@@ -193,34 +384,56 @@ fn get_datetimes_parsed(matches: &ArgMatches) -> Vec<DateTime<FixedOffset>>
 /// A 3 element tuple: `(Vec<DateTime<FixedOffset>>, Vec<(String, usize, usize)>, Vec<bool>)`
 /// * `Vec<DateTime<FixedOffset>>` all parsed datetimes not excluded by the filter
 /// * `Vec<(String,usize,usize)>` all origional datetime strings and their positions (including those excluded by the filter) (first `usize` is the line number (1-indexed), second `usize` is the line character position)
-/// * `Vec<bool>` indicates which elements of `Vec<(String,usize,usize)>` passed the filter and were included in `Vec<DateTime<FixedOffset>>`
+/// * `Vec<bool>` indicates which elements of `Vec<(String,usize,usize)>` passed the filter (after 'filter_invert' is applied) and were included in `Vec<DateTime<FixedOffset>>`
 ///
 /// # Panics
 /// Panics if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+#[allow(clippy::type_complexity)]
 fn get_datetimes_parsed_with_strs_and_positions(matches: &ArgMatches) -> (Vec<DateTime<FixedOffset>>, Vec<(String, usize, usize)>, Vec<bool>)
 {
+    let tz = timezone::get_assumed_timezone(matches);
+    let clock = clock::get_clock(matches);
     let datetimes_and_locations = get_datetimes_and_locations(matches);
     let datetimes_strs = datetimes_and_locations.iter().map(|(s, _, _)| s.to_string()).collect();
-    let datetimes_parsed = parse_datetime::parse_datetimes(&datetimes_strs);
+    let datetimes_parsed = parse_datetime::parse_datetimes_with_tz(&datetimes_strs, &tz);
     if datetimes_parsed.is_none() {
         panic!("failed to parse datetimes_strs=({:?})", datetimes_strs);
     }
     let datetimes_parsed = datetimes_parsed.unwrap();
 
-    let (filter_start, filter_end) = parse_filter_start_end(matches);
+    let (filter_start, filter_end) = parse_filter_start_end(matches, &tz, clock.as_ref());
     let filter_invert = matches.is_present("filter_invert");
-    let indexes_filter = filter_datetimes_valid_indexes(&datetimes_parsed, &filter_start, &filter_end);
-    let datetimes_filtered = datetimes_parsed.iter()
+    let ranges = parse_ranges(matches);
+    let calendar = parse_calendar(matches);
+    let recurring_filter = parse_recurring_filter(matches);
+    let clip = matches.is_present("clip");
+    let indexes_filter: Vec<bool> = filter_datetimes_valid_indexes(&datetimes_parsed, &filter_start, &filter_end).iter()
+        .zip(filter_datetimes_in_ranges_indexes(&datetimes_parsed, &ranges, &calendar).iter())
+        .zip(datetimes_parsed.iter())
+        .map(|((&in_start_end, &in_ranges), datetime)| {
+            let in_recurring = clip || recurring_filter.contains(datetime);
+            ((in_start_end && in_ranges) != filter_invert) && in_recurring
+        })
+        .collect();
+    let datetimes_filtered: Vec<DateTime<FixedOffset>> = datetimes_parsed.iter()
         .zip(indexes_filter.iter())
-        .filter(|(_, &include)| if filter_invert { !include } else { include })
+        .filter(|(_, &include)| include)
         .map(|(&x, _)| x)
         .collect();
 
-    if matches.is_present("no_future") {
-        reject_datetimes_future(&datetimes_filtered);
-    }
-    if matches.is_present("no_unsorted") {
-        reject_datetimes_unsorted(&datetimes_filtered);
+    if matches.is_present("no_future") || matches.is_present("no_unsorted") {
+        let located: Vec<LocatedDatetime> = datetimes_parsed.iter()
+            .zip(datetimes_and_locations.iter())
+            .zip(indexes_filter.iter())
+            .filter(|(_, &include)| include)
+            .map(|((&datetime, (text, line, position)), _)| LocatedDatetime { text: text.clone(), datetime, line: *line, position: *position, })
+            .collect();
+        if matches.is_present("no_future") {
+            reject_datetimes_future(&located, clock.as_ref());
+        }
+        if matches.is_present("no_unsorted") {
+            reject_datetimes_unsorted(&located);
+        }
     }
 
     assert_eq!(datetimes_and_locations.len(), indexes_filter.len());
@@ -229,30 +442,221 @@ fn get_datetimes_parsed_with_strs_and_positions(matches: &ArgMatches) -> (Vec<Da
 
 /// Get parsed-and-filtered datetimes from given input, and group them by interval
 ///
-/// Interval is specified as 'per' `matches` argument, and may be 'y' / 'm' / 'd'
+/// Interval is specified as 'per' `matches` argument (see `Period`)
 ///
 /// Getting parsed-and-filtered datetimes from input is handled by `get_datetimes_parsed()`
-/// Grouping is handled by `group_datetimes::group_datetimes()`
+/// Grouping is handled by `group_datetimes::group_datetimes_with_calendar()`, in the calendar given by 'group_tz' / 'day_start' `matches` arguments
 ///
 /// Returns
-/// `HashMap<String, Vec<DateTime<FixedOffset>>>` a list of all datetimes corresponding to each interval (the intervals are represented as strings, '%Y' / '%Y-%m' / '%Y-%m-%d' depending on interval type)
+/// `HashMap<String, Vec<DateTime<FixedOffset>>>` a list of all datetimes corresponding to each interval (the intervals are represented as strings, as per `Period::key()`)
 ///
 /// # Panics
 /// `get_datetimes_parsed_with_strs_and_positions()` may panic if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
 fn get_datetimes_grouped(matches: &ArgMatches) -> HashMap<String, Vec<DateTime<FixedOffset>>>
 {
-    let interval = matches.value_of("per").expect("expected `matches` argument 'per'");
+    let period = parse_period(matches);
+    let calendar = parse_calendar(matches);
     let datetimes_parsed = get_datetimes_parsed(matches);
-    let datetimes_grouped = group_datetimes::group_datetimes(&datetimes_parsed, interval);
-    datetimes_grouped
+    group_datetimes::group_datetimes_with_calendar(&datetimes_parsed, &period, &calendar)
+}
+
+/// Insert an empty (default) value for every interval with no value, between the first and last intervals present, or across the 'range' `matches` arguments
+///
+/// Interval is specified as 'per' `matches` argument (see `Period`)
+/// Intervals are enumerated by `get_interval_keys_span()`
+fn fill_intervals<T: Default>(matches: &ArgMatches, values_per_interval: &mut HashMap<String, T>)
+{
+    let period = parse_period(matches);
+    let ranges = if matches.is_present("filter_invert") { vec![] } else { parse_ranges(matches) };
+    let keys: Vec<String> = values_per_interval.keys().cloned().collect();
+    for key in get_interval_keys_span(&period, &keys, &ranges) {
+        values_per_interval.entry(key).or_default();
+    }
+}
+
+/// Get the keys (as per `Period::key()`) of every interval from the first to the last of `keys`, or across `ranges` if any are given
+///
+/// Open ends of `ranges` are taken from the first / last of `keys`. For cyclic periods, every key of the cycle is given, and for `Period::All`, the single key 'all'.
+///
+/// # Returns
+/// The keys, in order (empty if there are no `keys` and no closed `ranges`)
+fn get_interval_keys_span(period: &Period, keys: &[String], ranges: &[DateRange]) -> Vec<String>
+{
+    if period.is_cyclic() || *period == Period::All {
+        return DateRange { start: NaiveDate::MIN, end: NaiveDate::MIN, }.get_period_keys(period);
+    }
+    let mut keys_sorted = keys.to_vec();
+    period.sort_keys(&mut keys_sorted);
+    let first_key = keys_sorted.first().and_then(|x| period.parse_key(x).map(|start| (x, start)));
+    let last_key = keys_sorted.last().and_then(|x| period.parse_key(x).map(|start| (x, start)));
+    let mut result: Vec<String> = vec![];
+    if ranges.is_empty() {
+        if let (Some((first_key, first)), Some((last_key, last))) = (first_key, last_key) {
+            result = DateRange { start: first.date(), end: last.date(), }.get_period_keys(period)
+                .into_iter()
+                .filter(|x| period.cmp_keys(x, first_key).is_ge() && period.cmp_keys(x, last_key).is_le())
+                .collect();
+        }
+    }
+    for range in ranges {
+        let start = match (range.start, first_key) {
+            (NaiveDate::MIN, Some((_, first))) => first.date(),
+            (NaiveDate::MIN, None) => continue,
+            (start, _) => start,
+        };
+        let end = match (range.end, last_key) {
+            (NaiveDate::MAX, Some((_, last))) => last.date(),
+            (NaiveDate::MAX, None) => continue,
+            (end, _) => end,
+        };
+        if start <= end {
+            result.extend(DateRange { start, end, }.get_period_keys(period));
+        }
+    }
+    period.sort_keys(&mut result);
+    result.dedup();
+    result
+}
+
+/// Get the keys of intervals containing no datetimes
+///
+/// Interval is specified as 'per' `matches` argument (see `Period`)
+/// Intervals are enumerated by `get_interval_keys_span()`, from the first to the last interval with datetimes, or across the 'range' `matches` arguments (unless 'filter_invert' is specified)
+/// Intervals every day of which is in a range listed in 'exclude_file' (see `parse_exclude_file()`) are not reported
+///
+/// Returns
+/// `Vec<String>` the keys of intervals without datetimes (as per `Period::key()`), in order
+///
+/// # Panics
+/// Panics if 'exclude_file' cannot be read or is invalid, reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+fn get_missing_intervals(matches: &ArgMatches) -> Vec<String>
+{
+    let period = parse_period(matches);
+    let excluded = parse_exclude_file(matches);
+    let ranges = if matches.is_present("filter_invert") { vec![] } else { parse_ranges(matches) };
+    let datetimes_grouped = get_datetimes_grouped(matches);
+    let keys: Vec<String> = datetimes_grouped.keys().cloned().collect();
+    let result: Vec<String> = get_interval_keys_span(&period, &keys, &ranges)
+        .into_iter()
+        .filter(|key| !datetimes_grouped.contains_key(key))
+        .filter(|key| {
+            let start = period.parse_key(key).expect("expect `get_interval_keys_span()` to give valid keys").date();
+            let end = period.next(&start.and_time(NaiveTime::MIN)).date().pred_opt().unwrap();
+            !DateRange { start, end, }.is_covered_by(&excluded)
+        })
+        .collect();
+    log::trace!("get_missing_intervals(), result=({:?})", result);
+    result
+}
+
+/// Parses the file given as 'exclude_file' `matches` argument into a list of ranges (see `date_range::parse_date_ranges()`), with END included in each range unless 'range_bounds' is 'half_open'
+///
+/// # Returns
+/// The ranges, or an empty vector if 'exclude_file' is not given
+///
+/// # Panics
+/// Panics if 'exclude_file' cannot be opened, or contains a line that is not a valid range
+fn parse_exclude_file(matches: &ArgMatches) -> Vec<DateRange>
+{
+    let inclusive = matches.value_of("range_bounds") != Some("half_open");
+    match matches.value_of("exclude_file") {
+        Some(path_str) => {
+            let file = File::open(path_str)
+                .unwrap_or_else(|e| panic!("failed to open exclude_file=({}) ({})", path_str, e));
+            date_range::parse_date_ranges(BufReader::new(file), inclusive)
+                .unwrap_or_else(|e| panic!("invalid exclude_file=({}), {}", path_str, e))
+        },
+        None => vec![],
+    }
+}
+
+/// Get the 'timeout' `matches` argument (in seconds)
+///
+/// If 'timeout' is 'auto', it is chosen from the distribution of deltas between `datetimes` (see `delta_datetimes::suggest_timeout()`)
+fn parse_timeout(matches: &ArgMatches, datetimes: &[DateTime<FixedOffset>]) -> u64
+{
+    let timeout_str = matches.value_of("timeout").expect("expect argument 'timeout' in `matches`");
+    if timeout_str != "auto" {
+        return timeout_str.parse().unwrap();
+    }
+    let deltas = delta_datetimes::delta_datetimes(&datetimes.to_vec(), false);
+    let suggestion = delta_datetimes::suggest_timeout(&deltas);
+    log::info!("parse_timeout(), timeout=(auto), result=({}), rationale=({})", suggestion.timeout, suggestion.rationale());
+    suggestion.timeout
+}
+
+/// Get the policy for negative deltas (see `delta_datetimes::NegativePolicy`) from the 'negative' `matches` argument (default=clamp)
+///
+/// 'allow_negative' is an alias for 'break'
+///
+/// # Panics
+/// Panics if 'negative' is not a valid policy
+fn parse_negative(matches: &ArgMatches) -> delta_datetimes::NegativePolicy
+{
+    if matches.is_present("allow_negative") {
+        return delta_datetimes::NegativePolicy::Break;
+    }
+    matches.value_of("negative").unwrap_or("clamp")
+        .parse().unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Get the time-accounting model (see `delta_datetimes::Model`) from the 'model' `matches` argument (default=discard)
+///
+/// The seconds earned per datetime by 'credit', and the padding added to each session by 'padded', are given as durations by the 'credit' / 'padding' `matches` arguments (see `relative_datetime::parse_duration_seconds()`)
+///
+/// # Panics
+/// Panics if 'credit' / 'padding' is not a valid duration
+fn parse_model(matches: &ArgMatches) -> delta_datetimes::Model
+{
+    let parse_duration_arg = |name: &str| -> u64 {
+        let duration_str = matches.value_of(name).unwrap_or_else(|| panic!("expect argument '{}' in `matches`", name));
+        relative_datetime::parse_duration_seconds(duration_str)
+            .filter(|x| *x >= 0)
+            .unwrap_or_else(|| panic!("invalid {}=({})", name, duration_str)) as u64
+    };
+    let model_str = matches.value_of("model").unwrap_or("discard");
+    match model_str {
+        "discard" => delta_datetimes::Model::Discard,
+        "capped" => delta_datetimes::Model::Capped,
+        "credit" => delta_datetimes::Model::Credit(parse_duration_arg("credit")),
+        "padded" => delta_datetimes::Model::Padded(parse_duration_arg("padding")),
+        _ => panic!("invalid model=({})", model_str),
+    }
+}
+
+/// Get the `Calendar` datetimes are grouped (and matched against ranges) in, from the 'group_tz' / 'day_start' `matches` arguments
+///
+/// # Panics
+/// Panics if 'group_tz' is not a valid timezone, or 'day_start' is not a valid time-of-day
+fn parse_calendar(matches: &ArgMatches) -> Calendar
+{
+    let tz = matches.value_of("group_tz").map(|tz_str| {
+        TimeZoneSpec::parse(tz_str)
+            .unwrap_or_else(|| panic!("invalid group_tz=({})", tz_str))
+    });
+    let day_start = matches.value_of("day_start").map(|day_start_str| {
+        recurring_filter::parse_time_of_day(day_start_str)
+            .unwrap_or_else(|| panic!("invalid day_start=({})", day_start_str))
+    });
+    Calendar::new(tz).with_day_start(day_start.unwrap_or(NaiveTime::MIN))
+}
+
+/// Get the `Period` given as 'per' `matches` argument
+///
+/// # Panics
+/// Panics if 'per' is not a valid `Period`
+fn parse_period(matches: &ArgMatches) -> Period
+{
+    let per = matches.value_of("per").expect("expected `matches` argument 'per'");
+    per.parse().unwrap_or_else(|e| panic!("{}", e))
 }
 
 /// Get 'deltas' - seconds between each parsed-and-filtered datetimes from given input
 ///
-/// If 'allow_negative' is not specified as `matches` argument, any delta<0 is replaced with 0
+/// Negative deltas are handled as per the 'negative' `matches` argument (see `parse_negative()`), (any delta<0 is replaced with 0 by default, and kept with 'break')
 ///
 /// Getting parsed-and-filtered datetimes from input is handled by `get_datetimes_parsed()`
-/// Difference between datetimes is found by `delta_datetimes::delta_datetimes()`
+/// Difference between datetimes is found by `delta_datetimes::delta_datetimes_with_policy()`
 ///
 /// Returns
 /// `Vec<i64>` list of deltas in seconds
@@ -261,47 +665,75 @@ fn get_datetimes_grouped(matches: &ArgMatches) -> HashMap<String, Vec<DateTime<F
 /// `get_datetimes_parsed_with_strs_and_positions()` may panic if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
 fn get_deltas(matches: &ArgMatches) -> Vec<i64>
 {
-    let allow_negative = matches.is_present("allow_negative");
+    let negative = parse_negative(matches);
     let datetimes_parsed = get_datetimes_parsed(matches);
-    let deltas = delta_datetimes::delta_datetimes(&datetimes_parsed, allow_negative);
-    deltas
-}
-
-fn _get_splits(matches: &ArgMatches) -> Vec<u64>
-{
-    let allow_negative = false;
-    let timeout: u64 = matches.value_of("timeout").expect("expect argument 'timeout' in `matches`")
-        .parse().unwrap();
-    let datetimes_parsed = get_datetimes_parsed(matches);
-    let deltas = delta_datetimes::delta_datetimes(&datetimes_parsed, allow_negative);
-    let splits = delta_datetimes::split_deltas(&deltas, timeout);
-    splits
+    delta_datetimes::delta_datetimes_with_policy(&datetimes_parsed, &negative)
 }
 
 /// Get 'splits' for each interval - sums of continuous 'deltas' where no delta > timeout
 ///
-/// Interval is specified as 'per' `matches` argument, and may be 'y' / 'm' / 'd'
-/// 'timeout' is given as `matches` argument (in seconds)
+/// Interval is specified as 'per' `matches` argument (see `Period`)
+/// 'timeout' is given as `matches` argument (in seconds, or 'auto', see `parse_timeout()`)
+/// If 'clip' is specified, only the part of each delta inside the recurring filter ('time_of_day' / 'weekdays') is counted
+/// If 'apportion' is specified, splits are found on the whole timeline and then divided at period boundaries (by `group_datetimes::group_splits_apportioned()`), instead of grouping datetimes before finding splits
+/// If a 'model' other than 'discard' is specified (see `parse_model()`), splits are the durations of sessions under that model (as per `get_sessions_per_interval()`)
+/// Negative deltas are handled as per the 'negative' `matches` argument (see `parse_negative()`), within each interval (or on the whole timeline if 'apportion' is specified), (by default, counted as 0 without ending the split)
+///
+/// Without 'apportion', totals can differ between 'per' intervals (eg: for out-of-order input, the sum over --per d exceeds that for --per all), because datetimes are grouped before splits are found, so out-of-order datetimes (whose negative deltas are clamped to 0 on the whole timeline) become adjacent to datetimes of the same interval. With 'apportion' (or 'sort') the total is the same for every 'per'.
 ///
 /// Getting grouped parsed-and-filtered datetimes from input is handled by `get_datetimes_grouped()`
 /// Grouping parsed-and-filtered-datetimes is handled by `delta_datetimes::delta_datetimes()`
-/// Getting splits is handled by `delta_datetimes::split_deltas()`
+/// Getting splits is handled by `delta_datetimes::split_datetimes_clipped()`, (the durations of `delta_datetimes::sessions_clipped()`)
 ///
 /// Returns
-/// `HashMap<String, Vec<u64>>` a list of all splits (in seconds) - sums of continuous deltas where delta <= timeout - corresponding to each interval (the intervals are represented as strings, '%Y' / '%Y-%m' / '%Y-%m-%d' depending on interval type)
+/// `HashMap<String, Vec<u64>>` a list of all splits (in seconds) - sums of continuous deltas where delta <= timeout - corresponding to each interval (the intervals are represented as strings, as per `Period::key()`)
 ///
 /// # Panics
 /// Panics if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
 fn get_splits_per_interval(matches: &ArgMatches) -> HashMap<String, Vec<u64>>
 {
-    let allow_negative = false;
-    let timeout: u64 = matches.value_of("timeout").expect("expect argument 'timeout' in `matches`")
-        .parse().unwrap();
-    let datetimes_grouped = get_datetimes_grouped(matches);
+    if parse_model(matches) != delta_datetimes::Model::Discard {
+        let datetimes_with_lines = get_datetimes_parsed_with_lines(matches);
+        let splits_per_interval: HashMap<String, Vec<u64>> = get_sessions_per_interval_from(matches, datetimes_with_lines)
+            .into_iter()
+            .map(|(interval, sessions)| (interval, sessions.iter().map(|x| x.duration).collect()))
+            .collect();
+        log::trace!("get_splits_per_interval(), result=({:?})", splits_per_interval);
+        return splits_per_interval;
+    }
+    let period = parse_period(matches);
+    let calendar = parse_calendar(matches);
+    let recurring_filter = parse_recurring_filter(matches);
+    let clip = matches.is_present("clip") && recurring_filter.is_active();
+    let negative = parse_negative(matches);
+    let datetimes_parsed = get_datetimes_parsed(matches);
+    let timeout = parse_timeout(matches, &datetimes_parsed);
+    let measure = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| -> u64 {
+        if clip {
+            recurring_filter.overlap_seconds(a, b)
+        } else {
+            delta_datetimes::datetime_difference_seconds(*a, *b).max(0) as u64
+        }
+    };
+    if matches.is_present("apportion") {
+        let datetimes_parsed = delta_datetimes::order_datetimes(datetimes_parsed, &negative, |x| *x);
+        let mut splits_per_interval: HashMap<String, Vec<u64>> = HashMap::new();
+        for run in delta_datetimes::split_datetimes_runs(&datetimes_parsed, &negative, |x| *x) {
+            for (interval, splits) in group_datetimes::group_splits_apportioned(&run, timeout, &period, &calendar, measure) {
+                splits_per_interval.entry(interval).or_default().extend(splits);
+            }
+        }
+        log::trace!("get_splits_per_interval(), result=({:?})", splits_per_interval);
+        return splits_per_interval;
+    }
+    let datetimes_grouped = group_datetimes::group_datetimes_with_calendar(&datetimes_parsed, &period, &calendar);
     let mut splits_per_interval = HashMap::new();
     for (interval, datetimes) in &datetimes_grouped {
-        let deltas = delta_datetimes::delta_datetimes(datetimes, allow_negative);
-        let splits = delta_datetimes::split_deltas(&deltas, timeout);
+        let datetimes = delta_datetimes::order_datetimes(datetimes.clone(), &negative, |x| *x);
+        let splits: Vec<u64> = delta_datetimes::split_datetimes_runs(&datetimes, &negative, |x| *x)
+            .into_iter()
+            .flat_map(|run| delta_datetimes::split_datetimes_clipped(&run, timeout, measure))
+            .collect();
         if !splits.is_empty() {
             splits_per_interval.insert(interval.clone(), splits);
         }
@@ -310,15 +742,188 @@ fn get_splits_per_interval(matches: &ArgMatches) -> HashMap<String, Vec<u64>>
     splits_per_interval
 }
 
+/// Get 'sessions' for each interval - splits (as per `get_splits_per_interval()`), with their start/end datetimes and line numbers
+///
+/// Interval is specified as 'per' `matches` argument (see `Period`)
+/// 'timeout' is given as `matches` argument (in seconds, or 'auto', see `parse_timeout()`)
+/// If 'clip' is specified, only the part of each delta inside the recurring filter ('time_of_day' / 'weekdays') is counted toward the duration of a session
+/// If 'apportion' is specified, sessions are found on the whole timeline, and each is listed under the interval containing its start
+/// The duration of each session is that under the 'model' `matches` argument (see `parse_model()`)
+/// Negative deltas are handled as per the 'negative' `matches` argument (see `parse_negative()`), within each interval (or on the whole timeline if 'apportion' is specified)
+///
+/// Getting sessions is handled by `delta_datetimes::sessions_clipped()`, and applying the model by `delta_datetimes::apply_model()`
+///
+/// Returns
+/// `HashMap<String, Vec<delta_datetimes::Session>>` the sessions (with a duration > 0) corresponding to each interval (the intervals are represented as strings, as per `Period::key()`)
+///
+/// # Panics
+/// Panics if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+fn get_sessions_per_interval(matches: &ArgMatches) -> HashMap<String, Vec<delta_datetimes::Session>>
+{
+    let datetimes_with_lines = get_datetimes_parsed_with_lines(matches);
+    get_sessions_per_interval_from(matches, datetimes_with_lines)
+}
+
+/// Get 'sessions' for each interval (as per `get_sessions_per_interval()`) from already parsed-and-filtered datetimes and their line numbers
+fn get_sessions_per_interval_from(matches: &ArgMatches, datetimes_with_lines: Vec<(DateTime<FixedOffset>, usize)>) -> HashMap<String, Vec<delta_datetimes::Session>>
+{
+    let negative = parse_negative(matches);
+    let datetimes_parsed: Vec<DateTime<FixedOffset>> = datetimes_with_lines.iter().map(|(x, _)| *x).collect();
+    let timeout = parse_timeout(matches, &datetimes_parsed);
+    let model = parse_model(matches);
+    let period = parse_period(matches);
+    let calendar = parse_calendar(matches);
+    let recurring_filter = parse_recurring_filter(matches);
+    let clip = matches.is_present("clip") && recurring_filter.is_active();
+    let measure = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| -> u64 {
+        if clip {
+            recurring_filter.overlap_seconds(a, b)
+        } else {
+            delta_datetimes::datetime_difference_seconds(*a, *b).max(0) as u64
+        }
+    };
+    let sessions_of = |datetimes_with_lines: Vec<(DateTime<FixedOffset>, usize)>| -> Vec<delta_datetimes::Session> {
+        let datetimes_with_lines = delta_datetimes::order_datetimes(datetimes_with_lines, &negative, |x| x.0);
+        delta_datetimes::split_datetimes_runs(&datetimes_with_lines, &negative, |x| x.0)
+            .iter()
+            .flat_map(|run| delta_datetimes::sessions_clipped(run, timeout, measure))
+            .collect()
+    };
+    let mut sessions_per_interval: HashMap<String, Vec<delta_datetimes::Session>> = HashMap::new();
+    if matches.is_present("apportion") {
+        let mut sessions = sessions_of(datetimes_with_lines);
+        delta_datetimes::apply_model(&mut sessions, &model, timeout);
+        for session in sessions {
+            sessions_per_interval.entry(calendar.key(&session.start, &period)).or_default().push(session);
+        }
+    } else {
+        let mut datetimes_with_lines_grouped: HashMap<String, Vec<(DateTime<FixedOffset>, usize)>> = HashMap::new();
+        for (datetime, line) in datetimes_with_lines {
+            datetimes_with_lines_grouped.entry(calendar.key(&datetime, &period)).or_default().push((datetime, line));
+        }
+        for (interval, datetimes_with_lines) in datetimes_with_lines_grouped {
+            let mut sessions = sessions_of(datetimes_with_lines);
+            delta_datetimes::apply_model(&mut sessions, &model, timeout);
+            sessions_per_interval.insert(interval, sessions);
+        }
+    }
+    sessions_per_interval.values_mut().for_each(|sessions| sessions.retain(|session| session.duration > 0));
+    sessions_per_interval.retain(|_, sessions| !sessions.is_empty());
+    log::trace!("get_sessions_per_interval(), result=({:?})", sessions_per_interval);
+    sessions_per_interval
+}
+
+/// Get summary statistics of deltas and of session lengths for each interval
+///
+/// Interval is specified as 'per' `matches` argument (see `Period`)
+/// 'timeout' is given as `matches` argument (in seconds, or 'auto', see `parse_timeout()`)
+/// Deltas are between consecutive datetimes in each interval, with negative deltas handled as per the 'negative' `matches` argument (see `parse_negative()`)
+/// Sessions are found by `get_sessions_per_interval_from()`
+///
+/// Statistics are calculated by `delta_datetimes::stats()`
+///
+/// Returns
+/// `HashMap<String, Vec<(String, Option<delta_datetimes::Stats>)>>` the statistics of 'deltas' and 'sessions' (`None` if there are none) for each interval containing datetimes (the intervals are represented as strings, as per `Period::key()`)
+///
+/// # Panics
+/// Panics if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+#[allow(clippy::type_complexity)]
+fn get_stats_per_interval(matches: &ArgMatches) -> HashMap<String, Vec<(String, Option<delta_datetimes::Stats>)>>
+{
+    let negative = parse_negative(matches);
+    let period = parse_period(matches);
+    let calendar = parse_calendar(matches);
+    let datetimes_with_lines = get_datetimes_parsed_with_lines(matches);
+    let datetimes_parsed: Vec<DateTime<FixedOffset>> = datetimes_with_lines.iter().map(|(x, _)| *x).collect();
+    let datetimes_grouped = group_datetimes::group_datetimes_with_calendar(&datetimes_parsed, &period, &calendar);
+    let sessions_per_interval = get_sessions_per_interval_from(matches, datetimes_with_lines);
+    let mut result: HashMap<String, Vec<(String, Option<delta_datetimes::Stats>)>> = HashMap::new();
+    for (interval, datetimes) in &datetimes_grouped {
+        let deltas: Vec<f64> = delta_datetimes::delta_datetimes_with_policy(datetimes, &negative).iter()
+            .map(|&x| x as f64)
+            .collect();
+        let durations: Vec<f64> = sessions_per_interval.get(interval)
+            .map(|sessions| sessions.iter().map(|x| x.duration as f64).collect())
+            .unwrap_or_default();
+        result.insert(interval.to_string(), vec![
+            ("deltas".to_string(), delta_datetimes::stats(&deltas)),
+            ("sessions".to_string(), delta_datetimes::stats(&durations)),
+        ]);
+    }
+    log::trace!("get_stats_per_interval(), result=({:?})", result);
+    result
+}
+
+/// Get anomalies - changes of UTC offset, and backwards jumps, between consecutive parsed-and-filtered datetimes from given input
+///
+/// Backwards jumps within 'dst_tolerance' of one hour are DST repeats, and other backwards jumps longer than 'min_skew' are clock skew (both given as durations, see `relative_datetime::parse_duration_seconds()`)
+///
+/// Finding anomalies is handled by `delta_datetimes::anomalies()`
+///
+/// Returns
+/// `Vec<delta_datetimes::Anomaly>` the anomalies, in the order of the input
+///
+/// # Panics
+/// Panics if 'dst_tolerance' / 'min_skew' are not valid durations, reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+fn get_anomalies(matches: &ArgMatches) -> Vec<delta_datetimes::Anomaly>
+{
+    let parse_duration = |name: &str| -> u64 {
+        let duration_str = matches.value_of(name).unwrap_or_else(|| panic!("expect argument '{}' in `matches`", name));
+        relative_datetime::parse_duration_seconds(duration_str)
+            .filter(|x| *x >= 0)
+            .unwrap_or_else(|| panic!("invalid {}=({})", name, duration_str)) as u64
+    };
+    let dst_tolerance = parse_duration("dst_tolerance");
+    let min_skew = parse_duration("min_skew");
+    let datetimes_with_lines = get_datetimes_parsed_with_lines(matches);
+    delta_datetimes::anomalies(&datetimes_with_lines, dst_tolerance, min_skew)
+}
+
+/// Get 'gaps' - deltas > timeout between parsed-and-filtered datetimes from given input
+///
+/// 'timeout' is given as `matches` argument (in seconds, or 'auto', see `parse_timeout()`)
+/// If 'min_gap' is specified (as a duration, see `relative_datetime::parse_duration_seconds()`), shorter gaps are excluded
+/// If 'top' is specified, only the given number of longest gaps are included, longest first
+///
+/// Finding gaps is handled by `delta_datetimes::gaps()`
+///
+/// Returns
+/// `Vec<delta_datetimes::Gap>` the gaps, in the order of the input (unless 'top' is specified)
+///
+/// # Panics
+/// Panics if 'min_gap' is not a valid duration, reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
+fn get_gaps(matches: &ArgMatches) -> Vec<delta_datetimes::Gap>
+{
+    let min_gap = matches.value_of("min_gap").map(|min_gap_str| {
+        relative_datetime::parse_duration_seconds(min_gap_str)
+            .filter(|x| *x >= 0)
+            .unwrap_or_else(|| panic!("invalid min_gap=({})", min_gap_str)) as u64
+    });
+    let top: Option<usize> = matches.value_of("top").map(|x| x.parse().unwrap());
+    let datetimes_with_lines = get_datetimes_parsed_with_lines(matches);
+    let datetimes_parsed: Vec<DateTime<FixedOffset>> = datetimes_with_lines.iter().map(|(x, _)| *x).collect();
+    let timeout = parse_timeout(matches, &datetimes_parsed);
+    let mut gaps = delta_datetimes::gaps(&datetimes_with_lines, timeout);
+    if let Some(min_gap) = min_gap {
+        gaps.retain(|gap| gap.duration >= min_gap);
+    }
+    if let Some(top) = top {
+        gaps.sort_by_key(|gap| std::cmp::Reverse(gap.duration));
+        gaps.truncate(top);
+    }
+    log::trace!("get_gaps(), result=({:?})", gaps);
+    gaps
+}
+
 /// Get the sum of splits for each interval
 ///
-/// Interval is specified as 'per' `matches` argument, and may be 'y' / 'm' / 'd'
-/// 'timeout' is given as `matches` argument (in seconds)
+/// Interval is specified as 'per' `matches` argument (see `Period`)
+/// 'timeout' is given as `matches` argument (in seconds, or 'auto', see `parse_timeout()`)
 ///
 /// Getting splits-per-interval is handled by `get_splits_per_interval()`
 /// 
 /// Returns
-/// `HashMap<String, u64>` sum of all splits (in seconds) - sums of continuous deltas where delta <= timeout - corresponding to each interval (the intervals are represented as strings, '%Y' / '%Y-%m' / '%Y-%m-%d' depending on interval type)
+/// `HashMap<String, u64>` sum of all splits (in seconds) - sums of continuous deltas where delta <= timeout - corresponding to each interval (the intervals are represented as strings, as per `Period::key()`)
 ///
 /// # Panics
 /// `get_datetimes_parsed_with_strs_and_positions()` may panic if reading input fails, parsing a matched datetime fails, a datetime is in the future from now and 'no_future' was specified, or datetimes located are out of order and 'no_unsorted' was specified
@@ -357,94 +962,160 @@ fn filter_datetimes_valid_indexes(datetimes: &[DateTime<FixedOffset>], filter_st
     }).collect()
 }
 
-/// Checks if any `DateTime<FixedOffset>` values in the provided vector are in the future and panics if any are found.
+/// Filters a slice of `DateTime<FixedOffset>` values based on a list of `DateRange`
 ///
-/// This function compares each datetime in the input vector to the current datetime adjusted to the corresponding timezone. If any datetimes are detected to be in the future, the function panics and lists those future datetimes.
+/// A datetime passes the filter if it is contained by any of the ranges (the selection is the union of the ranges). If no ranges are given, every datetime passes.
 ///
 /// # Arguments
-/// * `datetimes`: A reference to a `Vec<DateTime<FixedOffset>>` to be checked for future datetimes.
+/// * `datetimes`: A slice of `DateTime<FixedOffset>` values to be filtered.
+/// * `ranges`: The `DateRange`s datetimes must fall inside.
+/// * `calendar`: The `Calendar` in which the date of each datetime is taken.
+///
+/// # Returns
+/// A `Vec<bool>` where each element indicates whether the corresponding datetime in `datetimes` passed the filter.
+fn filter_datetimes_in_ranges_indexes(datetimes: &[DateTime<FixedOffset>], ranges: &[DateRange], calendar: &Calendar) -> Vec<bool>
+{
+    datetimes.iter().map(|datetime| {
+        ranges.is_empty() || ranges.iter().any(|range| range.contains_in(datetime, calendar))
+    }).collect()
+}
+
+/// Checks if any of the provided datetimes are in the future and panics if any are found.
+///
+/// This function compares each datetime to the current datetime given by `clock` (see `validate::check_future()`). If any datetimes are detected to be in the future, the function panics and lists those future datetimes, with their lines and columns.
+///
+/// # Arguments
+/// * `located`: The datetimes to be checked for future datetimes, with their locations in input.
+/// * `clock`: The `Clock` providing the current datetime.
 ///
 /// # Panics
-/// Panics if any datetimes in the provided vector are in the future, listing the offending datetimes.
-fn reject_datetimes_future(datetimes: &Vec<DateTime<FixedOffset>>)
-{
-    let mut future_datetimes = Vec::new();
-    for date in datetimes {
-        let now_in_timezone = Utc::now().with_timezone(date.offset());
-        if *date > now_in_timezone {
-            future_datetimes.push(*date);
-        }
-    }
-    if !future_datetimes.is_empty() {
-        panic!("reject future_datetimes=({:?})", future_datetimes);
+/// Panics if any of the provided datetimes are in the future, listing the offending datetimes.
+fn reject_datetimes_future(located: &[LocatedDatetime], clock: &dyn Clock)
+{
+    let issues = validate::check_future(located, &clock.now());
+    if !issues.is_empty() {
+        panic!("reject future_datetimes=({})", issues.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "));
     }
 }
 
-/// Checks if the `DateTime<FixedOffset>` values in the provided vector are out of ascending order and panics if any are found.
+/// Checks if the provided datetimes are out of ascending order and panics if any are found.
 ///
-/// The function iterates through the provided vector and checks if any datetime is earlier than its predecessor. If any out-of-order datetimes are detected, the function panics and lists those datetimes.
+/// The function checks if any datetime is earlier than its predecessor (see `validate::check_unsorted()`). If any out-of-order datetimes are detected, the function panics and lists those datetimes, with their lines and columns.
 ///
 /// # Arguments
-/// * `datetimes`: A reference to a `Vec<DateTime<FixedOffset>>` to be checked for ordering.
+/// * `located`: The datetimes to be checked for ordering, with their locations in input.
 ///
 /// # Panics
-/// Panics if any consecutive datetimes in the provided vector are out of ascending order, listing the offending datetimes.
-fn reject_datetimes_unsorted(datetimes: &Vec<DateTime<FixedOffset>>)
+/// Panics if any consecutive datetimes are out of ascending order, listing the offending datetimes.
+fn reject_datetimes_unsorted(located: &[LocatedDatetime])
 {
-    let mut out_of_order_datetimes = Vec::new();
-    for i in 1..datetimes.len() {
-        if datetimes[i] < datetimes[i-1] {
-            out_of_order_datetimes.push(datetimes[i]);
-        }
-    }
-    if !out_of_order_datetimes.is_empty() {
-        panic!("reject out_of_order_datetimes=({:?})", out_of_order_datetimes);
+    let issues = validate::check_unsorted(located);
+    if !issues.is_empty() {
+        panic!("reject out_of_order_datetimes=({})", issues.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "));
     }
 }
 
 /// Parses `filter_start` and `filter_end` arguments from `ArgMatches` into `DateTime<FixedOffset>` options.
 ///
-/// The function checks the presence of `filter_start` and `filter_end` arguments in the given `ArgMatches`. If present and valid, they are parsed into `DateTime<FixedOffset>`. If invalid, the function will panic with the offending argument.
+/// The function checks the presence of `filter_start` and `filter_end` arguments in the given `ArgMatches`. If present and valid, they are parsed into `DateTime<FixedOffset>` by `parse_filter_bound()`. If invalid, the function will panic with the offending argument.
+/// If `last` is given (a non-negative duration), `filter_start` is the current time minus that duration.
 ///
 /// # Arguments
 /// * `matches`: A reference to `ArgMatches` which might contain `filter_start` and `filter_end` arguments.
+/// * `tz`: The timezone assumed for arguments given without an offset.
+/// * `clock`: The `Clock` providing the current datetime.
 ///
 /// # Returns
 /// A tuple containing `Option<DateTime<FixedOffset>>` values for `filter_start` and `filter_end`, respectively.
 ///
 /// # Panics
-/// Panics if `filter_start` or `filter_end` are present in `matches` but are invalid datetime strings.
-fn parse_filter_start_end(matches: &ArgMatches) -> (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>)
-{
-    let filter_start = {
-        if matches.is_present("filter_start") {
-            let filter_start_str = matches.value_of("filter_start").unwrap();
-            let filter_start = parse_datetime::parse_datetime(filter_start_str);
-            if filter_start.is_none() {
-                panic!("invalid filter_start=({})", filter_start_str);
-            } else {
-                filter_start
-            }
-        } else {
-            None
-        }
-    };
-    let filter_end = {
-        if matches.is_present("filter_end") {
-            let filter_end_str = matches.value_of("filter_end").unwrap();
-            let filter_end = parse_datetime::parse_datetime(filter_end_str);
-            if filter_end.is_none() {
-                panic!("invalid filter_end=({})", filter_end_str);
-            } else {
-                filter_end 
-            }
-        } else {
-            None
-        }
-    };
+/// Panics if `filter_start` or `filter_end` are present in `matches` but are invalid datetime strings, or `last` is present but is not a valid (non-negative) duration.
+fn parse_filter_start_end(matches: &ArgMatches, tz: &TimeZoneSpec, clock: &dyn Clock) -> (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>)
+{
+    let filter_start = matches.value_of("filter_start").map(|filter_start_str| {
+        parse_filter_bound(filter_start_str, false, tz, clock)
+            .unwrap_or_else(|| panic!("invalid filter_start=({})", filter_start_str))
+    });
+    let filter_start = filter_start.or_else(|| {
+        matches.value_of("last").map(|last_str| {
+            relative_datetime::parse_duration_seconds(last_str)
+                .filter(|x| *x >= 0)
+                .and_then(chrono::Duration::try_seconds)
+                .and_then(|last| clock.now().checked_sub_signed(last))
+                .unwrap_or_else(|| panic!("invalid last=({})", last_str))
+        })
+    });
+    let filter_end = matches.value_of("filter_end").map(|filter_end_str| {
+        parse_filter_bound(filter_end_str, true, tz, clock)
+            .unwrap_or_else(|| panic!("invalid filter_end=({})", filter_end_str))
+    });
     (filter_start, filter_end)
 }
 
+/// Parses each 'range' argument from `ArgMatches` into a `DateRange`
+///
+/// Ranges are parsed by `DateRange::parse()`, with END included in the range unless 'range_bounds' is 'half_open'
+///
+/// # Panics
+/// Panics if any 'range' is not a valid range
+fn parse_ranges(matches: &ArgMatches) -> Vec<DateRange>
+{
+    let inclusive = matches.value_of("range_bounds") != Some("half_open");
+    match matches.values_of("range") {
+        Some(ranges_strs) => ranges_strs
+            .map(|range_str| {
+                DateRange::parse(range_str, inclusive)
+                    .unwrap_or_else(|| panic!("invalid range=({})", range_str))
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Parses the recurring filter arguments 'time_of_day', 'weekdays', 'filter_tz', and 'recurring_invert' from `ArgMatches` into a `RecurringFilter`
+///
+/// # Panics
+/// Panics if any of the arguments are present but invalid
+fn parse_recurring_filter(matches: &ArgMatches) -> RecurringFilter
+{
+    let time_of_day = matches.value_of("time_of_day").map(|time_of_day_str| {
+        recurring_filter::parse_time_of_day_range(time_of_day_str)
+            .unwrap_or_else(|| panic!("invalid time_of_day=({})", time_of_day_str))
+    });
+    let weekdays = matches.value_of("weekdays").map(|weekdays_str| {
+        recurring_filter::parse_weekdays(weekdays_str)
+            .unwrap_or_else(|| panic!("invalid weekdays=({})", weekdays_str))
+    });
+    let tz = matches.value_of("filter_tz").map(|tz_str| {
+        TimeZoneSpec::parse(tz_str)
+            .unwrap_or_else(|| panic!("invalid filter_tz=({})", tz_str))
+    });
+    let invert = matches.is_present("recurring_invert");
+    RecurringFilter { time_of_day, weekdays, tz, invert, }
+}
+
+/// Parses a single filter bound into a `DateTime<FixedOffset>`
+///
+/// Partial datetimes ('YYYY' / 'YYYY-MM' / 'YYYY-MM-DD' / 'YYYY-MM-DDTHH') are expanded by `date_range::parse_partial_datetime_bounds()` to their first second (for a start bound) or last second (for an end bound), in the timezone `tz`, with the offset in effect on that date. Relative expressions ('-7d' / 'today' / 'last month' / 'ytd') are expanded the same way by `relative_datetime::parse_relative_bounds()`. Anything else is parsed as a full datetime by `parse_datetime::parse_datetime_with_tz()`.
+///
+/// # Arguments
+/// * `bound_str`: The filter argument.
+/// * `is_end`: Whether the bound is an end bound.
+/// * `tz`: The timezone assumed for arguments given without an offset.
+/// * `clock`: The `Clock` providing the current datetime (for relative expressions).
+///
+/// # Returns
+/// `Some(DateTime<FixedOffset>)` if `bound_str` could be parsed, otherwise `None`
+fn parse_filter_bound(bound_str: &str, is_end: bool, tz: &TimeZoneSpec, clock: &dyn Clock) -> Option<DateTime<FixedOffset>>
+{
+    let bounds = date_range::parse_partial_datetime_bounds(bound_str)
+        .map(|(first, last)| (tz.from_local(&first), tz.from_local(&last)))
+        .or_else(|| relative_datetime::parse_relative_bounds(bound_str, clock, tz));
+    match bounds {
+        Some((first, last)) => Some(if is_end { last } else { first }),
+        None => parse_datetime::parse_datetime_with_tz(bound_str, tz),
+    }
+}
 
 //  Tests: filter_datetimes / reject_datetimes
 //  (placing these tests out-of-the-way in 'subcommands/tests.rs' works, but produces error in vim-YCM)
@@ -452,6 +1123,7 @@ fn parse_filter_start_end(matches: &ArgMatches) -> (Option<DateTime<FixedOffset>
 mod tests {
     //  {{{
     use super::*;
+    use crate::clock::FixedClock;
     use std::panic;
 
     #[test]
@@ -502,9 +1174,104 @@ mod tests {
         assert_eq!(result, vec![false, true, false]);
     }
 
+    #[test]
+    fn filter_datetimes_in_ranges_indexes_none() {
+        let datetimes: Vec<DateTime<FixedOffset>> = vec![
+            DateTime::parse_from_rfc3339("2023-05-27T00:00:00+00:00").unwrap(),
+            DateTime::parse_from_rfc3339("2023-06-27T00:00:00+00:00").unwrap(),
+        ];
+        let result = filter_datetimes_in_ranges_indexes(&datetimes, &[], &Calendar::default());
+        assert_eq!(result, vec![true, true]);
+    }
+
+    #[test]
+    fn filter_datetimes_in_ranges_indexes_union() {
+        let datetimes: Vec<DateTime<FixedOffset>> = vec![
+            DateTime::parse_from_rfc3339("2023-05-27T00:00:00+00:00").unwrap(),
+            DateTime::parse_from_rfc3339("2023-06-27T00:00:00+00:00").unwrap(),
+            DateTime::parse_from_rfc3339("2023-07-27T00:00:00+00:00").unwrap(),
+            DateTime::parse_from_rfc3339("2023-08-27T00:00:00+00:00").unwrap(),
+        ];
+        let ranges = vec![
+            DateRange::parse("..2023-05", true).unwrap(),
+            DateRange::parse("2023-07-01..2023-08-01", true).unwrap(),
+        ];
+        let result = filter_datetimes_in_ranges_indexes(&datetimes, &ranges, &Calendar::default());
+        assert_eq!(result, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn parse_filter_bound_datetime() {
+        let clock = FixedClock::new(DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap());
+        let tz = TimeZoneSpec::parse("+10:00").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2023-05-14T13:10:00+00:00").unwrap();
+        assert_eq!(parse_filter_bound("2023-05-14T13:10:00+00:00", false, &tz, &clock), Some(expected));
+        assert_eq!(parse_filter_bound("2023-05-14T13:10:00+00:00", true, &tz, &clock), Some(expected));
+        let expected = DateTime::parse_from_rfc3339("2023-05-14T13:10:00+10:00").unwrap();
+        assert_eq!(parse_filter_bound("2023-05-14T13:10:00", true, &tz, &clock), Some(expected));
+    }
+
+    #[test]
+    fn parse_filter_bound_partial() {
+        let clock = FixedClock::new(DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap());
+        let tz = TimeZoneSpec::parse("+10:00").unwrap();
+        let checks = vec![
+            ("2023", "2023-01-01T00:00:00+10:00", "2023-12-31T23:59:59+10:00"),
+            ("2023-02", "2023-02-01T00:00:00+10:00", "2023-02-28T23:59:59+10:00"),
+            ("2023-05-14", "2023-05-14T00:00:00+10:00", "2023-05-14T23:59:59+10:00"),
+            ("2023-05-14T13", "2023-05-14T13:00:00+10:00", "2023-05-14T13:59:59+10:00"),
+            ("2023-05-14 13", "2023-05-14T13:00:00+10:00", "2023-05-14T13:59:59+10:00"),
+        ];
+        for (bound_str, start, end) in checks {
+            assert_eq!(parse_filter_bound(bound_str, false, &tz, &clock), Some(DateTime::parse_from_rfc3339(start).unwrap()));
+            assert_eq!(parse_filter_bound(bound_str, true, &tz, &clock), Some(DateTime::parse_from_rfc3339(end).unwrap()));
+        }
+        assert_eq!(parse_filter_bound("2023-13", false, &tz, &clock), None);
+        assert_eq!(parse_filter_bound("invalid", true, &tz, &clock), None);
+    }
+
+    #[test]
+    fn parse_filter_bound_partial_dst() {
+        let checks = vec![
+            ("2023-01", "2023-01-01T00:00:00+11:00", "2023-01-31T23:59:59+11:00"),
+            ("2023-06", "2023-06-01T00:00:00+10:00", "2023-06-30T23:59:59+10:00"),
+            ("2023-01-15T13:10:00", "2023-01-15T13:10:00+11:00", "2023-01-15T13:10:00+11:00"),
+        ];
+        let tz = TimeZoneSpec::parse("Australia/Sydney").unwrap();
+        for now in ["2023-01-15T12:00:00+11:00", "2023-06-15T12:00:00+10:00"] {
+            let clock = FixedClock::new(DateTime::parse_from_rfc3339(now).unwrap());
+            for (bound_str, start, end) in &checks {
+                assert_eq!(parse_filter_bound(bound_str, false, &tz, &clock), Some(DateTime::parse_from_rfc3339(start).unwrap()));
+                assert_eq!(parse_filter_bound(bound_str, true, &tz, &clock), Some(DateTime::parse_from_rfc3339(end).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_filter_bound_relative() {
+        let clock = FixedClock::new(DateTime::parse_from_rfc3339("2023-05-24T12:00:00+10:00").unwrap());
+        let tz = TimeZoneSpec::parse("+10:00").unwrap();
+        let checks = vec![
+            ("-7d", "2023-05-17T12:00:00+10:00", "2023-05-17T12:00:00+10:00"),
+            ("today", "2023-05-24T00:00:00+10:00", "2023-05-24T23:59:59+10:00"),
+            ("this week", "2023-05-22T00:00:00+10:00", "2023-05-28T23:59:59+10:00"),
+            ("last month", "2023-04-01T00:00:00+10:00", "2023-04-30T23:59:59+10:00"),
+            ("ytd", "2023-01-01T00:00:00+10:00", "2023-05-24T12:00:00+10:00"),
+        ];
+        for (bound_str, start, end) in checks {
+            assert_eq!(parse_filter_bound(bound_str, false, &tz, &clock), Some(DateTime::parse_from_rfc3339(start).unwrap()));
+            assert_eq!(parse_filter_bound(bound_str, true, &tz, &clock), Some(DateTime::parse_from_rfc3339(end).unwrap()));
+        }
+    }
+
+    fn locate(datetimes: &[DateTime<FixedOffset>]) -> Vec<LocatedDatetime> {
+        datetimes.iter().enumerate().map(|(i, &datetime)| LocatedDatetime { text: datetime.to_rfc3339(), datetime, line: i+1, position: 0, }).collect()
+    }
+
     #[test]
     fn reject_datetimes_future_no_future_dates() {
-        let now = Utc::now();
+        let now = DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap();
+        let clock = FixedClock::new(now);
         let date_strings = vec![
             now.to_rfc3339(),
             (now - chrono::Duration::days(1)).to_rfc3339(),
@@ -514,13 +1281,14 @@ mod tests {
             .map(|s| DateTime::parse_from_rfc3339(&s).unwrap())
             .collect();
 
-        let result = panic::catch_unwind(|| reject_datetimes_future(&dates));
+        let result = panic::catch_unwind(|| reject_datetimes_future(&locate(&dates), &clock));
         assert!(result.is_ok());
     }
 
     #[test]
     fn reject_datetimes_future_with_future_dates() {
-        let now = Utc::now();
+        let now = DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap();
+        let clock = FixedClock::new(now);
         let date_strings = vec![
             now.to_rfc3339(),
             (now - chrono::Duration::days(1)).to_rfc3339(),
@@ -530,7 +1298,22 @@ mod tests {
             .map(|s| DateTime::parse_from_rfc3339(&s).unwrap())
             .collect();
 
-        let result = panic::catch_unwind(|| reject_datetimes_future(&dates));
+        let result = panic::catch_unwind(|| reject_datetimes_future(&locate(&dates), &clock));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reject_datetimes_future_with_future_dates_other_offset() {
+        let now = DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap();
+        let clock = FixedClock::new(now);
+        let dates: Vec<DateTime<FixedOffset>> = vec![
+            DateTime::parse_from_rfc3339("2023-05-27T01:59:59+00:00").unwrap(),
+            DateTime::parse_from_rfc3339("2023-05-27T02:00:01+00:00").unwrap(), // future date
+        ];
+
+        let result = panic::catch_unwind(|| reject_datetimes_future(&locate(&dates[..1]), &clock));
+        assert!(result.is_ok());
+        let result = panic::catch_unwind(|| reject_datetimes_future(&locate(&dates), &clock));
         assert!(result.is_err());
     }
 
@@ -546,7 +1329,7 @@ mod tests {
             .map(|s| DateTime::parse_from_rfc3339(s).unwrap())
             .collect();
 
-        let result = panic::catch_unwind(|| reject_datetimes_unsorted(&dates));
+        let result = panic::catch_unwind(|| reject_datetimes_unsorted(&locate(&dates)));
         assert!(result.is_ok());
     }
 
@@ -561,9 +1344,49 @@ mod tests {
             .map(|s| DateTime::parse_from_rfc3339(s).unwrap())
             .collect();
 
-        let result = panic::catch_unwind(|| reject_datetimes_unsorted(&dates));
+        let result = panic::catch_unwind(|| reject_datetimes_unsorted(&locate(&dates)));
         assert!(result.is_err());
     }
+
+    #[test]
+    #[should_panic(expected = "reject out_of_order_datetimes=(3:1: unsorted: 2023-05-27T10:10:00+00:00 (before 2023-05-27T11:10:00+00:00 on line 2))")]
+    fn reject_datetimes_unsorted_reports_lines() {
+        let dates: Vec<DateTime<FixedOffset>> = ["2023-05-27T09:10:00+00:00", "2023-05-27T11:10:00+00:00", "2023-05-27T10:10:00+00:00"].iter()
+            .map(|s| DateTime::parse_from_rfc3339(s).unwrap())
+            .collect();
+        reject_datetimes_unsorted(&locate(&dates));
+    }
+
+    #[test]
+    fn get_interval_keys_span_from_keys() {
+        let keys: Vec<String> = ["2023-05-14T22", "2023-05-15T01"].iter().map(|x| x.to_string()).collect();
+        let result = get_interval_keys_span(&Period::Hour, &keys, &[]);
+        assert_eq!(result, vec!["2023-05-14T22", "2023-05-14T23", "2023-05-15T00", "2023-05-15T01"]);
+        let keys: Vec<String> = ["2023-W02", "2022-W52"].iter().map(|x| x.to_string()).collect();
+        let result = get_interval_keys_span(&Period::Week, &keys, &[]);
+        assert_eq!(result, vec!["2022-W52", "2023-W01", "2023-W02"]);
+        assert!(get_interval_keys_span(&Period::Day, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn get_interval_keys_span_from_ranges() {
+        let keys: Vec<String> = ["2023-03", "2023-05"].iter().map(|x| x.to_string()).collect();
+        let ranges = vec![
+            DateRange::parse("..2023-01", true).unwrap(),
+            DateRange::parse("2023-04..", true).unwrap(),
+        ];
+        let result = get_interval_keys_span(&Period::Month, &keys, &ranges);
+        assert_eq!(result, vec!["2023-04", "2023-05"]);
+        let ranges = vec![DateRange::parse("2022-12..2023-01", true).unwrap()];
+        let result = get_interval_keys_span(&Period::Quarter, &[], &ranges);
+        assert_eq!(result, vec!["2022-Q4", "2023-Q1"]);
+    }
+
+    #[test]
+    fn get_interval_keys_span_cyclic() {
+        assert_eq!(get_interval_keys_span(&Period::HourOfDay, &[], &[]).len(), 24);
+        assert_eq!(get_interval_keys_span(&Period::All, &[], &[]), vec!["all"]);
+    }
 }
 //  }}}
 
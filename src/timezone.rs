@@ -0,0 +1,128 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use clap::ArgMatches;
+
+/// Timezone given as an argument, either an IANA name (eg: 'Australia/Melbourne') or a fixed offset (eg: '+1000' / '+10:00' / 'UTC')
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZoneSpec {
+    Named(Tz),
+    Fixed(FixedOffset),
+    /// The local timezone of the system
+    Local,
+}
+
+impl TimeZoneSpec
+{
+    /// Parses an IANA timezone name or a fixed offset
+    ///
+    /// # Examples
+    /// ```
+    /// use datetimescan::timezone::TimeZoneSpec;
+    /// assert!(TimeZoneSpec::parse("Australia/Melbourne").is_some());
+    /// assert!(TimeZoneSpec::parse("+1000").is_some());
+    /// assert!(TimeZoneSpec::parse("-05:30").is_some());
+    /// assert!(TimeZoneSpec::parse("UTC").is_some());
+    /// assert!(TimeZoneSpec::parse("Mars/Olympus_Mons").is_none());
+    /// ```
+    ///
+    /// # Returns
+    /// `Some(TimeZoneSpec)` if `s` could be parsed, otherwise `None`
+    pub fn parse(s: &str) -> Option<TimeZoneSpec>
+    {
+        let s = s.trim();
+        if let Some(offset) = parse_offset(s) {
+            return Some(TimeZoneSpec::Fixed(offset));
+        }
+        s.parse::<Tz>().ok().map(TimeZoneSpec::Named)
+    }
+
+    /// Get `datetime` as a naive local datetime in this timezone
+    pub fn to_local(&self, datetime: &DateTime<FixedOffset>) -> NaiveDateTime
+    {
+        match self {
+            TimeZoneSpec::Named(tz) => datetime.with_timezone(tz).naive_local(),
+            TimeZoneSpec::Fixed(offset) => datetime.with_timezone(offset).naive_local(),
+            TimeZoneSpec::Local => datetime.with_timezone(&Local).naive_local(),
+        }
+    }
+
+    /// Get the datetime at which it is the naive local datetime `local` in this timezone
+    ///
+    /// Where `local` is ambiguous (clocks going back) the earlier is returned, and where it does not exist (clocks going forward) the first datetime after the gap is returned
+    pub fn from_local(&self, local: &NaiveDateTime) -> DateTime<FixedOffset>
+    {
+        match self {
+            TimeZoneSpec::Named(tz) => resolve_local(tz, local),
+            TimeZoneSpec::Fixed(offset) => offset.from_local_datetime(local).unwrap(),
+            TimeZoneSpec::Local => resolve_local(&Local, local),
+        }
+    }
+}
+
+/// Get the datetime at which it is the naive local datetime `local` in `tz`, with the offset in effect at that datetime
+///
+/// Where `local` is ambiguous (clocks going back) the earlier is returned, and where it does not exist (clocks going forward) the first datetime after the gap is returned
+///
+/// # Example
+/// ```
+/// use chrono::{DateTime, NaiveDateTime};
+/// use datetimescan::timezone::resolve_local;
+/// let local = NaiveDateTime::parse_from_str("2023-10-01T02:30:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+/// let result = resolve_local(&chrono_tz::Australia::Sydney, &local);
+/// assert_eq!(result, DateTime::parse_from_rfc3339("2023-10-01T03:00:00+11:00").unwrap());
+/// ```
+pub fn resolve_local<Tz: TimeZone>(tz: &Tz, local: &NaiveDateTime) -> DateTime<FixedOffset>
+{
+    let mut local = *local;
+    loop {
+        if let Some(datetime) = tz.from_local_datetime(&local).earliest() {
+            return datetime.with_timezone(&datetime.offset().fix());
+        }
+        local += chrono::Duration::minutes(1);
+    }
+}
+
+/// Parses a fixed offset given as 'UTC' / 'Z' / '+HHMM' / '+HH:MM' / '+HH'
+fn parse_offset(s: &str) -> Option<FixedOffset>
+{
+    if s.eq_ignore_ascii_case("utc") || s == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+    let sign = match s.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = s[1..].replace(':', "");
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes): (i32, i32) = match digits.len() {
+        2 => (digits.parse().ok()?, 0),
+        4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+        _ => return None,
+    };
+    if minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Get the timezone assumed for datetimes given without an offset, as specified by 'assumetz' in `matches`
+///
+/// If 'assumetz' is not given (as an argument, or by the environment variable 'DATETIMESCAN_ASSUMETZ'), the local timezone is assumed
+///
+/// # Panics
+/// Panics if 'assumetz' is given but is not a valid timezone
+pub fn get_assumed_timezone(matches: &ArgMatches) -> TimeZoneSpec
+{
+    match matches.value_of("assumetz") {
+        Some(tz_str) => TimeZoneSpec::parse(tz_str)
+            .unwrap_or_else(|| panic!("invalid assumetz=({})", tz_str)),
+        None => TimeZoneSpec::Local,
+    }
+}
@@ -0,0 +1,244 @@
+//  vim-modelines:  {{{3
+//  vim: set tabstop=4 modeline modelines=10:
+//  vim: set foldlevel=2 foldcolumn=2 foldmethod=marker:
+//  {{{2
+use crate::clock::Clock;
+use crate::delta_datetimes::datetime_difference_seconds;
+use crate::parse_datetime::parse_datetime_with_tz;
+use crate::timezone::TimeZoneSpec;
+
+use chrono::{DateTime, FixedOffset};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A check made of located datetimes by `validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Check {
+    /// Datetime match which cannot be parsed
+    Unparsable,
+    /// Datetime after the present
+    Future,
+    /// Datetime earlier than the one before it
+    Unsorted,
+    /// Datetime at the same instant as an earlier one
+    Duplicate,
+    /// Delta from the datetime before longer than the maximum gap
+    Gap,
+    /// Datetime with a different offset to the one before it
+    MixedOffset,
+}
+
+impl Check {
+    /// Every check, in the order issues on the same location are reported
+    pub const ALL: [Check; 6] = [Check::Unparsable, Check::Future, Check::Unsorted, Check::Duplicate, Check::Gap, Check::MixedOffset];
+
+    /// Name of the check, as given to 'checks' and reported for each issue
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::Unparsable => "unparsable",
+            Check::Future => "future",
+            Check::Unsorted => "unsorted",
+            Check::Duplicate => "duplicate",
+            Check::Gap => "gap",
+            Check::MixedOffset => "mixed_offset",
+        }
+    }
+}
+
+impl FromStr for Check
+{
+    type Err = String;
+
+    /// Parses a check given by its name (see `Check::name()`)
+    fn from_str(s: &str) -> Result<Check, String>
+    {
+        Check::ALL.iter()
+            .find(|check| check.name() == s.to_ascii_lowercase())
+            .copied()
+            .ok_or_else(|| format!("unsupported check=({}) (must be unparsable/future/unsorted/duplicate/gap/mixed_offset)", s))
+    }
+}
+
+/// A problem found with a datetime in input
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    /// The check which found the problem
+    pub check: Check,
+    /// Line number (1-indexed) of the datetime
+    pub line: usize,
+    /// Column (1-indexed) at which the datetime begins
+    pub column: usize,
+    /// The datetime, as it appears in input
+    pub datetime: String,
+    /// Description of the problem
+    pub detail: String,
+}
+
+impl fmt::Display for Issue {
+    /// Formats the issue as 'line:column: check: datetime (detail)'
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}: {} ({})", self.line, self.column, self.check.name(), self.datetime, self.detail)
+    }
+}
+
+/// A parsed datetime, with the text it was parsed from and its location in input
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedDatetime {
+    /// The datetime, as it appears in input
+    pub text: String,
+    /// The parsed datetime
+    pub datetime: DateTime<FixedOffset>,
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// Position (0-indexed) on the line
+    pub position: usize,
+}
+
+impl LocatedDatetime {
+    fn issue(&self, check: Check, detail: String) -> Issue {
+        Issue { check, line: self.line, column: self.position + 1, datetime: self.text.clone(), detail, }
+    }
+}
+
+/// Parses located datetime matches (as per `search_datetimes::search_datetimes()`), reporting those which cannot be parsed
+///
+/// # Arguments
+/// * `datetimes_and_locations` - Each datetime string, its line number (1-indexed), and position on that line
+/// * `tz` - The timezone assumed for datetimes given without an offset
+///
+/// # Returns
+/// A tuple of the datetimes parsed, and an `Unparsable` issue for each match which could not be parsed
+///
+/// # Example
+/// ```
+/// use datetimescan::timezone::TimeZoneSpec;
+/// use datetimescan::validate::{parse_located, Check};
+/// let tz = TimeZoneSpec::parse("+10:00").unwrap();
+/// let datetimes_and_locations = vec![
+///     ("2023-05-11T00:00:00+10:00".to_string(), 1, 0),
+///     ("2023-05-11T25:00:00+10:00".to_string(), 2, 4),
+/// ];
+/// let (located, issues) = parse_located(&datetimes_and_locations, &tz);
+/// assert_eq!(located.len(), 1);
+/// assert_eq!((issues[0].check, issues[0].line, issues[0].column), (Check::Unparsable, 2, 5));
+/// ```
+pub fn parse_located(datetimes_and_locations: &[(String, usize, usize)], tz: &TimeZoneSpec) -> (Vec<LocatedDatetime>, Vec<Issue>)
+{
+    let mut located = Vec::with_capacity(datetimes_and_locations.len());
+    let mut issues = vec![];
+    for (text, line, position) in datetimes_and_locations {
+        match parse_datetime_with_tz(text, tz) {
+            Some(datetime) => located.push(LocatedDatetime { text: text.clone(), datetime, line: *line, position: *position, }),
+            None => issues.push(Issue { check: Check::Unparsable, line: *line, column: position + 1, datetime: text.clone(), detail: "failed to parse".to_string(), }),
+        }
+    }
+    (located, issues)
+}
+
+/// Reports each datetime after `now`
+pub fn check_future(located: &[LocatedDatetime], now: &DateTime<FixedOffset>) -> Vec<Issue>
+{
+    located.iter()
+        .filter(|x| x.datetime > *now)
+        .map(|x| x.issue(Check::Future, format!("after now=({})", now.to_rfc3339())))
+        .collect()
+}
+
+/// Reports each datetime earlier than the one before it
+pub fn check_unsorted(located: &[LocatedDatetime]) -> Vec<Issue>
+{
+    located.windows(2)
+        .filter(|w| w[1].datetime < w[0].datetime)
+        .map(|w| w[1].issue(Check::Unsorted, format!("before {} on line {}", w[0].text, w[0].line)))
+        .collect()
+}
+
+/// Reports each datetime at the same instant as an earlier one (anywhere in input)
+pub fn check_duplicates(located: &[LocatedDatetime]) -> Vec<Issue>
+{
+    let mut first_lines: HashMap<DateTime<FixedOffset>, usize> = HashMap::new();
+    let mut result = vec![];
+    for x in located {
+        match first_lines.get(&x.datetime) {
+            Some(line) => result.push(x.issue(Check::Duplicate, format!("same instant as line {}", line))),
+            None => { first_lines.insert(x.datetime, x.line); },
+        }
+    }
+    result
+}
+
+/// Reports each datetime more than `max_gap` seconds after the one before it
+pub fn check_gaps(located: &[LocatedDatetime], max_gap: u64) -> Vec<Issue>
+{
+    located.windows(2)
+        .filter_map(|w| {
+            let delta = datetime_difference_seconds(w[0].datetime, w[1].datetime);
+            (delta > 0 && delta as u64 > max_gap)
+                .then(|| w[1].issue(Check::Gap, format!("{}s after line {} (max_gap={}s)", delta, w[0].line, max_gap)))
+        })
+        .collect()
+}
+
+/// Reports each datetime with a different offset to the one before it
+pub fn check_mixed_offsets(located: &[LocatedDatetime]) -> Vec<Issue>
+{
+    located.windows(2)
+        .filter(|w| w[1].datetime.offset() != w[0].datetime.offset())
+        .map(|w| w[1].issue(Check::MixedOffset, format!("offset {} differs from {} on line {}", w[1].datetime.offset(), w[0].datetime.offset(), w[0].line)))
+        .collect()
+}
+
+/// Validates located datetime matches (as per `search_datetimes::search_datetimes()`), reporting every issue found by the given checks
+///
+/// Checks other than `Unparsable` are made of the datetimes which could be parsed, in the order they appear in input
+///
+/// # Arguments
+/// * `datetimes_and_locations` - Each datetime string, its line number (1-indexed), and position on that line
+/// * `checks` - The checks to make
+/// * `tz` - The timezone assumed for datetimes given without an offset
+/// * `clock` - The `Clock` providing the present (for `Future`)
+/// * `max_gap` - The longest delta (in seconds) not reported by `Gap`
+///
+/// # Returns
+/// The issues, ordered by location (and then in the order of `Check::ALL`)
+///
+/// # Example
+/// ```
+/// use chrono::DateTime;
+/// use datetimescan::clock::FixedClock;
+/// use datetimescan::timezone::TimeZoneSpec;
+/// use datetimescan::validate::{validate, Check};
+/// let tz = TimeZoneSpec::parse("+10:00").unwrap();
+/// let clock = FixedClock::new(DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap());
+/// let datetimes_and_locations = vec![
+///     ("2023-05-11T00:05:00+10:00".to_string(), 1, 0),
+///     ("2023-05-11T00:01:00+10:00".to_string(), 2, 0),
+///     ("2023-05-11T00:01:00+10:00".to_string(), 3, 0),
+/// ];
+/// let issues = validate(&datetimes_and_locations, &Check::ALL, &tz, &clock, 3600);
+/// let found: Vec<(usize, Check)> = issues.iter().map(|x| (x.line, x.check)).collect();
+/// assert_eq!(found, vec![(2, Check::Unsorted), (3, Check::Duplicate)]);
+/// ```
+pub fn validate(datetimes_and_locations: &[(String, usize, usize)], checks: &[Check], tz: &TimeZoneSpec, clock: &dyn Clock, max_gap: u64) -> Vec<Issue>
+{
+    let (located, mut result) = parse_located(datetimes_and_locations, tz);
+    if !checks.contains(&Check::Unparsable) {
+        result.clear();
+    }
+    for check in checks {
+        match check {
+            Check::Unparsable => {},
+            Check::Future => result.extend(check_future(&located, &clock.now())),
+            Check::Unsorted => result.extend(check_unsorted(&located)),
+            Check::Duplicate => result.extend(check_duplicates(&located)),
+            Check::Gap => result.extend(check_gaps(&located, max_gap)),
+            Check::MixedOffset => result.extend(check_mixed_offsets(&located)),
+        }
+    }
+    result.sort_by_key(|x| (x.line, x.column, x.check));
+    result.dedup();
+    log::debug!("validate(), result=({:?})", result);
+    result
+}
+
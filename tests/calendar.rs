@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod test_calendar {
+    use datetimescan::calendar::Calendar;
+    use datetimescan::period::Period;
+    use datetimescan::timezone::TimeZoneSpec;
+    use chrono::{DateTime, NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_key_default() {
+        let datetime = DateTime::parse_from_rfc3339("2023-05-14T23:30:00+10:00").unwrap();
+        let calendar = Calendar::default();
+        assert_eq!(calendar.key(&datetime, &Period::Day), "2023-05-14");
+        assert_eq!(calendar.key(&datetime, &Period::HourOfDay), "23");
+    }
+
+    #[test]
+    fn test_key_named_tz_across_dst() {
+        let calendar = Calendar::new(TimeZoneSpec::parse("Australia/Melbourne"));
+        //  AEDT (+1100) until 2023-04-02T03:00, AEST (+1000) after
+        let before = DateTime::parse_from_rfc3339("2023-04-01T13:30:00+00:00").unwrap();
+        let after = DateTime::parse_from_rfc3339("2023-04-02T13:30:00+00:00").unwrap();
+        assert_eq!(calendar.key(&before, &Period::Hour), "2023-04-02T00");
+        assert_eq!(calendar.key(&after, &Period::Hour), "2023-04-02T23");
+    }
+
+    #[test]
+    fn test_key_fixed_tz() {
+        let calendar = Calendar::new(TimeZoneSpec::parse("-0500"));
+        let datetime = DateTime::parse_from_rfc3339("2023-01-01T03:00:00+00:00").unwrap();
+        assert_eq!(calendar.key(&datetime, &Period::Year), "2022");
+        assert_eq!(calendar.key(&datetime, &Period::Week), "2022-W52");
+    }
+
+    #[test]
+    fn test_day_start() {
+        let calendar = Calendar::default().with_day_start(NaiveTime::from_hms_opt(4, 0, 0).unwrap());
+        let late = DateTime::parse_from_rfc3339("2023-05-15T01:30:00+10:00").unwrap();
+        let early = DateTime::parse_from_rfc3339("2023-05-15T04:00:00+10:00").unwrap();
+        assert_eq!(calendar.date(&late), NaiveDate::from_ymd_opt(2023, 5, 14).unwrap());
+        assert_eq!(calendar.date(&early), NaiveDate::from_ymd_opt(2023, 5, 15).unwrap());
+        assert_eq!(calendar.key(&late, &Period::Day), "2023-05-14");
+        assert_eq!(calendar.key(&late, &Period::Weekday), "Sun");
+        //  Hours are not shifted
+        assert_eq!(calendar.key(&late, &Period::Hour), "2023-05-15T01");
+        assert_eq!(calendar.key(&late, &Period::HourOfDay), "01");
+    }
+
+    #[test]
+    fn test_day_start_month_boundary() {
+        let calendar = Calendar::new(TimeZoneSpec::parse("UTC")).with_day_start(NaiveTime::from_hms_opt(6, 0, 0).unwrap());
+        let datetime = DateTime::parse_from_rfc3339("2023-01-01T05:59:59+00:00").unwrap();
+        assert_eq!(calendar.key(&datetime, &Period::Year), "2022");
+        assert_eq!(calendar.key(&datetime, &Period::Month), "2022-12");
+    }
+
+    #[test]
+    fn test_split_at_boundaries_day_start() {
+        let calendar = Calendar::default().with_day_start(NaiveTime::from_hms_opt(4, 0, 0).unwrap());
+        let start = DateTime::parse_from_rfc3339("2023-05-14T23:50:00+10:00").unwrap();
+        let end = DateTime::parse_from_rfc3339("2023-05-15T04:10:00+10:00").unwrap();
+        let parts = calendar.split_at_boundaries(&start, &end, &Period::Day);
+        let parts: Vec<(String, i64)> = parts.iter().map(|(key, a, b)| (key.clone(), (*b - *a).num_seconds())).collect();
+        assert_eq!(parts, vec![("2023-05-14".to_string(), 4 * 3600 + 600), ("2023-05-15".to_string(), 600)]);
+    }
+
+    #[test]
+    fn test_split_at_boundaries_hours_across_dst() {
+        //  Clocks go back from 03:00 AEDT to 02:00 AEST on 2023-04-02
+        let calendar = Calendar::new(TimeZoneSpec::parse("Australia/Melbourne"));
+        let start = DateTime::parse_from_rfc3339("2023-04-02T01:30:00+11:00").unwrap();
+        let end = DateTime::parse_from_rfc3339("2023-04-02T03:30:00+10:00").unwrap();
+        let parts = calendar.split_at_boundaries(&start, &end, &Period::Day);
+        assert_eq!(parts.len(), 1);
+        assert_eq!((parts[0].2 - parts[0].1).num_seconds(), 3 * 3600);
+        let parts = calendar.split_at_boundaries(&start, &end, &Period::HourOfDay);
+        let total: i64 = parts.iter().map(|(_, a, b)| (*b - *a).num_seconds()).sum();
+        assert_eq!(total, 3 * 3600);
+        assert_eq!(parts.first().unwrap().0, "01");
+        assert_eq!(parts.last().unwrap().0, "03");
+    }
+
+    #[test]
+    fn test_split_at_boundaries_all_and_empty() {
+        let start = DateTime::parse_from_rfc3339("2023-05-14T23:50:00+10:00").unwrap();
+        let end = DateTime::parse_from_rfc3339("2024-05-15T00:10:00+10:00").unwrap();
+        let parts = Calendar::default().split_at_boundaries(&start, &end, &Period::All);
+        assert_eq!(parts, vec![("all".to_string(), start, end)]);
+        assert!(Calendar::default().split_at_boundaries(&end, &start, &Period::Day).is_empty());
+    }
+}
+
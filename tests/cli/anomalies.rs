@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod test_cli_anomalies {
+
+    use datetimescan::create_arg_parser;
+    use datetimescan::subcommands;
+    use datetimescan::printer;
+    use crate::cli;
+
+    #[test]
+    fn test_empty_file() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "anomalies", "--input", &path_input];
+        run_anomalies(&args, "");
+    }
+
+    #[test]
+    fn test_isodatetimes1() {
+        let path_input = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "anomalies", "--input", &path_input];
+        run_anomalies(&args, "");
+    }
+
+    #[test]
+    fn test_isodatetimes2() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "anomalies", "--input", &path_input];
+        let expected = 
+"kind\tstart\tend\tdelta\tstart_line\tend_line
+skew\t2999-04-19T22:12:54+10:00\t2023-04-19T22:14:15+10:00\t-30799612719\t6\t10
+skew\t2023-04-19T22:21:07+10:00\t2023-04-19T22:20:05+10:00\t-62\t24\t25
+";
+        run_anomalies(&args, expected);
+    }
+
+    #[test]
+    fn test_dst_repeat() {
+        let path_input = cli::utils::get_path_textwithdstrepeat();
+        let args = vec!["datetimescan", "anomalies", "--assumetz", "Australia/Sydney", "--input", &path_input];
+        let expected = 
+"kind\tstart\tend\tdelta\tstart_line\tend_line
+dst_repeat\t2023-04-02T02:57:03+11:00\t2023-04-02T02:08:19+11:00\t-2924\t3\t4
+offset_change\t2023-04-02T02:31:55+11:00\t2023-04-02T02:45:00+10:00\t4385\t5\t6
+";
+        run_anomalies(&args, expected);
+    }
+
+    #[test]
+    fn test_dst_repeat_now_in_winter() {
+        let path_input = cli::utils::get_path_textwithdstrepeat();
+        let args = vec!["datetimescan", "anomalies", "--assumetz", "Australia/Sydney", "--now", "2023-07-01T12:00:00+10:00", "--input", &path_input];
+        let expected = 
+"kind\tstart\tend\tdelta\tstart_line\tend_line
+dst_repeat\t2023-04-02T02:57:03+11:00\t2023-04-02T02:08:19+11:00\t-2924\t3\t4
+offset_change\t2023-04-02T02:31:55+11:00\t2023-04-02T02:45:00+10:00\t4385\t5\t6
+";
+        run_anomalies(&args, expected);
+    }
+
+    #[test]
+    fn test_dst_repeat_tolerance_min_skew() {
+        let path_input = cli::utils::get_path_textwithdstrepeat();
+        let args = vec!["datetimescan", "anomalies", "--assumetz", "Australia/Sydney", "--dst_tolerance", "5m", "--min_skew", "30s", "--unit", "hms", "--input", &path_input];
+        let expected = 
+"kind\tstart\tend\tdelta\tstart_line\tend_line
+skew\t2023-04-02T02:57:03+11:00\t2023-04-02T02:08:19+11:00\t-48m44s\t3\t4
+offset_change\t2023-04-02T02:31:55+11:00\t2023-04-02T02:45:00+10:00\t1h13m05s\t5\t6
+skew\t2023-04-02T02:45:00+10:00\t2023-04-02T02:44:10+10:00\t-50s\t6\t7
+";
+        run_anomalies(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_offset_change() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "anomalies", "--range", "2022-12-11..2022-12-27", "--input", &path_input];
+        let expected = 
+"kind\tstart\tend\tdelta\tstart_line\tend_line
+offset_change\t2022-12-27T18:57:58+10:00\t2022-12-11T20:11:37+11:00\t-1381581\t891\t906
+skew\t2022-12-27T18:57:58+10:00\t2022-12-11T20:11:37+11:00\t-1381581\t891\t906
+";
+        run_anomalies(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid min_skew=(1x)")]
+    fn test_min_skew_invalid() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "anomalies", "--min_skew", "1x", "--input", &path_input];
+        run_anomalies(&args, "");
+    }
+
+    #[test]
+    fn test_dst_repeat_json() {
+        let path_input = cli::utils::get_path_textwithdstrepeat();
+        let args = vec!["datetimescan", "--format", "json", "anomalies", "--assumetz", "Australia/Sydney", "--input", &path_input];
+        let expected = 
+r#"[
+{"kind":"dst_repeat","start":"2023-04-02T02:57:03+11:00","end":"2023-04-02T02:08:19+11:00","delta":-2924,"start_line":3,"end_line":4,"unit":"s"},
+{"kind":"offset_change","start":"2023-04-02T02:31:55+11:00","end":"2023-04-02T02:45:00+10:00","delta":4385,"start_line":5,"end_line":6,"unit":"s"}
+]
+"#;
+        run_anomalies(&args, expected);
+    }
+
+
+    fn run_anomalies(args: &Vec<&str>, expected: &str) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
+        match matches.subcommand() {
+            ("anomalies", Some(matches)) => subcommands::anomalies(matches, renderer.as_mut()),
+            _ => panic!("wrong subcommand"),
+        }
+        drop(renderer);
+        let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
+        assert_eq!(result, expected);
+    }
+}
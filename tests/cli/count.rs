@@ -8,7 +8,7 @@ mod test_cli_count {
 
     use datetimescan::create_arg_parser;
     use datetimescan::subcommands;
-    use datetimescan::printer::Printer;
+    use datetimescan::printer;
     use crate::cli;
 
     #[test]
@@ -21,7 +21,7 @@ mod test_cli_count {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "count", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -29,7 +29,7 @@ mod test_cli_count {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "count", "--per", "all", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -37,7 +37,7 @@ mod test_cli_count {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "count", "--per", "y", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -45,7 +45,7 @@ mod test_cli_count {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "count", "--per", "m", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -53,7 +53,7 @@ mod test_cli_count {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "count", "--per", "d", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
 
@@ -65,7 +65,7 @@ mod test_cli_count {
         let expected = 
 r"5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -75,7 +75,7 @@ r"5
         let expected = 
 r"5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -85,7 +85,7 @@ r"5
         let expected = 
 r"5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -95,7 +95,7 @@ r"5
         let expected = 
 r"5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -105,7 +105,7 @@ r"5
         let expected = 
 "2023: 5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -115,7 +115,7 @@ r"5
         let expected = 
 r"2023-05: 5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -125,7 +125,7 @@ r"2023-05: 5
         let expected = 
 r"2023-05-05: 5
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
 
@@ -136,7 +136,7 @@ r"2023-05-05: 5
         let expected = 
 r"56
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -146,7 +146,7 @@ r"56
         let expected = 
 r"56
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -157,7 +157,7 @@ r"56
 r"2023: 55
 2999: 1
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -168,7 +168,7 @@ r"2023: 55
 r"2023-04: 55
 2999-04: 1
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -179,7 +179,7 @@ r"2023-04: 55
 r"2023-04-19: 55
 2999-04-19: 1
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -190,7 +190,7 @@ r"2023-04-19: 55
         let expected = 
 r"56
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -201,7 +201,28 @@ r"56
         let expected = 
 r"56
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes_2_no_future_now() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "count", "--no_future", "--now", "3000-01-01T00:00:00+0000", "--input", &path_input];
+        let expected = 
+r"56
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "reject future_datetimes")]
+    fn test_isodatetimes_2_no_future_now_past() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "count", "--no_future", "--now", "2023-04-19T22:10:00AEST", "--input", &path_input];
+        let expected = 
+r"56
+";
+        run_count(&args, expected);
     }
 
 
@@ -212,7 +233,7 @@ r"56
         let expected = 
 r"346
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -222,7 +243,7 @@ r"346
         let expected = 
 r"346
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -233,7 +254,7 @@ r"346
 r"2022: 62
 2023: 284
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -252,7 +273,94 @@ r"2022-06: 6
 2023-04: 64
 2023-05: 188
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_capture() {
+        use datetimescan::period::Period;
+        use datetimescan::render::{Capture, Rendered};
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "m", "--input", &path_input];
+        let matches = create_arg_parser::create_arg_parser().get_matches_from(args);
+        let mut capture = Capture::default();
+        if let ("count", Some(matches)) = matches.subcommand() {
+            subcommands::count(matches, &mut capture);
+        }
+        match capture.rendered.as_slice() {
+            [Rendered::Counts(result)] => {
+                assert_eq!(result.period, Period::Month);
+                assert_eq!(result.counts.len(), 10);
+                assert_eq!(result.counts[0], ("2022-06".to_string(), 6));
+                assert_eq!(result.counts[9], ("2023-05".to_string(), 188));
+            },
+            rendered => panic!("unexpected rendered=({:?})", rendered),
+        }
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_table_average() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--table", "--average", "--range", "2023-05-01..2023-05-05", "--input", &path_input];
+        let expected =
+r"interval    count  percent
+2023-05-01     20    32.8%
+2023-05-02      9    14.8%
+2023-05-03     11    18.0%
+2023-05-04      4     6.6%
+2023-05-05     17    27.9%
+total          61   100.0%
+average     12.20
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_weekday_table_color() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "weekday", "--fill", "--table", "--color", "always", "--range", "2023-05-01..2023-05-07", "--input", &path_input];
+        let expected = "\x1b[1minterval  count  percent\x1b[0m\nMon          20    27.4%\nTue           9    12.3%\nWed          11    15.1%\nThu           4     5.5%\nFri          17    23.3%\nSat          12    16.4%\n\x1b[2mSun           0     0.0%\x1b[0m\n\x1b[1mtotal        73   100.0%\x1b[0m\n";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_empty_file_table() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "count", "--table", "--input", &path_input];
+        let expected = "interval  count  percent\ntotal         0        -\n";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid table with format")]
+    fn test_table_format_csv() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "count", "--table", "--format", "csv", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_weekday_chart() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "weekday", "--fill", "--chart", "--width", "40", "--range", "2023-05-01..2023-05-07", "--input", &path_input];
+        let expected =
+r"Mon █████████████████████████████████ 20
+Tue ██████████████▉                    9
+Wed ██████████████████▏               11
+Thu ██████▋                            4
+Fri ████████████████████████████      17
+Sat ███████████████████▊              12
+Sun                                    0
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid chart with format")]
+    fn test_chart_format_json() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "count", "--chart", "--format", "json", "--input", &path_input];
+        run_count(&args, "");
     }
 
     #[test]
@@ -289,7 +397,7 @@ r"2022-06-05: 5
 2023-05-22: 12
 2023-05-23: 6
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -303,7 +411,7 @@ r"2022-11: 13
 2023-02: 15
 2023-03: 12
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -317,19 +425,353 @@ r"2022-06: 6
 2023-04: 64
 2023-05: 188
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_filter_partial() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "m", "--filter_start", "2022-10", "--filter_end", "2023-03", "--assumetz", "Australia/Sydney", "--now", "2023-06-01T00:00:00AEST", "--input", &path_input];
+        let expected = 
+r"2022-11: 13
+2022-12: 19
+2023-01: 5
+2023-02: 15
+2023-03: 12
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_filter_partial_year() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "y", "--filter_start", "2023", "--filter_end", "2023", "--assumetz", "Australia/Sydney", "--now", "2023-06-01T00:00:00AEST", "--input", &path_input];
+        let expected = 
+r"2023: 284
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_filter_partial_day() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--filter_start", "2023-05-14", "--filter_end", "2023-05-15", "--assumetz", "Australia/Sydney", "--now", "2023-06-01T00:00:00AEST", "--input", &path_input];
+        let expected = 
+r"2023-05-14: 29
+2023-05-15: 13
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid filter_end=(2023-13)")]
+    fn test_worklog_sample_filter_partial_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--filter_end", "2023-13", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_filter_relative() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--filter_start", "-9d", "--assumetz", "Australia/Sydney", "--now", "2023-05-23T12:00:00AEST", "--input", &path_input];
+        let expected = 
+r"2023-05-14: 29
+2023-05-15: 13
+2023-05-18: 4
+2023-05-19: 9
+2023-05-20: 22
+2023-05-22: 12
+2023-05-23: 6
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_filter_last_week() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--filter_start", "last week", "--filter_end", "last week", "--assumetz", "Australia/Sydney", "--now", "2023-05-23T12:00:00AEST", "--input", &path_input];
+        let expected = 
+r"2023-05-15: 13
+2023-05-18: 4
+2023-05-19: 9
+2023-05-20: 22
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_last() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--last", "2d", "--now", "2023-05-23T12:00:00AEST", "--input", &path_input];
+        let expected = 
+r"2023-05-22: 12
+2023-05-23: 6
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid last=(-5)")]
+    fn test_worklog_sample_last_negative() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--last=-5", "--now", "2023-05-23T12:00:00AEST", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid last=(99999999999999999)")]
+    fn test_worklog_sample_last_out_of_range() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--last", "99999999999999999", "--now", "2023-05-23T12:00:00AEST", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid filter_start=(-99999999999d)")]
+    fn test_worklog_sample_filter_relative_out_of_range() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--filter_start=-99999999999d", "--now", "2023-05-23T12:00:00AEST", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_range() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "m", "--range", "2022-10..2023-03", "--input", &path_input];
+        let expected = 
+r"2022-11: 13
+2022-12: 19
+2023-01: 5
+2023-02: 15
+2023-03: 12
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_range_multiple() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "m", "--range", "..2022-07", "--range", "2023-03..2023-05", "--range_bounds", "half_open", "--input", &path_input];
+        let expected = 
+r"2022-06: 6
+2023-03: 12
+2023-04: 64
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_range_filter_invert() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "m", "--filter_invert", "--range", "2022-07..2023-04", "--input", &path_input];
+        let expected = 
+r"2022-06: 6
+2023-05: 188
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_h() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "h", "--range", "2023-05-19", "--input", &path_input];
+        let expected = 
+r"2023-05-19T18: 2
+2023-05-19T19: 3
+2023-05-19T20: 2
+2023-05-19T21: 2
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_w() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "w", "--range", "2023-05", "--input", &path_input];
+        let expected = 
+r"2023-W18: 73
+2023-W19: 49
+2023-W20: 48
+2023-W21: 18
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_q() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "q", "--input", &path_input];
+        let expected = 
+r"2022-Q2: 6
+2022-Q3: 24
+2022-Q4: 32
+2023-Q1: 32
+2023-Q2: 252
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_weekday() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "weekday", "--input", &path_input];
+        let expected = 
+r"Mon: 70
+Tue: 32
+Wed: 36
+Thu: 79
+Fri: 26
+Sat: 56
+Sun: 47
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_hour_of_day() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "hour_of_day", "--range", "2023-05-19", "--input", &path_input];
+        let expected = 
+r"18: 2
+19: 3
+20: 2
+21: 2
+";
+        run_count(&args, expected);
     }
 
+    #[test]
+    fn test_worklog_sample_per_d_group_tz() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--range", "2023-05-18..2023-05-20", "--group_tz", "UTC", "--input", &path_input];
+        let expected = 
+r"2023-05-18: 4
+2023-05-19: 11
+2023-05-20: 20
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid group_tz=(Mars/Olympus_Mons)")]
+    fn test_worklog_sample_group_tz_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--group_tz", "Mars/Olympus_Mons", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_day_start() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--range", "2023-05-18..2023-05-20", "--day_start", "04:00", "--input", &path_input];
+        let expected = 
+r"2023-05-18: 4
+2023-05-19: 11
+2023-05-20: 20
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid day_start=(4am)")]
+    fn test_worklog_sample_day_start_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "d", "--day_start", "4am", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_fill() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "m", "--fill", "--filter_end", "2022-12", "--input", &path_input];
+        let expected = 
+r"2022-06: 6
+2022-07: 15
+2022-08: 0
+2022-09: 9
+2022-10: 0
+2022-11: 13
+2022-12: 19
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_weekday_fill_range() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--per", "weekday", "--fill", "--range", "2023-05-19", "--input", &path_input];
+        let expected = 
+r"Mon: 0
+Tue: 0
+Wed: 0
+Thu: 0
+Fri: 9
+Sat: 0
+Sun: 0
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_empty_file_fill() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "count", "--fill", "--input", &path_input];
+        run_count(&args, "0\n");
+        let args = vec!["datetimescan", "count", "--per", "d", "--fill", "--input", &path_input];
+        run_count(&args, "");
+        let args = vec!["datetimescan", "count", "--per", "d", "--fill", "--range", "2023-05-01..2023-05-02", "--input", &path_input];
+        run_count(&args, "2023-05-01: 0\n2023-05-02: 0\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range=(2023-05..2023-01)")]
+    fn test_worklog_sample_range_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "count", "--range", "2023-05..2023-01", "--input", &path_input];
+        run_count(&args, "");
+    }
+
+
+    #[test]
+    fn test_isodatetimes_2_format() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let checks = [
+            ("json", "[\n{\"interval\":\"2023-04-19\",\"count\":55},\n{\"interval\":\"2999-04-19\",\"count\":1}\n]\n"),
+            ("ndjson", "{\"interval\":\"2023-04-19\",\"count\":55}\n{\"interval\":\"2999-04-19\",\"count\":1}\n"),
+            ("csv", "interval,count\n2023-04-19,55\n2999-04-19,1\n"),
+            ("tsv", "interval\tcount\n2023-04-19\t55\n2999-04-19\t1\n"),
+        ];
+        for (format, expected) in checks {
+            let args = vec!["datetimescan", "count", "--per", "d", "--format", format, "--input", &path_input];
+            run_count(&args, expected);
+        }
+    }
+
+    #[test]
+    fn test_isodatetimes_2_format_per_all() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "count", "--format", "ndjson", "--input", &path_input];
+        run_count(&args, "{\"interval\":\"all\",\"count\":56}\n");
+    }
+
+    #[test]
+    fn test_empty_file_format_json() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "count", "--format", "json", "--input", &path_input];
+        run_count(&args, "[]\n");
+    }
 
     fn run_count(args: &Vec<&str>, expected: &str) {
         let parser = create_arg_parser::create_arg_parser();
         let matches = parser.get_matches_from(args);
         let mut buffer = Vec::<u8>::new();
-        let mut printer = Printer::new(Some(&mut buffer));
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
         match matches.subcommand() {
-            ("count", Some(matches)) => subcommands::count(&matches, &mut printer),
+            ("count", Some(matches)) => subcommands::count(matches, renderer.as_mut()),
             _ => panic!("wrong subcommand"),
         }
+        drop(renderer);
         let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
         assert_eq!(result, expected);
     }
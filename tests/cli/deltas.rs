@@ -4,7 +4,7 @@ mod test_cli_deltas {
 
     use datetimescan::create_arg_parser;
     use datetimescan::subcommands;
-    use datetimescan::printer::Printer;
+    use datetimescan::printer::{self, Printer};
     use crate::cli;
 
     #[test]
@@ -12,7 +12,7 @@ mod test_cli_deltas {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "deltas", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -25,7 +25,7 @@ r"41
 34
 17
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -38,7 +38,7 @@ r"41
 34
 17
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -51,7 +51,7 @@ r"41
 34
 17
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -115,7 +115,7 @@ r"36
 656
 7
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -179,7 +179,86 @@ r"36
 656
 7
 ";
-        run_count(&args, &expected);
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_negative() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "deltas", "--negative", "drop", "--input", &path_input];
+        let expected = 
+r"36
+170
+189
+5
+71
+27
+30
+23
+88
+82
+14
+72
+127
+193
+17
+71
+85
+23
+25
+27
+38
+95
+8
+14
+41
+49
+23
+39
+97
+48
+7
+26
+21
+31
+28
+5
+26
+2466
+10
+15
+34
+28
+348
+27
+15
+15
+46
+48
+95
+20
+52
+656
+7
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_negative_break_matches_allow_negative() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let parser = create_arg_parser::create_arg_parser();
+        let outputs: Vec<String> = ["--allow_negative", "--negative=break"].iter().map(|arg| {
+            let matches = parser.clone().get_matches_from(vec!["datetimescan", "deltas", arg, "--input", &path_input]);
+            let mut buffer = Vec::<u8>::new();
+            let mut printer = Printer::new(Some(&mut buffer));
+            if let ("deltas", Some(matches)) = matches.subcommand() {
+                subcommands::deltas(matches, &mut printer);
+            }
+            String::from_utf8(buffer).unwrap()
+        }).collect();
+        assert_eq!(outputs[0], outputs[1]);
+        assert!(outputs[0].contains("-62\n"));
     }
 
     #[test]
@@ -188,7 +267,7 @@ r"36
         let path_input = cli::utils::get_path_textwithisodatetimes2();
         let args = vec!["datetimescan", "deltas", "--no_unsorted", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
     #[test]
@@ -197,19 +276,47 @@ r"36
         let path_input = cli::utils::get_path_textwithisodatetimes2();
         let args = vec!["datetimescan", "deltas", "--no_future", "--input", &path_input];
         let expected = "";
-        run_count(&args, &expected);
+        run_count(&args, expected);
     }
 
 
+    #[test]
+    fn test_isodatetimes1_chart_ascii() {
+        let path_input = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "deltas", "--chart", "--ascii", "--width", "30", "--unit", "hms", "--input", &path_input];
+        let expected =
+r"<=32s   #################### 2
+<=1m04s #################### 2
+";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes1_format_tsv() {
+        let path_input = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "deltas", "--format", "tsv", "--input", &path_input];
+        let expected = "delta\tunit\n41\ts\n21\ts\n34\ts\n17\ts\n";
+        run_count(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes1_unit_m() {
+        let path_input = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "deltas", "--unit", "m", "--input", &path_input];
+        let expected = "0.68\n0.35\n0.57\n0.28\n";
+        run_count(&args, expected);
+    }
+
     fn run_count(args: &Vec<&str>, expected: &str) {
         let parser = create_arg_parser::create_arg_parser();
         let matches = parser.get_matches_from(args);
         let mut buffer = Vec::<u8>::new();
-        let mut printer = Printer::new(Some(&mut buffer));
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
         match matches.subcommand() {
-            ("deltas", Some(matches)) => subcommands::deltas(&matches, &mut printer),
+            ("deltas", Some(matches)) => subcommands::deltas(matches, renderer.as_mut()),
             _ => panic!("wrong subcommand"),
         }
+        drop(renderer);
         let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
         assert_eq!(result, expected);
     }
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod test_cli_gaps {
+
+    use datetimescan::create_arg_parser;
+    use datetimescan::subcommands;
+    use datetimescan::printer;
+    use crate::cli;
+
+
+    #[test]
+    fn test_empty_file() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "gaps", "--input", &path_input];
+        let expected = "";
+        run_gaps(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "gaps", "--input", &path_input];
+        let expected = 
+"start\tend\tduration\tstart_line\tend_line
+2023-04-19T22:11:06+10:00\t2999-04-19T22:12:54+10:00\t30799612908\t4\t6
+2023-04-19T22:40:31+10:00\t2023-04-19T23:21:37+10:00\t2466\t61\t63
+2023-04-19T23:23:04+10:00\t2023-04-19T23:28:52+10:00\t348\t69\t71
+2023-04-19T23:34:10+10:00\t2023-04-19T23:45:06+10:00\t656\t84\t86
+";
+        run_gaps(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_timeout_min_gap() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "gaps", "--timeout", "600", "--min_gap", "30m", "--filter_end", "2024", "--input", &path_input];
+        let expected = 
+"start\tend\tduration\tstart_line\tend_line
+2023-04-19T22:40:31+10:00\t2023-04-19T23:21:37+10:00\t2466\t61\t63
+";
+        run_gaps(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_top() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "gaps", "--top", "3", "--unit", "hms", "--input", &path_input];
+        let expected = 
+"start\tend\tduration\tstart_line\tend_line
+2023-05-05T19:36:35+10:00\t2023-05-18T21:49:02+10:00\t314h12m27s\t157\t171
+2022-07-13T00:16:50+10:00\t2022-07-13T22:48:22+10:00\t22h31m32s\t998\t1001
+2023-05-20T00:15:32+10:00\t2023-05-20T19:21:29+10:00\t19h05m57s\t83\t88
+";
+        run_gaps(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_total_per_w() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "gaps", "--total", "--per", "w", "--unit", "h", "--range", "2023-05", "--input", &path_input];
+        let expected = 
+r"2023-W18: 367.07
+2023-W19: 10.78
+2023-W20: 47.49
+2023-W21: 3.29
+";
+        run_gaps(&args, expected);
+        let args = vec!["datetimescan", "gaps", "--total", "--range", "2023-05", "--input", &path_input];
+        let expected = 
+r"1543055
+";
+        run_gaps(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid min_gap=(1x)")]
+    fn test_worklog_sample_min_gap_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "gaps", "--min_gap", "1x", "--input", &path_input];
+        run_gaps(&args, "");
+    }
+
+    #[test]
+    fn test_isodatetimes2_csv() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "--format", "csv", "gaps", "--min_gap", "10m", "--input", &path_input];
+        let expected = 
+r"start,end,duration,start_line,end_line,unit
+2023-04-19T22:11:06+10:00,2999-04-19T22:12:54+10:00,30799612908,4,6,s
+2023-04-19T22:40:31+10:00,2023-04-19T23:21:37+10:00,2466,61,63,s
+2023-04-19T23:34:10+10:00,2023-04-19T23:45:06+10:00,656,84,86,s
+";
+        run_gaps(&args, expected);
+    }
+
+
+    fn run_gaps(args: &Vec<&str>, expected: &str) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
+        match matches.subcommand() {
+            ("gaps", Some(matches)) => subcommands::gaps(matches, renderer.as_mut()),
+            _ => panic!("wrong subcommand"),
+        }
+        drop(renderer);
+        let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
+        assert_eq!(result, expected);
+    }
+}
+
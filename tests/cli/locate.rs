@@ -12,7 +12,7 @@ mod test_cli_locate {
 
     use datetimescan::create_arg_parser;
     use datetimescan::subcommands;
-    use datetimescan::printer::Printer;
+    use datetimescan::printer;
     use crate::cli;
 
     #[test]
@@ -25,7 +25,7 @@ mod test_cli_locate {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "locate", "--input", &path_input];
         let expected = "";
-        run_locate(&args, &expected);
+        run_locate(&args, expected);
     }
 
     #[test]
@@ -39,7 +39,7 @@ r"2023-05-05T19:34:42+1000	1	0
 2023-05-05T19:36:18+1000	4	0
 2023-05-05T19:36:35+1000	5	0
 ";
-        run_locate(&args, &expected);
+        run_locate(&args, expected);
     }
 
     #[test]
@@ -108,18 +108,35 @@ r"2023-04-19T22:07:40AEST	1	10
 2023-04-19T23:45:06AEST	86	0
 2023-04-19T23:45:13AEST	87	0
 ";
-        run_locate(&args, &expected);
+        run_locate(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes1_format_json() {
+        let path_input = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "locate", "--format", "json", "--input", &path_input];
+        let expected = 
+r#"[
+{"datetime":"2023-05-05T19:34:42+1000","line":1,"position":0},
+{"datetime":"2023-05-05T19:35:23+1000","line":2,"position":0},
+{"datetime":"2023-05-05T19:35:44+1000","line":3,"position":10},
+{"datetime":"2023-05-05T19:36:18+1000","line":4,"position":0},
+{"datetime":"2023-05-05T19:36:35+1000","line":5,"position":0}
+]
+"#;
+        run_locate(&args, expected);
     }
 
     fn run_locate(args: &Vec<&str>, expected: &str) {
         let parser = create_arg_parser::create_arg_parser();
         let matches = parser.get_matches_from(args);
         let mut buffer = Vec::<u8>::new();
-        let mut printer = Printer::new(Some(&mut buffer));
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
         match matches.subcommand() {
-            ("locate", Some(matches)) => subcommands::locate(&matches, &mut printer),
+            ("locate", Some(matches)) => subcommands::locate(matches, renderer.as_mut()),
             _ => panic!("wrong subcommand"),
         }
+        drop(renderer);
         let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
         assert_eq!(result, expected);
     }
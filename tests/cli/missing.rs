@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod test_cli_missing {
+
+    use datetimescan::create_arg_parser;
+    use datetimescan::subcommands;
+    use datetimescan::printer;
+    use crate::cli;
+
+
+    #[test]
+    fn test_empty_file() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "missing", "--input", &path_input];
+        let expected = "";
+        run_missing(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "missing", "--per", "m", "--input", &path_input];
+        let expected = 
+r"2022-08
+2022-10
+";
+        run_missing(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_range() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "missing", "--range", "2023-05-15..2023-05-26", "--input", &path_input];
+        let expected = 
+r"2023-05-16
+2023-05-17
+2023-05-21
+2023-05-24
+2023-05-25
+2023-05-26
+";
+        run_missing(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_exclude_file() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let path_exclude = cli::utils::get_path_holidays();
+        let args = vec!["datetimescan", "missing", "--range", "2023-05", "--exclude_file", &path_exclude, "--input", &path_input];
+        let expected = 
+r"2023-05-16
+2023-05-17
+2023-05-21
+";
+        run_missing(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_w_exclude_file() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "missing", "--per", "w", "--range", "2023-05", "--input", &path_input];
+        run_missing(&args, "2023-W22\n");
+        let path_exclude = cli::utils::get_path_holidays();
+        let args = vec!["datetimescan", "missing", "--per", "w", "--range", "2023-05", "--exclude_file", &path_exclude, "--input", &path_input];
+        run_missing(&args, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to open exclude_file=(does-not-exist.txt)")]
+    fn test_exclude_file_not_found() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "missing", "--exclude_file", "does-not-exist.txt", "--input", &path_input];
+        run_missing(&args, "");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid exclude_file=")]
+    fn test_exclude_file_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let path_exclude = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "missing", "--exclude_file", &path_exclude, "--input", &path_input];
+        run_missing(&args, "");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_range_csv() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "--format", "csv", "missing", "--range", "2023-05-15..2023-05-26", "--input", &path_input];
+        let expected = 
+r"interval
+2023-05-16
+2023-05-17
+2023-05-21
+2023-05-24
+2023-05-25
+2023-05-26
+";
+        run_missing(&args, expected);
+    }
+
+
+    fn run_missing(args: &Vec<&str>, expected: &str) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
+        match matches.subcommand() {
+            ("missing", Some(matches)) => subcommands::missing(matches, renderer.as_mut()),
+            _ => panic!("wrong subcommand"),
+        }
+        drop(renderer);
+        let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
+        assert_eq!(result, expected);
+    }
+}
+
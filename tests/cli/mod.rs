@@ -4,3 +4,9 @@ mod count;
 mod deltas;
 mod splits;
 mod sum;
+mod gaps;
+mod missing;
+mod stats;
+mod suggest_timeout;
+mod validate;
+mod anomalies;
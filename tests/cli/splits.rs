@@ -4,7 +4,7 @@ mod test_cli_splits {
 
     use datetimescan::create_arg_parser;
     use datetimescan::subcommands;
-    use datetimescan::printer::Printer;
+    use datetimescan::printer;
     use crate::cli;
 
 
@@ -13,7 +13,7 @@ mod test_cli_splits {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "splits", "--input", &path_input];
         let expected = "";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -23,7 +23,7 @@ mod test_cli_splits {
         let expected = 
 r"113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -33,7 +33,7 @@ r"113
         let expected = 
 r"113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -43,7 +43,7 @@ r"113
         let expected = 
 r"1.88
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -53,7 +53,7 @@ r"1.88
         let expected = 
 r"0.03
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -63,7 +63,7 @@ r"0.03
         let expected = 
 r"1m53s
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -73,7 +73,7 @@ r"1m53s
         let expected = 
 r"113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -83,7 +83,7 @@ r"113
         let expected = 
 r"2023: 113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -93,7 +93,7 @@ r"2023: 113
         let expected = 
 r"2023-05: 113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -103,7 +103,7 @@ r"2023-05: 113
         let expected = 
 r"2023-05-05: 113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -112,7 +112,7 @@ r"2023-05-05: 113
         let args = vec!["datetimescan", "splits", "--timeout", "1", "--input", &path_input];
         let expected = 
 r"";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -123,7 +123,7 @@ r"";
 r"21
 17
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -133,7 +133,7 @@ r"21
         let expected = 
 r"113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -143,7 +143,7 @@ r"113
         let expected = 
 r"113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -153,7 +153,7 @@ r"113
         let expected = 
 r"113
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
 
@@ -168,7 +168,7 @@ r"206
 318
 7
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -182,7 +182,7 @@ r"206
 318
 7
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -196,7 +196,7 @@ r"3.43
 5.30
 0.12
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -210,7 +210,7 @@ r"0.06
 0.09
 0.00
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -224,7 +224,7 @@ r"3m26s
 5m18s
 7s
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -233,7 +233,7 @@ r"3m26s
         let args = vec!["datetimescan", "splits", "--timeout", "1", "--input", &path_input];
         let expected = 
 r"";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -246,7 +246,7 @@ r"206
 753
 7
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -258,7 +258,7 @@ r"206
 1638
 1416
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -268,7 +268,7 @@ r"206
         let expected = 
 r"2023: 2033, 87, 318, 7
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -278,7 +278,7 @@ r"2023: 2033, 87, 318, 7
         let expected = 
 r"2023-04: 2033, 87, 318, 7
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -288,7 +288,127 @@ r"2023-04: 2033, 87, 318, 7
         let expected = 
 r"2023-04-19: 2033, 87, 318, 7
 ";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_apportion() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "splits", "--per", "d", "--range", "2022-07-13..2022-07-14", "--apportion", "--input", &path_input];
+        let expected = 
+r"2022-07-13: 291, 60, 99, 86
+2022-07-14: 36
+";
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_verbose() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "splits", "--verbose", "--range", "2023-05-19", "--input", &path_input];
+        let expected = 
+"start\tend\tduration\tevents\tfirst_line\tlast_line
+2023-05-19T18:30:18+10:00\t2023-05-19T18:32:52+10:00\t154\t2\t138\t139
+2023-05-19T19:29:08+10:00\t2023-05-19T19:29:59+10:00\t51\t3\t142\t144
+2023-05-19T20:28:13+10:00\t2023-05-19T20:29:00+10:00\t47\t2\t146\t147
+2023-05-19T21:39:01+10:00\t2023-05-19T21:39:43+10:00\t42\t2\t150\t151
+";
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_verbose_per_d() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "splits", "-v", "--per", "d", "--unit", "hms", "--range", "2022-07-13..2022-07-14", "--input", &path_input];
+        let expected = 
+"interval\tstart\tend\tduration\tevents\tfirst_line\tlast_line
+2022-07-13\t2022-07-13T00:04:38+10:00\t2022-07-13T00:09:29+10:00\t4m51s\t4\t991\t995
+2022-07-13\t2022-07-13T00:15:50+10:00\t2022-07-13T00:16:50+10:00\t1m\t2\t997\t998
+2022-07-13\t2022-07-13T22:53:26+10:00\t2022-07-13T22:55:05+10:00\t1m39s\t3\t1006\t1010
+2022-07-14\t2022-07-14T00:00:19+10:00\t2022-07-14T00:00:36+10:00\t17s\t2\t1017\t1018
+";
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_w_fill() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "splits", "--per", "w", "--fill", "--range", "2022-06-01..2022-06-20", "--input", &path_input];
+        let expected = 
+r"2022-W22: 232, 57
+2022-W23: 0
+2022-W24: 0
+2022-W25: 0
+";
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_model_credit() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "splits", "--per", "d", "--range", "2023-05-18..2023-05-19", "--model", "credit", "--credit", "1m", "--input", &path_input];
+        let expected = 
+r"2023-05-18: 240
+2023-05-19: 120, 180, 120, 120
+";
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_model_padded_verbose() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "splits", "--model", "padded", "--padding", "1m", "--verbose", "--input", &path_input];
+        let expected = 
+"start\tend\tduration\tevents\tfirst_line\tlast_line
+2023-04-19T22:07:40+10:00\t2023-04-19T22:11:06+10:00\t326\t3\t1\t4
+2023-04-19T22:14:15+10:00\t2999-04-19T22:12:54+10:00\t1698\t37\t6\t61
+2023-04-19T23:21:37+10:00\t2023-04-19T23:23:04+10:00\t147\t5\t63\t69
+2023-04-19T23:28:52+10:00\t2023-04-19T23:34:10+10:00\t438\t9\t71\t84
+2023-04-19T23:45:06+10:00\t2023-04-19T23:45:13+10:00\t127\t2\t86\t87
+";
+        run_splits(&args, expected);
+        let args = vec!["datetimescan", "splits", "--model", "padded", "--padding", "1m", "--input", &path_input];
+        run_splits(&args, "326\n1698\n147\n438\n127\n");
+    }
+
+    #[test]
+    fn test_isodatetimes2_negative() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let checks = [
+            ("clamp", "206\n1638\n87\n318\n7\n"),
+            ("break", "206\n412\n1226\n87\n318\n7\n"),
+            ("drop", "1971\n87\n318\n7\n"),
+            ("sort", "1971\n87\n318\n7\n"),
+        ];
+        for (negative, expected) in checks {
+            let args = vec!["datetimescan", "splits", "--negative", negative, "--input", &path_input];
+            run_splits(&args, expected);
+        }
+    }
+
+    #[test]
+    fn test_worklog_sample_sort_verbose() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "splits", "--verbose", "--sort", "--range", "2023-02-20", "--input", &path_input];
+        let expected = 
+r"start	end	duration	events	first_line	last_line
+2023-02-20T21:18:21+11:00	2023-02-20T21:25:58+11:00	457	7	802	810
+2023-02-20T22:04:06+11:00	2023-02-20T22:05:10+11:00	64	4	812	815
+2023-02-20T22:36:16+11:00	2023-02-20T22:38:00+11:00	104	3	818	835
+";
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_timeout_auto() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "splits", "--timeout", "auto", "--input", &path_input];
+        let expected = 
+r"206
+1638
+1416
+";
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -297,7 +417,7 @@ r"2023-04-19: 2033, 87, 318, 7
         let path_input = cli::utils::get_path_textwithisodatetimes2();
         let args = vec!["datetimescan", "splits", "--no_future", "--input", &path_input];
         let expected = r"";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
     #[test]
@@ -306,19 +426,45 @@ r"2023-04-19: 2033, 87, 318, 7
         let path_input = cli::utils::get_path_textwithisodatetimes2();
         let args = vec!["datetimescan", "splits", "--no_unsorted", "--input", &path_input];
         let expected = r"";
-        run_splits(&args, &expected);
+        run_splits(&args, expected);
     }
 
 
+    #[test]
+    fn test_isodatetimes2_format_ndjson() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "splits", "--per", "d", "--unit", "h", "--format", "ndjson", "--input", &path_input];
+        let expected = 
+r#"{"interval":"2023-04-19","duration":0.56,"unit":"h"}
+{"interval":"2023-04-19","duration":0.02,"unit":"h"}
+{"interval":"2023-04-19","duration":0.09,"unit":"h"}
+{"interval":"2023-04-19","duration":0.00,"unit":"h"}
+"#;
+        run_splits(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_verbose_format_csv() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "splits", "--verbose", "--range", "2023-04-19", "--timeout", "1200", "--format", "csv", "--input", &path_input];
+        let expected = 
+r"interval,start,end,duration,events,first_line,last_line,unit
+all,2023-04-19T22:07:40+10:00,2023-04-19T22:40:31+10:00,2033,39,1,61,s
+all,2023-04-19T23:21:37+10:00,2023-04-19T23:45:13+10:00,1416,16,63,87,s
+";
+        run_splits(&args, expected);
+    }
+
     fn run_splits(args: &Vec<&str>, expected: &str) {
         let parser = create_arg_parser::create_arg_parser();
         let matches = parser.get_matches_from(args);
         let mut buffer = Vec::<u8>::new();
-        let mut printer = Printer::new(Some(&mut buffer));
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
         match matches.subcommand() {
-            ("splits", Some(matches)) => subcommands::splits(&matches, &mut printer),
+            ("splits", Some(matches)) => subcommands::splits(matches, renderer.as_mut()),
             _ => panic!("wrong subcommand"),
         }
+        drop(renderer);
         let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
         assert_eq!(result, expected);
     }
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod test_cli_stats {
+
+    use datetimescan::create_arg_parser;
+    use datetimescan::subcommands;
+    use datetimescan::printer;
+    use crate::cli;
+
+
+    #[test]
+    fn test_empty_file() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "stats", "--input", &path_input];
+        let expected = "";
+        run_stats(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "stats", "--input", &path_input];
+        let expected = 
+"measure\tcount\tmin\tmax\tmean\tmedian\tstddev\tp90\tp95\tp99
+deltas\t55\t0\t30799612908\t559993066.07\t30\t4115091034.91\t181.40\t440.40\t14167823269.32
+sessions\t5\t7\t1638\t451.20\t206\t602.71\t1110\t1374\t1585.20
+";
+        run_stats(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_hms() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "stats", "--per", "d", "--unit", "hms", "--range", "2023-05-18..2023-05-19", "--input", &path_input];
+        let expected = 
+"interval\tmeasure\tcount\tmin\tmax\tmean\tmedian\tstddev\tp90\tp95\tp99
+2023-05-18\tdeltas\t3\t19s\t3m45s\t1m29s\t22s\t1m36s\t3m04s\t3m25s\t3m41s
+2023-05-18\tsessions\t1\t4m26s\t4m26s\t4m26s\t4m26s\t0s\t4m26s\t4m26s\t4m26s
+2023-05-19\tdeltas\t8\t12s\t1h10m01s\t23m41s\t1m41s\t29m33s\t1h01m46s\t1h05m54s\t1h09m12s
+2023-05-19\tsessions\t4\t42s\t2m34s\t1m14s\t49s\t47s\t2m03s\t2m19s\t2m31s
+";
+        run_stats(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_no_sessions() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "stats", "--per", "d", "--timeout", "10", "--range", "2023-05-18", "--input", &path_input];
+        let expected = 
+"interval\tmeasure\tcount\tmin\tmax\tmean\tmedian\tstddev\tp90\tp95\tp99
+2023-05-18\tdeltas\t3\t19\t225\t88.67\t22\t96.41\t184.40\t204.70\t220.94
+2023-05-18\tsessions\t0\t-\t-\t-\t-\t-\t-\t-\t-
+";
+        run_stats(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_no_sessions_ndjson() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "--format", "ndjson", "stats", "--per", "d", "--timeout", "10", "--range", "2023-05-18", "--input", &path_input];
+        let expected = 
+r#"{"interval":"2023-05-18","measure":"deltas","count":3,"min":19,"max":225,"mean":88.67,"median":22,"stddev":96.41,"p90":184.40,"p95":204.70,"p99":220.94,"unit":"s"}
+{"interval":"2023-05-18","measure":"sessions","count":0,"min":null,"max":null,"mean":null,"median":null,"stddev":null,"p90":null,"p95":null,"p99":null,"unit":"s"}
+"#;
+        run_stats(&args, expected);
+    }
+
+    fn run_stats(args: &Vec<&str>, expected: &str) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
+        match matches.subcommand() {
+            ("stats", Some(matches)) => subcommands::stats(matches, renderer.as_mut()),
+            _ => panic!("wrong subcommand"),
+        }
+        drop(renderer);
+        let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
+        assert_eq!(result, expected);
+    }
+}
+
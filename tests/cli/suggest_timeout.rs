@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod test_cli_suggest_timeout {
+
+    use datetimescan::create_arg_parser;
+    use datetimescan::subcommands;
+    use datetimescan::printer;
+    use crate::cli;
+
+
+    #[test]
+    fn test_empty_file() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "suggest-timeout", "--input", &path_input];
+        let expected = 
+r"timeout: 300
+sessions: 0
+rationale: too few deltas (0 positive, need 10) to find a valley, using default timeout
+";
+        run_suggest_timeout(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "suggest-timeout", "--input", &path_input];
+        let expected = 
+"timeout: 1024
+sessions: 62
+rationale: valley at (512s, 1024s] between peaks of intra-session deltas at (32s, 64s] and inter-session deltas at (2048s, 4096s], in log2 histogram of 312 positive deltas
+histogram:
+<=4\t2\t##
+<=8\t5\t###
+<=16\t31\t##################
+<=32\t71\t########################################
+<=64\t72\t########################################
+<=128\t19\t###########
+<=256\t22\t#############
+<=512\t20\t############
+<=1024\t9\t#####\t<- timeout
+<=2048\t15\t#########
+<=4096\t20\t############
+<=8192\t13\t########
+<=16384\t6\t####
+<=32768\t1\t#
+<=65536\t3\t##
+<=131072\t2\t##
+<=262144\t0
+<=524288\t0
+<=1048576\t0
+<=2097152\t1\t#
+";
+        run_suggest_timeout(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_hms() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "suggest-timeout", "--unit", "hms", "--filter_end", "2024", "--input", &path_input];
+        let expected = 
+"timeout: 34m08s
+sessions: 2
+rationale: valley at (1024s, 2048s] between peaks of intra-session deltas at (16s, 32s] and inter-session deltas at (2048s, 4096s], in log2 histogram of 53 positive deltas
+histogram:
+<=8s\t5\t#############
+<=16s\t6\t###############
+<=32s\t16\t########################################
+<=1m04s\t10\t#########################
+<=2m08s\t9\t#######################
+<=4m16s\t4\t##########
+<=8m32s\t1\t###
+<=17m04s\t1\t###
+<=34m08s\t0\t\t<- timeout
+<=1h08m16s\t1\t###
+";
+        run_suggest_timeout(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_ndjson() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "--format", "ndjson", "suggest-timeout", "--input", &path_input];
+        let expected = 
+r#"{"timeout":1024,"sessions":62,"rationale":"valley at (512s, 1024s] between peaks of intra-session deltas at (32s, 64s] and inter-session deltas at (2048s, 4096s], in log2 histogram of 312 positive deltas","unit":"s"}
+"#;
+        run_suggest_timeout(&args, expected);
+    }
+
+
+    fn run_suggest_timeout(args: &Vec<&str>, expected: &str) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
+        match matches.subcommand() {
+            ("suggest-timeout", Some(matches)) => subcommands::suggest_timeout(matches, renderer.as_mut()),
+            _ => panic!("wrong subcommand"),
+        }
+        drop(renderer);
+        let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
+        assert_eq!(result, expected);
+    }
+}
+
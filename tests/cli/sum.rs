@@ -4,7 +4,7 @@ mod test_cli_sum {
 
     use datetimescan::create_arg_parser;
     use datetimescan::subcommands;
-    use datetimescan::printer::Printer;
+    use datetimescan::printer::{self, Printer};
     use crate::cli;
 
     #[test]
@@ -16,7 +16,7 @@ mod test_cli_sum {
         let path_input = cli::utils::get_path_empty();
         let args = vec!["datetimescan", "sum", "--input", &path_input];
         let expected = r"";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -26,7 +26,7 @@ mod test_cli_sum {
         let expected = 
 r"113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -36,7 +36,7 @@ r"113
         let expected = 
 r"113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -46,7 +46,7 @@ r"113
         let expected = 
 r"113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -56,7 +56,7 @@ r"113
         let expected = 
 r"113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -66,7 +66,7 @@ r"113
         let expected = 
 r"2023: 113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -76,7 +76,7 @@ r"2023: 113
         let expected = 
 r"2023-05: 113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -86,7 +86,7 @@ r"2023-05: 113
         let expected = 
 r"2023-05-05: 113
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -96,7 +96,115 @@ r"2023-05-05: 113
         let expected = 
 r"2256
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_models() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let checks = [
+            ("discard", "2023-05-18: 266\n2023-05-19: 294\n"),
+            ("capped", "2023-05-18: 266\n2023-05-19: 1194\n"),
+            ("credit", "2023-05-18: 240\n2023-05-19: 540\n"),
+            ("padded", "2023-05-18: 506\n2023-05-19: 1254\n"),
+        ];
+        for (model, expected) in checks {
+            let args = vec!["datetimescan", "sum", "--per", "d", "--range", "2023-05-18..2023-05-19", "--model", model, "--credit", "1m", "--padding", "2m", "--input", &path_input];
+            run_sum(&args, expected);
+        }
+    }
+
+    #[test]
+    fn test_isodatetimes2_model_capped() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--model", "capped", "--input", &path_input];
+        let expected = 
+r"3456
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_model_apportion() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--model", "credit", "--apportion", "--per", "d", "--input", &path_input];
+        let result = create_arg_parser::create_arg_parser().get_matches_from_safe(args);
+        assert_eq!(result.unwrap_err().kind, clap::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid padding=(2x)")]
+    fn test_model_padding_invalid() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--model", "padded", "--padding", "2x", "--input", &path_input];
+        run_sum(&args, "");
+    }
+
+    #[test]
+    fn test_isodatetimes2_negative() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let checks = [
+            ("clamp", "2256\n"),
+            ("break", "2256\n"),
+            ("drop", "2383\n"),
+            ("sort", "2383\n"),
+        ];
+        for (negative, expected) in checks {
+            let args = vec!["datetimescan", "sum", "--negative", negative, "--input", &path_input];
+            run_sum(&args, expected);
+        }
+    }
+
+    #[test]
+    fn test_worklog_sample_negative_per_d() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        // deltas on '2023-02-20' include -98s and -1258s
+        let checks = [
+            ("clamp", "2023-02-20: 400\n"),
+            ("break", "2023-02-20: 400\n"),
+            ("drop", "2023-02-20: 302\n"),
+            ("sort", "2023-02-20: 625\n"),
+        ];
+        for (negative, expected) in checks {
+            let args = vec!["datetimescan", "sum", "--per", "d", "--range", "2023-02-20", "--negative", negative, "--input", &path_input];
+            run_sum(&args, expected);
+        }
+    }
+
+    #[test]
+    fn test_isodatetimes2_sort() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--sort", "--input", &path_input];
+        run_sum(&args, "2383\n");
+    }
+
+    #[test]
+    fn test_isodatetimes2_dedup() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--dedup", "--input", &path_input];
+        run_sum(&args, "2256\n");
+        let args = vec!["datetimescan", "sum", "--dedup=30", "--input", &path_input];
+        run_sum(&args, "2195\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid dedup=(30x)")]
+    fn test_dedup_invalid() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--dedup=30x", "--input", &path_input];
+        run_sum(&args, "");
+    }
+
+    #[test]
+    fn test_isodatetimes2_timeout_auto() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--timeout", "auto", "--input", &path_input];
+        let expected = 
+r"3260
+";
+        run_sum(&args, expected);
+        let args = vec!["datetimescan", "sum", "--timeout", "2048", "--input", &path_input];
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -106,7 +214,7 @@ r"2256
         let expected = 
 r"2256
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -116,7 +224,7 @@ r"2256
         let expected = 
 r"37.60
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -126,7 +234,7 @@ r"37.60
         let expected = 
 r"0.63
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -136,7 +244,7 @@ r"0.63
         let expected = 
 r"2256
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -146,7 +254,7 @@ r"2256
         let expected = 
 r"2023: 2445
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -156,7 +264,7 @@ r"2023: 2445
         let expected = 
 r"2023: 2445
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -166,7 +274,7 @@ r"2023: 2445
         let expected = 
 r"2023: 40.75
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -176,7 +284,7 @@ r"2023: 40.75
         let expected = 
 r"2023: 0.68
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -186,7 +294,7 @@ r"2023: 0.68
         let expected = 
 r"2023-04: 2445
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -196,7 +304,7 @@ r"2023-04: 2445
         let expected = 
 r"2023-04-19: 2445
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -206,7 +314,7 @@ r"2023-04-19: 2445
         let expected = 
 r"2023-04-19: 2445
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -216,7 +324,7 @@ r"2023-04-19: 2445
         let expected = 
 r"2023-04-19: 40.75
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -226,7 +334,7 @@ r"2023-04-19: 40.75
         let expected = 
 r"2023-04-19: 0.68
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -236,7 +344,7 @@ r"2023-04-19: 0.68
         let expected = 
 r"2023-04-19: 40m45s
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -246,7 +354,7 @@ r"2023-04-19: 40m45s
         let expected = 
 r"3260
 ";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -255,7 +363,7 @@ r"3260
         let path_input = cli::utils::get_path_textwithisodatetimes2();
         let args = vec!["datetimescan", "sum", "--no_future", "--input", &path_input];
         let expected = r"";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
     #[test]
@@ -264,19 +372,210 @@ r"3260
         let path_input = cli::utils::get_path_textwithisodatetimes2();
         let args = vec!["datetimescan", "sum", "--no_unsorted", "--input", &path_input];
         let expected = r"";
-        run_sum(&args, &expected);
+        run_sum(&args, expected);
     }
 
+    #[test]
+    fn test_worklog_sample_time_of_day() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--time_of_day", "19:00-22:00", "--input", &path_input];
+        let expected = 
+r"4617
+";
+        run_sum(&args, expected);
+    }
 
-    fn run_sum(args: &Vec<&str>, expected: &str) {
+    #[test]
+    fn test_worklog_sample_time_of_day_clip() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--time_of_day", "19:00-22:00", "--clip", "--input", &path_input];
+        let expected = 
+r"4639
+";
+        run_sum(&args, expected);
+        let args = vec!["datetimescan", "sum", "--time_of_day", "19:00-22:00", "--recurring_invert", "--clip", "--input", &path_input];
+        let expected = 
+r"7421
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_weekdays() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--weekdays", "sat,sun", "--input", &path_input];
+        let expected = 
+r"3487
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_weekdays_filter_tz() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--weekdays", "sat,sun", "--filter_tz", "UTC", "--input", &path_input];
+        let expected = 
+r"3496
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_weekday() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--per", "weekday", "--input", &path_input];
+        let expected = 
+r"Mon: 2368
+Tue: 1248
+Wed: 1308
+Thu: 2980
+Fri: 564
+Sat: 1490
+Sun: 1997
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_day_start() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--per", "d", "--range", "2023-05-18..2023-05-20", "--input", &path_input];
+        let expected = 
+r"2023-05-18: 266
+2023-05-19: 294
+2023-05-20: 528
+";
+        run_sum(&args, expected);
+        let args = vec!["datetimescan", "sum", "--per", "d", "--range", "2023-05-18..2023-05-20", "--day_start", "04:00", "--input", &path_input];
+        let expected = 
+r"2023-05-18: 266
+2023-05-19: 319
+2023-05-20: 503
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_apportion() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--per", "d", "--range", "2022-07-13..2022-07-14", "--input", &path_input];
+        let expected = 
+r"2022-07-13: 450
+2022-07-14: 17
+";
+        run_sum(&args, expected);
+        let args = vec!["datetimescan", "sum", "--per", "d", "--range", "2022-07-13..2022-07-14", "--apportion", "--input", &path_input];
+        let expected = 
+r"2022-07-13: 536
+2022-07-14: 36
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_per_d_apportion_matches_all() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--per", "d", "--apportion", "--input", &path_input];
         let parser = create_arg_parser::create_arg_parser();
         let matches = parser.get_matches_from(args);
         let mut buffer = Vec::<u8>::new();
         let mut printer = Printer::new(Some(&mut buffer));
+        if let ("sum", Some(matches)) = matches.subcommand() {
+            subcommands::sum(matches, &mut printer);
+        }
+        let result = String::from_utf8(buffer).unwrap();
+        let total: u64 = result.lines().map(|line| line.split(": ").nth(1).unwrap().parse::<u64>().unwrap()).sum();
+        assert_eq!(total, 2256);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_fill_range() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--per", "d", "--fill", "--range", "2023-05-17..2023-05-21", "--input", &path_input];
+        let expected = 
+r"2023-05-17: 0
+2023-05-18: 266
+2023-05-19: 294
+2023-05-20: 528
+2023-05-21: 0
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid time_of_day=(9am-5pm)")]
+    fn test_worklog_sample_time_of_day_invalid() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--time_of_day", "9am-5pm", "--input", &path_input];
+        run_sum(&args, "");
+    }
+
+
+    #[test]
+    fn test_isodatetimes2_format_csv_unit_hms() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--unit", "hms", "--format", "csv", "--input", &path_input];
+        run_sum(&args, "interval,sum,unit\nall,37m36s,hms\n");
+    }
+
+    #[test]
+    fn test_worklog_sample_per_m_table_h() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--per", "m", "--table", "--unit", "h", "--input", &path_input];
+        let expected =
+r"interval   sum  percent
+2022-06   0.08     2.4%
+2022-07   0.16     4.7%
+2022-09   0.04     1.2%
+2022-11   0.13     3.8%
+2022-12   0.23     6.9%
+2023-01   0.02     0.6%
+2023-02   0.11     3.3%
+2023-03   0.12     3.4%
+2023-04   0.65    19.5%
+2023-05   1.81    54.1%
+total     3.35   100.0%
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_worklog_sample_per_d_chart_ascii() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "sum", "--per", "d", "--unit", "m", "--chart", "--ascii", "--width", "40", "--range", "2023-05-01..2023-05-05", "--input", &path_input];
+        let expected =
+r"2023-05-01 ####################### 12.43
+2023-05-02 ########                 4.52
+2023-05-03 ##############           7.40
+2023-05-04 ###                      1.82
+2023-05-05 ########                 4.50
+";
+        run_sum(&args, expected);
+    }
+
+    #[test]
+    fn test_isodatetimes2_format_json_fill() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "sum", "--per", "d", "--fill", "--range", "2023-04-18..2023-04-19", "--format", "json", "--input", &path_input];
+        let expected = 
+r#"[
+{"interval":"2023-04-18","sum":0,"unit":"s"},
+{"interval":"2023-04-19","sum":2445,"unit":"s"}
+]
+"#;
+        run_sum(&args, expected);
+    }
+
+    fn run_sum(args: &Vec<&str>, expected: &str) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
         match matches.subcommand() {
-            ("sum", Some(matches)) => subcommands::sum(&matches, &mut printer),
+            ("sum", Some(matches)) => subcommands::sum(matches, renderer.as_mut()),
             _ => panic!("wrong subcommand"),
         }
+        drop(renderer);
         let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
         assert_eq!(result, expected);
     }
@@ -35,6 +35,16 @@ pub fn get_path_partialdatetimes() -> String {
     get_test_data_file("partialDatetimes.txt")
 }
 
+#[cfg(test)]
+pub fn get_path_holidays() -> String {
+    get_test_data_file("holidays.txt")
+}
+
+#[cfg(test)]
+pub fn get_path_textwithdstrepeat() -> String {
+    get_test_data_file("textWithDstRepeat.txt")
+}
+
 #[cfg(test)]
 fn get_test_data_file(filename: &str) -> String {
     let path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
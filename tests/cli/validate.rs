@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod test_cli_validate {
+
+    use datetimescan::create_arg_parser;
+    use datetimescan::subcommands;
+    use datetimescan::printer;
+    use crate::cli;
+
+    #[test]
+    fn test_empty_file() {
+        let path_input = cli::utils::get_path_empty();
+        let args = vec!["datetimescan", "validate", "--input", &path_input];
+        run_validate(&args, "", true);
+    }
+
+    #[test]
+    fn test_isodatetimes1() {
+        let path_input = cli::utils::get_path_textwithisodatetimes1();
+        let args = vec!["datetimescan", "validate", "--now", "2023-06-01T00:00:00+10:00", "--input", &path_input];
+        run_validate(&args, "", true);
+    }
+
+    #[test]
+    fn test_isodatetimes2() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "validate", "--now", "2023-06-01T00:00:00+10:00", "--input", &path_input];
+        let expected = 
+"line\tcolumn\tcheck\tdatetime\tdetail
+6\t1\tfuture\t2999-04-19T22:12:54AEST\tafter now=(2023-06-01T00:00:00+10:00)
+6\t1\tgap\t2999-04-19T22:12:54AEST\t30799612908s after line 4 (max_gap=604800s)
+10\t1\tunsorted\t2023-04-19T22:14:15AEST\tbefore 2999-04-19T22:12:54AEST on line 6
+25\t7\tunsorted\t2023-04-19T22:20:05AEST\tbefore 2023-04-19T22:21:07AEST on line 24
+";
+        run_validate(&args, expected, false);
+    }
+
+    #[test]
+    fn test_isodatetimes2_ndjson_checks() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "--format", "ndjson", "validate", "--checks", "future,unsorted", "--now", "2023-06-01T00:00:00+10:00", "--input", &path_input];
+        let expected = 
+r#"{"line":6,"column":1,"check":"future","datetime":"2999-04-19T22:12:54AEST","detail":"after now=(2023-06-01T00:00:00+10:00)"}
+{"line":10,"column":1,"check":"unsorted","datetime":"2023-04-19T22:14:15AEST","detail":"before 2999-04-19T22:12:54AEST on line 6"}
+{"line":25,"column":7,"check":"unsorted","datetime":"2023-04-19T22:20:05AEST","detail":"before 2023-04-19T22:21:07AEST on line 24"}
+"#;
+        run_validate(&args, expected, false);
+    }
+
+    #[test]
+    fn test_isodatetimes2_max_gap() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "validate", "--checks", "gap", "--max_gap", "10m", "--input", &path_input];
+        let expected = 
+"line\tcolumn\tcheck\tdatetime\tdetail
+6\t1\tgap\t2999-04-19T22:12:54AEST\t30799612908s after line 4 (max_gap=600s)
+63\t1\tgap\t2023-04-19T23:21:37AEST\t2466s after line 61 (max_gap=600s)
+86\t1\tgap\t2023-04-19T23:45:06AEST\t656s after line 84 (max_gap=600s)
+";
+        run_validate(&args, expected, false);
+    }
+
+    #[test]
+    fn test_worklog_sample_mixed_offset() {
+        let path_input = cli::utils::get_path_worklogscrambledsamples();
+        let args = vec!["datetimescan", "validate", "--checks", "mixed_offset", "--range", "2023-03-29", "--input", &path_input];
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut printer = printer::Printer::new(Some(&mut buffer));
+        if let ("validate", Some(matches)) = matches.subcommand() {
+            assert!(!subcommands::validate(matches, &mut printer));
+        }
+        let result = String::from_utf8(buffer).unwrap();
+        // datetimes are not filtered by --range
+        assert!(result.contains("775\t1\tmixed_offset\t2023-03-29T21:00:39AEDT\toffset +11:00 differs from +10:00 on line 763\n"));
+        assert!(result.contains("848\t10\tmixed_offset\t2023-01-01T17:59:13AEST\toffset +10:00 differs from +11:00 on line 835\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid max_gap=(1x)")]
+    fn test_max_gap_invalid() {
+        let path_input = cli::utils::get_path_textwithisodatetimes2();
+        let args = vec!["datetimescan", "validate", "--max_gap", "1x", "--input", &path_input];
+        run_validate(&args, "", false);
+    }
+
+
+    fn run_validate(args: &Vec<&str>, expected: &str, expected_valid: bool) {
+        let parser = create_arg_parser::create_arg_parser();
+        let matches = parser.get_matches_from(args);
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = printer::get_renderer(&matches, Some(&mut buffer));
+        let valid = match matches.subcommand() {
+            ("validate", Some(matches)) => subcommands::validate(matches, renderer.as_mut()),
+            _ => panic!("wrong subcommand"),
+        };
+        drop(renderer);
+        let result = String::from_utf8(buffer).expect("Failed to convert Printer buffer to String");
+        assert_eq!(result, expected);
+        assert_eq!(valid, expected_valid);
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod test_clock {
+    use datetimescan::clock::{Clock, FixedClock, SystemClock};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_fixed_clock() {
+        let now = DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap();
+        let clock = FixedClock::new(now);
+        assert_eq!(clock.now(), now);
+        assert_eq!(clock.now(), clock.now());
+    }
+
+    #[test]
+    fn test_system_clock() {
+        let before = Utc::now();
+        let now = SystemClock.now();
+        let after = Utc::now();
+        assert!(now >= before && now <= after);
+    }
+}
+
+#[cfg(test)]
+mod test_get_clock {
+    use datetimescan::clock::get_clock;
+    use datetimescan::create_arg_parser;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_now_arg() {
+        let args = vec!["datetimescan", "--now", "2023-05-27T12:00:00+10:00", "count"];
+        let matches = create_arg_parser::create_arg_parser().get_matches_from(args);
+        let clock = get_clock(&matches);
+        assert_eq!(clock.now(), DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap());
+    }
+
+    #[test]
+    fn test_now_arg_assumetz() {
+        let args = vec!["datetimescan", "--now", "2023-05-27T12:00:00", "--assumetz", "Australia/Sydney", "count"];
+        let matches = create_arg_parser::create_arg_parser().get_matches_from(args);
+        let clock = get_clock(&matches);
+        assert_eq!(clock.now(), DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid now=(tomorrow-ish)")]
+    fn test_now_arg_invalid() {
+        let args = vec!["datetimescan", "--now", "tomorrow-ish", "count"];
+        let matches = create_arg_parser::create_arg_parser().get_matches_from(args);
+        get_clock(&matches);
+    }
+}
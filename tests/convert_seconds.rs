@@ -73,5 +73,28 @@ mod test {
         let seconds: u64 = 0;
         assert_eq!(seconds.convert_seconds("hms"), "0s");
     }
-}
 
+    #[test]
+    fn test_f64() {
+        let value: f64 = 3600.0;
+        assert_eq!(value.convert_seconds("hms"), "1h");
+        assert_eq!(value.convert_seconds("h"), "1.00");
+        assert_eq!(value.convert_seconds("m"), "60.00");
+        assert_eq!(value.convert_seconds("s"), "3600");
+
+        let value: f64 = 90.456;
+        assert_eq!(value.convert_seconds("hms"), "1m30s");
+        assert_eq!(value.convert_seconds("m"), "1.51");
+        assert_eq!(value.convert_seconds("s"), "90.46");
+
+        let value: f64 = -3660.6;
+        assert_eq!(value.convert_seconds("hms"), "-1h01m01s");
+    }
+
+    #[test]
+    #[should_panic(expected = "unit=(d) must equal 'hms' / 'h' / 'm' / 's'")]
+    fn test_invalid_unit_f64() {
+        let value: f64 = 3600.0;
+        let _ = value.convert_seconds("d");
+    }
+}
@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod test_date_range_ctor { 
     use chrono::NaiveDate;
-    use datetimescan::date_range::{DateRange, parse_partial_date_str};
+    use datetimescan::date_range::{DateRange, parse_partial_date_str, parse_partial_datetime_bounds};
 
     #[test]
     fn test_parse_partial_date_str() {
@@ -27,6 +27,24 @@ mod test_date_range_ctor {
         assert_eq!(parse_partial_date_str("202300"), None);  // Not a valid format
     }
 
+    #[test]
+    fn test_parse_partial_datetime_bounds() {
+        let dt = |y, m, d, h, mi, s| NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, mi, s).unwrap();
+        assert_eq!(parse_partial_datetime_bounds("2023"), Some((dt(2023, 1, 1, 0, 0, 0), dt(2023, 12, 31, 23, 59, 59))));
+        assert_eq!(parse_partial_datetime_bounds("2023-12"), Some((dt(2023, 12, 1, 0, 0, 0), dt(2023, 12, 31, 23, 59, 59))));
+        assert_eq!(parse_partial_datetime_bounds("2024-02"), Some((dt(2024, 2, 1, 0, 0, 0), dt(2024, 2, 29, 23, 59, 59))));
+        assert_eq!(parse_partial_datetime_bounds("2023-03-03"), Some((dt(2023, 3, 3, 0, 0, 0), dt(2023, 3, 3, 23, 59, 59))));
+        assert_eq!(parse_partial_datetime_bounds("2023-03-03T09"), Some((dt(2023, 3, 3, 9, 0, 0), dt(2023, 3, 3, 9, 59, 59))));
+        assert_eq!(parse_partial_datetime_bounds("2023-03-03 23"), Some((dt(2023, 3, 3, 23, 0, 0), dt(2023, 3, 3, 23, 59, 59))));
+        assert_eq!(parse_partial_datetime_bounds("2023-03-03T09:30"), Some((dt(2023, 3, 3, 9, 30, 0), dt(2023, 3, 3, 9, 30, 59))));
+
+        // Test invalid inputs
+        assert_eq!(parse_partial_datetime_bounds(""), None);
+        assert_eq!(parse_partial_datetime_bounds("2023-13"), None);
+        assert_eq!(parse_partial_datetime_bounds("2023-03-03T24"), None);
+        assert_eq!(parse_partial_datetime_bounds("2023-03-03X09"), None);
+    }
+
     #[test]
     fn test_date_range_new() {
         let dr = DateRange::new("2023", "2023-03-03");
@@ -46,11 +64,11 @@ mod test_date_range_ctor {
 
     #[test]
     fn test_date_range_new_from_str_range() {
-        let dr = DateRange::new_from_str_range(&vec!["2023", "2023-03-03"]);
+        let dr = DateRange::new_from_str_range(&["2023", "2023-03-03"]);
         assert_eq!(dr.start, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
         assert_eq!(dr.end, NaiveDate::from_ymd_opt(2023, 3, 3).unwrap());
 
-        let dr = DateRange::new_from_str_range(&vec!["2023-02", "2023-03", "2023"]);
+        let dr = DateRange::new_from_str_range(&["2023-02", "2023-03", "2023"]);
         assert_eq!(dr.start, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
         assert_eq!(dr.end, NaiveDate::from_ymd_opt(2023, 3, 1).unwrap());
     }
@@ -58,7 +76,7 @@ mod test_date_range_ctor {
     #[test]
     #[should_panic]
     fn test_date_range_new_from_str_range_invalid() {
-        DateRange::new_from_str_range(&vec!["2023-02-30", "2023-03-03"]);  // Invalid date
+        DateRange::new_from_str_range(&["2023-02-30", "2023-03-03"]);  // Invalid date
     }
 }
 
@@ -66,87 +84,142 @@ mod test_date_range_ctor {
 mod test_date_range_get_dates { 
     use chrono::NaiveDate;
     use datetimescan::date_range::DateRange;
+    use datetimescan::period::Period;
 
     #[test]
     fn test_days_range() {
-        let inputs = vec![("2020-01-01", "2022-01-01"), 
+        let inputs = [("2020-01-01", "2022-01-01"), 
                           ("2020-01-01", "2020-01-02"), 
                           ("2021-01-01", "2021-12-31"), 
-                          ("2021-01-01", "2021-01-01"),];
-        let checks_len = vec![ 732, 2, 365, 1, ];
+                          ("2021-01-01", "2021-01-01")];
+        let checks_len = [732, 2, 365, 1];
         assert_eq!(inputs.len(), checks_len.len());
         for ((start, end), check_len) in inputs.iter().zip(checks_len.iter()) {
             let date_range = DateRange::new(start, end);
-            let result = date_range.get_dates("d");
+            let result = date_range.get_dates(&Period::Day);
             assert_eq!(result.len(), *check_len);
             assert_eq!(result[0].format("%F").to_string(), *start);
             assert_eq!(result[result.len()-1].format("%F").to_string(), *end);
         }
-        let inputs = vec![("2020-01-01", "2020-01-05"),];
-        let checks_strs = vec![vec!["2020-01-01", "2020-01-02", "2020-01-03", "2020-01-04", "2020-01-05"],];
+        let inputs = [("2020-01-01", "2020-01-05")];
+        let checks_strs = [vec!["2020-01-01", "2020-01-02", "2020-01-03", "2020-01-04", "2020-01-05"]];
         assert_eq!(inputs.len(), checks_strs.len());
         for ((start, end), check_strs) in inputs.iter().zip(checks_strs.iter()) {
             let check_dates: Vec<NaiveDate> = check_strs.iter().map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d").unwrap()).collect();
             let date_range = DateRange::new(start, end);
-            let result = date_range.get_dates("d");
+            let result = date_range.get_dates(&Period::Day);
             assert_eq!(result, check_dates);
         }
     }
 
     #[test]
     fn test_months_range() {
-        let inputs = vec![("2020-01-01", "2020-12-31"), 
+        let inputs = [("2020-01-01", "2020-12-31"), 
                           ("2020-01-01", "2020-01-01"), 
                           ("2020-03", "2020-09"), 
                           ("2020-03", "2020-09-30"),
-                          ("2020-03-01", "2020-05-15"),];
-        let checks_len = vec![ 12, 1, 7, 7, 3, ];
+                          ("2020-03-01", "2020-05-15")];
+        let checks_len = [12, 1, 7, 7, 3];
         assert_eq!(inputs.len(), checks_len.len());
         for ((start, end), check_len) in inputs.iter().zip(checks_len.iter()) {
             let date_range = DateRange::new(start, end);
-            let result = date_range.get_dates("m");
+            let result = date_range.get_dates(&Period::Month);
             assert_eq!(result.len(), *check_len);
             assert_eq!(result[0].format("%Y-%m").to_string(), start[0..7]);
             assert_eq!(result[result.len()-1].format("%Y-%m").to_string(), end[0..7]);
         }
-        let inputs = vec![("2020-01-01", "2020-05-01"),];
-        let checks_strs = vec![vec!["2020-01-01", "2020-02-01", "2020-03-01", "2020-04-01", "2020-05-01"],];
+        let inputs = [("2020-01-01", "2020-05-01")];
+        let checks_strs = [vec!["2020-01-01", "2020-02-01", "2020-03-01", "2020-04-01", "2020-05-01"]];
         assert_eq!(inputs.len(), checks_strs.len());
         for ((start, end), check_strs) in inputs.iter().zip(checks_strs.iter()) {
             let check_dates: Vec<NaiveDate> = check_strs.iter().map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d").unwrap()).collect();
             let date_range = DateRange::new(start, end);
-            let result = date_range.get_dates("m");
+            let result = date_range.get_dates(&Period::Month);
             assert_eq!(result, check_dates);
         }
     }
 
     #[test]
     fn test_years_range() {
-        let inputs = vec![("2020-01-01", "2020-12-31"), 
+        let inputs = [("2020-01-01", "2020-12-31"), 
                           ("2020-01-01", "2020-01-01"), 
                           ("2020-01-01", "2023-04-05"), 
                           ("2020", "2022-01-01"), 
                           ("1982", "2043"),
-                          ("2019-08", "2023-04"),];
-        let checks_len = vec![ 1, 1, 4, 3, 62, 5, ];
+                          ("2019-08", "2023-04")];
+        let checks_len = [1, 1, 4, 3, 62, 5];
         assert_eq!(inputs.len(), checks_len.len());
         for ((start, end), check_len) in inputs.iter().zip(checks_len.iter()) {
             let date_range = DateRange::new(start, end);
-            let result = date_range.get_dates("y");
+            let result = date_range.get_dates(&Period::Year);
             assert_eq!(result.len(), *check_len);
             assert_eq!(result[0].format("%Y").to_string(), start[0..4]);
             assert_eq!(result[result.len()-1].format("%Y").to_string(), end[0..4]);
         }
-        let inputs = vec![("2020-01-01", "2023-01-01"),];
-        let checks_strs = vec![vec!["2020-01-01", "2021-01-01", "2022-01-01", "2023-01-01"],];
+        let inputs = [("2020-01-01", "2023-01-01")];
+        let checks_strs = [vec!["2020-01-01", "2021-01-01", "2022-01-01", "2023-01-01"]];
         assert_eq!(inputs.len(), checks_strs.len());
         for ((start, end), check_strs) in inputs.iter().zip(checks_strs.iter()) {
             let check_dates: Vec<NaiveDate> = check_strs.iter().map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d").unwrap()).collect();
             let date_range = DateRange::new(start, end);
-            let result = date_range.get_dates("y");
+            let result = date_range.get_dates(&Period::Year);
             assert_eq!(result, check_dates);
         }
     }
+
+    #[test]
+    fn test_weeks_range() {
+        let inputs = [("2023-05-22", "2023-06-04"), 
+                          ("2023-05-24", "2023-05-24"), 
+                          ("2023-05-28", "2023-05-29")];
+        let checks_strs = [vec!["2023-05-22", "2023-05-29"],
+                           vec!["2023-05-22"],
+                           vec!["2023-05-22", "2023-05-29"]];
+        assert_eq!(inputs.len(), checks_strs.len());
+        for ((start, end), check_strs) in inputs.iter().zip(checks_strs.iter()) {
+            let check_dates: Vec<NaiveDate> = check_strs.iter().map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d").unwrap()).collect();
+            let date_range = DateRange::new(start, end);
+            let result = date_range.get_dates(&Period::Week);
+            assert_eq!(result, check_dates);
+        }
+    }
+
+    #[test]
+    fn test_quarters_range() {
+        let inputs = [("2022-11-15", "2023-05-01"), 
+                          ("2023-04-01", "2023-06-30")];
+        let checks_strs = [vec!["2022-10-01", "2023-01-01", "2023-04-01"],
+                           vec!["2023-04-01"]];
+        assert_eq!(inputs.len(), checks_strs.len());
+        for ((start, end), check_strs) in inputs.iter().zip(checks_strs.iter()) {
+            let check_dates: Vec<NaiveDate> = check_strs.iter().map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d").unwrap()).collect();
+            let date_range = DateRange::new(start, end);
+            let result = date_range.get_dates(&Period::Quarter);
+            assert_eq!(result, check_dates);
+        }
+    }
+
+    #[test]
+    fn test_get_period_keys() {
+        let date_range = DateRange::new("2022-12-31", "2023-01-02");
+        assert_eq!(date_range.get_period_keys(&Period::Day), vec!["2022-12-31", "2023-01-01", "2023-01-02"]);
+        assert_eq!(date_range.get_period_keys(&Period::Week), vec!["2022-W52", "2023-W01"]);
+        assert_eq!(date_range.get_period_keys(&Period::Month), vec!["2022-12", "2023-01"]);
+        assert_eq!(date_range.get_period_keys(&Period::Quarter), vec!["2022-Q4", "2023-Q1"]);
+        assert_eq!(date_range.get_period_keys(&Period::Year), vec!["2022", "2023"]);
+        let hours = date_range.get_period_keys(&Period::Hour);
+        assert_eq!(hours.len(), 3 * 24);
+        assert_eq!(hours[0], "2022-12-31T00");
+        assert_eq!(hours[hours.len()-1], "2023-01-02T23");
+        assert_eq!(date_range.get_period_keys(&Period::Weekday), vec!["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]);
+        assert_eq!(date_range.get_period_keys(&Period::HourOfDay).len(), 24);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_dates_invalid() {
+        DateRange::new("2023-01-01", "2023-01-02").get_dates(&Period::Weekday);
+    }
 }
 
 #[cfg(test)]
@@ -177,55 +250,171 @@ mod test_date_range_is_date_in_range {
 
 #[cfg(test)]
 mod test_date_range_get_missing {
+    use datetimescan::period::Period;
     use datetimescan::date_range::DateRange;
 
     #[test]
     fn test_get_missing_dates_days() {
-        let inputs = vec![vec!["2020-01-01", "2020-01-02", "2020-01-05", "2020-02-01"], 
+        let inputs = [vec!["2020-01-01", "2020-01-02", "2020-01-05", "2020-02-01"], 
                   vec!["2020-01-01", "2020-01-02"],
-                  vec!["2020-01-01"],];
-        let checks = vec![
-                vec!["2020-01-03", "2020-01-04", "2020-01-06"].iter().map(|&s| s.to_string()).chain(
+                  vec!["2020-01-01"]];
+        let checks = [["2020-01-03", "2020-01-04", "2020-01-06"].iter().map(|&s| s.to_string()).chain(
                             (7..=31).map(|i| format!("2020-01-{:02}", i))
                         ).collect(),
                 vec![],
-                vec![],];
+                vec![]];
         assert_eq!(inputs.len(), checks.len());
         for (dates, check) in inputs.iter().zip(checks.iter()) {
-            let missing = DateRange::get_missing_dates(dates, "d");
+            let missing = DateRange::get_missing_dates(dates, &Period::Day);
             assert_eq!(missing, *check);
         }
     }
 
     #[test]
     fn test_get_missing_dates_months() {
-        let inputs = vec![vec!["2020-01", "2020-03", "2020-04-01", "2020-05"], 
+        let inputs = [vec!["2020-01", "2020-03", "2020-04-01", "2020-05"], 
                         vec!["2020-01", "2020-02"],
-                        vec!["2020-01"],];
-        let checks = vec![vec!["2020-02"], 
+                        vec!["2020-01"]];
+        let checks = [vec!["2020-02"], 
                         vec![],
-                        vec![],];
+                        vec![]];
         assert_eq!(inputs.len(), checks.len());
         for (dates, check) in inputs.iter().zip(checks.iter()) {
-            let missing = DateRange::get_missing_dates(dates, "m");
+            let missing = DateRange::get_missing_dates(dates, &Period::Month);
             assert_eq!(missing, *check);
         }
     }
 
     #[test]
     fn test_get_missing_dates_years() {
-        let inputs = vec![vec!["2011", "2012", "2013", "2015", "2017",],
+        let inputs = [vec!["2011", "2012", "2013", "2015", "2017",],
                         vec!["2022", "2022"],
-                        vec!["2021"],];
-        let checks = vec![vec!["2014", "2016",],
+                        vec!["2021"]];
+        let checks = [vec!["2014", "2016",],
                         vec![],
-                        vec![],];
+                        vec![]];
         assert_eq!(inputs.len(), checks.len());
         for (dates, check) in inputs.iter().zip(checks.iter()) {
-            let missing = DateRange::get_missing_dates(dates, "y");
+            let missing = DateRange::get_missing_dates(dates, &Period::Year);
             assert_eq!(missing, *check);
         }
     }
 
 }
 
+
+#[cfg(test)]
+mod test_date_range_parse {
+    use chrono::{DateTime, NaiveDate};
+    use datetimescan::date_range::{DateRange, parse_date_ranges};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_inclusive() {
+        let checks = vec![
+            ("2023-05..2023-07", date(2023, 5, 1), date(2023, 7, 31)),
+            ("2023..2023", date(2023, 1, 1), date(2023, 12, 31)),
+            ("2023-05-01..", date(2023, 5, 1), NaiveDate::MAX),
+            ("..2023-06-15", NaiveDate::MIN, date(2023, 6, 15)),
+            ("..", NaiveDate::MIN, NaiveDate::MAX),
+            ("2024-02", date(2024, 2, 1), date(2024, 2, 29)),
+            ("2023-05-14", date(2023, 5, 14), date(2023, 5, 14)),
+        ];
+        for (range_str, start, end) in checks {
+            assert_eq!(DateRange::parse(range_str, true), Some(DateRange { start, end }), "range_str=({})", range_str);
+        }
+    }
+
+    #[test]
+    fn test_parse_half_open() {
+        let checks = vec![
+            ("2023-05..2023-07", date(2023, 5, 1), date(2023, 6, 30)),
+            ("2023..2024", date(2023, 1, 1), date(2023, 12, 31)),
+            ("..2023-06-15", NaiveDate::MIN, date(2023, 6, 14)),
+            ("2023-05-01..", date(2023, 5, 1), NaiveDate::MAX),
+            ("2023-05", date(2023, 5, 1), date(2023, 5, 31)),
+        ];
+        for (range_str, start, end) in checks {
+            assert_eq!(DateRange::parse(range_str, false), Some(DateRange { start, end }), "range_str=({})", range_str);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(DateRange::parse("2023-07..2023-05", true), None);
+        assert_eq!(DateRange::parse("2023-05..2023-05", false), None);
+        assert_eq!(DateRange::parse("2023-13..", true), None);
+        assert_eq!(DateRange::parse("invalid", true), None);
+        assert_eq!(DateRange::parse("", true), None);
+    }
+
+    #[test]
+    fn test_contains() {
+        let range = DateRange::parse("2023-05..2023-06", true).unwrap();
+        let checks = vec![
+            ("2023-04-30T23:59:59+10:00", false),
+            ("2023-05-01T00:00:00+10:00", true),
+            ("2023-06-30T23:59:59+10:00", true),
+            ("2023-06-30T23:59:59-10:00", true),
+            ("2023-07-01T00:00:00+10:00", false),
+        ];
+        for (datetime_str, check) in checks {
+            let datetime = DateTime::parse_from_rfc3339(datetime_str).unwrap();
+            assert_eq!(range.contains(&datetime), check, "datetime=({})", datetime_str);
+        }
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = DateRange::parse("2023-05..2023-07", true).unwrap();
+        let b = DateRange::parse("2023-06-15..", true).unwrap();
+        let c = DateRange::parse("..2023-04", true).unwrap();
+        assert_eq!(a.intersection(&b), Some(DateRange { start: date(2023, 6, 15), end: date(2023, 7, 31) }));
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.intersection(&a), Some(a));
+    }
+
+    #[test]
+    fn test_union() {
+        let a = DateRange::parse("2023-05..2023-07", true).unwrap();
+        let b = DateRange::parse("2023-06-15..2023-09", true).unwrap();
+        let c = DateRange::parse("2023-08..2023-09", true).unwrap();
+        let d = DateRange::parse("2023-10..", true).unwrap();
+        assert_eq!(a.union(&b), Some(DateRange { start: date(2023, 5, 1), end: date(2023, 9, 30) }));
+        assert_eq!(a.union(&c), Some(DateRange { start: date(2023, 5, 1), end: date(2023, 9, 30) }));
+        assert_eq!(c.union(&a), a.union(&c));
+        assert_eq!(a.union(&d), None);
+        assert_eq!(b.union(&d), Some(DateRange { start: date(2023, 6, 15), end: NaiveDate::MAX }));
+    }
+
+    #[test]
+    fn test_is_covered_by() {
+        let month = DateRange::parse("2023-05", true).unwrap();
+        let ranges = vec![
+            DateRange::parse("2023-05-10..", true).unwrap(),
+            DateRange::parse("2023-04-20..2023-05-05", true).unwrap(),
+            DateRange::parse("2023-05-06..2023-05-09", true).unwrap(),
+        ];
+        assert!(month.is_covered_by(&ranges));
+        assert!(!month.is_covered_by(&ranges[..2]));
+        assert!(!month.is_covered_by(&[]));
+        assert!(DateRange::parse("2023-05-12", true).unwrap().is_covered_by(&ranges[..1]));
+    }
+
+    #[test]
+    fn test_parse_date_ranges() {
+        let input = "2023-05-01\n  # comment\n2023-06..2023-08 # winter\n..2022\n";
+        let ranges = parse_date_ranges(input.as_bytes(), false).unwrap();
+        assert_eq!(ranges, vec![
+            DateRange { start: date(2023, 5, 1), end: date(2023, 5, 1) },
+            DateRange { start: date(2023, 6, 1), end: date(2023, 7, 31) },
+            DateRange { start: NaiveDate::MIN, end: date(2021, 12, 31) },
+        ]);
+        assert_eq!(parse_date_ranges("".as_bytes(), true), Ok(vec![]));
+        assert_eq!(parse_date_ranges("\n2023-05-32".as_bytes(), true), Err("invalid range=(2023-05-32) on line 2".to_string()));
+    }
+}
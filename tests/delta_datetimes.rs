@@ -102,7 +102,7 @@ mod test_difference_seconds {
     #[test]
     fn test_same_datetimes() {
         let dt1 = DateTime::parse_from_rfc3339("2023-05-01T00:00:00+00:00").unwrap();
-        let dt2 = dt1.clone();
+        let dt2 = dt1;
 
         assert_eq!(datetime_difference_seconds(dt1, dt2), 0);
     }
@@ -180,3 +180,503 @@ mod test_split_deltas {
     }
 }
 
+
+#[cfg(test)]
+mod test_split_datetimes_clipped {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{delta_datetimes, split_datetimes_clipped, split_deltas};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<DateTime<FixedOffset>> {
+        datetimes_strs.iter().map(|s| DateTime::parse_from_rfc3339(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_unclipped_matches_split_deltas() {
+        let datetimes = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:04:10+00:00",
+            "2023-05-11T00:02:00+00:00",
+            "2023-05-11T00:12:00+00:00",
+            "2023-05-11T00:13:40+00:00",
+        ]);
+        let full = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| (*b - *a).num_seconds() as u64;
+        let deltas = delta_datetimes(&datetimes, false);
+        assert_eq!(split_datetimes_clipped(&datetimes, 300, full), split_deltas(&deltas, 300));
+    }
+
+    #[test]
+    fn test_clipped_to_zero() {
+        let datetimes = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:10:00+00:00",
+            "2023-05-11T00:11:00+00:00",
+        ]);
+        let first_half_only = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| {
+            if a.format("%M").to_string().as_str() < "05" { (*b - *a).num_seconds() as u64 } else { 0 }
+        };
+        assert_eq!(split_datetimes_clipped(&datetimes, 300, first_half_only), vec![100]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let full = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| (*b - *a).num_seconds() as u64;
+        assert_eq!(split_datetimes_clipped(&vec![], 300, full), Vec::<u64>::new());
+    }
+}
+
+
+#[cfg(test)]
+mod test_split_datetimes_pairs {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{split_datetimes_clipped, split_datetimes_pairs};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<DateTime<FixedOffset>> {
+        datetimes_strs.iter().map(|s| DateTime::parse_from_rfc3339(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_matches_split_datetimes_clipped() {
+        let datetimes = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:04:10+00:00",
+            "2023-05-11T00:02:00+00:00",
+            "2023-05-11T00:12:00+00:00",
+            "2023-05-11T00:13:40+00:00",
+        ]);
+        let full = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| (*b - *a).num_seconds() as u64;
+        let sums: Vec<u64> = split_datetimes_pairs(&datetimes, 300).iter()
+            .map(|split| split.iter().map(|(a, b)| full(a, b)).sum())
+            .collect();
+        assert_eq!(sums, split_datetimes_clipped(&datetimes, 300, full));
+    }
+
+    #[test]
+    fn test_negative_skipped() {
+        let datetimes = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:00+00:00",
+            "2023-05-11T00:00:30+00:00",
+            "2023-05-11T00:02:00+00:00",
+        ]);
+        let result = split_datetimes_pairs(&datetimes, 300);
+        assert_eq!(result, vec![vec![(datetimes[0], datetimes[1]), (datetimes[2], datetimes[3])]]);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(split_datetimes_pairs(&vec![], 300).is_empty());
+        assert!(split_datetimes_pairs(&parse(&["2023-05-11T00:00:00+00:00"]), 300).is_empty());
+    }
+}
+
+
+#[cfg(test)]
+mod test_sessions {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{delta_datetimes, sessions, sessions_clipped, split_deltas};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<(DateTime<FixedOffset>, usize)> {
+        datetimes_strs.iter().enumerate().map(|(i, s)| (DateTime::parse_from_rfc3339(s).unwrap(), i * 2 + 1)).collect()
+    }
+
+    #[test]
+    fn test_durations_match_split_deltas() {
+        let datetimes_and_lines = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:04:10+00:00",
+            "2023-05-11T00:02:00+00:00",
+            "2023-05-11T00:12:00+00:00",
+            "2023-05-11T00:13:40+00:00",
+            "2023-05-11T01:00:00+00:00",
+        ]);
+        let datetimes = datetimes_and_lines.iter().map(|(x, _)| *x).collect();
+        let deltas = delta_datetimes(&datetimes, false);
+        let durations: Vec<u64> = sessions(&datetimes_and_lines, 300).iter()
+            .map(|session| session.duration)
+            .filter(|duration| *duration > 0)
+            .collect();
+        assert_eq!(durations, split_deltas(&deltas, 300));
+    }
+
+    #[test]
+    fn test_session_fields() {
+        let datetimes_and_lines = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:01:00+00:00",
+            "2023-05-11T00:20:00+00:00",
+        ]);
+        let result = sessions(&datetimes_and_lines, 300);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].start, datetimes_and_lines[0].0);
+        assert_eq!(result[0].end, datetimes_and_lines[1].0);
+        assert_eq!(result[0].duration, 100);
+        assert_eq!(result[0].events, 3);
+        assert_eq!((result[0].first_line, result[0].last_line), (1, 5));
+        assert_eq!(result[1].start, result[1].end);
+        assert_eq!(result[1].duration, 0);
+        assert_eq!(result[1].events, 1);
+        assert_eq!((result[1].first_line, result[1].last_line), (7, 7));
+    }
+
+    #[test]
+    fn test_clipped() {
+        let datetimes_and_lines = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+        ]);
+        let halved = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| (*b - *a).num_seconds() as u64 / 2;
+        assert_eq!(sessions_clipped(&datetimes_and_lines, 300, halved)[0].duration, 50);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(sessions(&[], 300).is_empty());
+    }
+}
+
+
+#[cfg(test)]
+mod test_gaps {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{gaps, sessions};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<(DateTime<FixedOffset>, usize)> {
+        datetimes_strs.iter().enumerate().map(|(i, s)| (DateTime::parse_from_rfc3339(s).unwrap(), i + 1)).collect()
+    }
+
+    #[test]
+    fn test_gaps_between_sessions() {
+        let datetimes_and_lines = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:20:00+00:00",
+            "2023-05-11T00:10:00+00:00",
+            "2023-05-11T00:12:00+00:00",
+            "2023-05-11T01:00:00+00:00",
+        ]);
+        let result = gaps(&datetimes_and_lines, 300);
+        assert_eq!(result.len(), 2);
+        assert_eq!((result[0].start_line, result[0].end_line, result[0].duration), (2, 3, 1100));
+        assert_eq!((result[1].start_line, result[1].end_line, result[1].duration), (5, 6, 2880));
+        assert_eq!(result.len() + 1, sessions(&datetimes_and_lines, 300).len());
+    }
+
+    #[test]
+    fn test_timeout_not_exceeded() {
+        let datetimes_and_lines = parse(&["2023-05-11T00:00:00+00:00", "2023-05-11T00:05:00+00:00"]);
+        assert!(gaps(&datetimes_and_lines, 300).is_empty());
+        assert_eq!(gaps(&datetimes_and_lines, 299).len(), 1);
+        assert!(gaps(&[], 300).is_empty());
+    }
+}
+
+
+#[cfg(test)]
+mod test_stats {
+    use datetimescan::delta_datetimes::{stats, percentile};
+
+    #[test]
+    fn test_stats() {
+        let values = [4.0, 2.0, 9.0, 5.0, 4.0, 5.0, 4.0, 7.0];
+        let result = stats(&values).unwrap();
+        assert_eq!(result.count, 8);
+        assert_eq!((result.min, result.max), (2.0, 9.0));
+        assert_eq!(result.mean, 5.0);
+        assert_eq!(result.median, 4.5);
+        assert_eq!(result.stddev, 2.0);
+        assert!((result.p90 - 7.6).abs() < 1e-9);
+        assert!((result.p95 - 8.3).abs() < 1e-9);
+        assert!((result.p99 - 8.86).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_single_value() {
+        let result = stats(&[300.0]).unwrap();
+        assert_eq!(result.count, 1);
+        assert_eq!((result.min, result.max, result.mean, result.median, result.p99), (300.0, 300.0, 300.0, 300.0, 300.0));
+        assert_eq!(result.stddev, 0.0);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(stats(&[]), None);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 2.5);
+        assert_eq!(percentile(&sorted, 100.0), 4.0);
+        assert_eq!(percentile(&sorted, 150.0), 4.0);
+    }
+}
+
+#[cfg(test)]
+mod test_suggest_timeout {
+    use datetimescan::delta_datetimes::{suggest_timeout, delta_histogram, histogram_bin_bounds, DEFAULT_TIMEOUT};
+
+    #[test]
+    fn test_histogram() {
+        let suggestion = suggest_timeout(&[1, 2, 3, 4, 5, 0, -10, 1024, 1025]);
+        assert_eq!(suggestion.histogram, vec![1, 1, 2, 1, 0, 0, 0, 0, 0, 0, 1, 1]);
+        assert_eq!(histogram_bin_bounds(10), (512, 1024));
+        assert_eq!(histogram_bin_bounds(11), (1024, 2048));
+    }
+
+    #[test]
+    fn test_delta_histogram() {
+        assert_eq!(delta_histogram(&[1, 2, 3, 4, 5, 0, -10, 1024, 1025]), suggest_timeout(&[1, 2, 3, 4, 5, 0, -10, 1024, 1025]).histogram);
+        assert!(delta_histogram(&[0, -5]).is_empty());
+    }
+
+    #[test]
+    fn test_valley() {
+        let mut deltas = vec![10; 20];
+        deltas.extend(vec![20; 8]);
+        deltas.extend(vec![200; 2]);
+        deltas.extend(vec![3000; 6]);
+        let suggestion = suggest_timeout(&deltas);
+        assert_eq!(suggestion.valley, Some((4, 9, 12)));
+        assert_eq!(suggestion.timeout, 512);
+        assert!(suggestion.rationale().starts_with("valley at (256s, 512s] between peaks"));
+    }
+
+    #[test]
+    fn test_shallow_valley_ignored() {
+        let mut deltas = vec![10; 20];
+        deltas.extend(vec![20; 7]);
+        deltas.extend(vec![40; 8]);
+        let suggestion = suggest_timeout(&deltas);
+        assert_eq!(suggestion.valley, None);
+        assert_eq!(suggestion.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(suggestion.rationale(), "no valley between peaks in log2 histogram of 35 positive deltas, using default timeout");
+    }
+
+    #[test]
+    fn test_too_few_deltas() {
+        let suggestion = suggest_timeout(&[10, 10, 10, 5000, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(suggestion.valley, None);
+        assert_eq!(suggestion.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(suggest_timeout(&[]).timeout, DEFAULT_TIMEOUT);
+        assert!(suggest_timeout(&[]).histogram.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_apply_model {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{sessions, apply_model, Model};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<(DateTime<FixedOffset>, usize)> {
+        datetimes_strs.iter().enumerate().map(|(i, s)| (DateTime::parse_from_rfc3339(s).unwrap(), i + 1)).collect()
+    }
+
+    fn durations(datetimes_and_lines: &[(DateTime<FixedOffset>, usize)], model: &Model) -> Vec<u64> {
+        let mut result = sessions(datetimes_and_lines, 300);
+        apply_model(&mut result, model, 300);
+        result.iter().map(|x| x.duration).collect()
+    }
+
+    fn datetimes_and_lines() -> Vec<(DateTime<FixedOffset>, usize)> {
+        parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:40+00:00",
+            "2023-05-11T00:10:00+00:00",
+            "2023-05-11T00:12:00+00:00",
+            "2023-05-11T01:00:00+00:00",
+        ])
+    }
+
+    #[test]
+    fn test_discard() {
+        assert_eq!(durations(&datetimes_and_lines(), &Model::Discard), vec![100, 120, 0]);
+    }
+
+    #[test]
+    fn test_capped() {
+        assert_eq!(durations(&datetimes_and_lines(), &Model::Capped), vec![400, 420, 0]);
+    }
+
+    #[test]
+    fn test_credit() {
+        assert_eq!(durations(&datetimes_and_lines(), &Model::Credit(60)), vec![120, 120, 60]);
+    }
+
+    #[test]
+    fn test_padded() {
+        assert_eq!(durations(&datetimes_and_lines(), &Model::Padded(60)), vec![220, 240, 120]);
+        // gap between the first and second sessions is 500s, shared between their padding
+        assert_eq!(durations(&datetimes_and_lines(), &Model::Padded(300)), vec![650, 670, 600]);
+    }
+
+    #[test]
+    fn test_empty() {
+        for model in [Model::Discard, Model::Capped, Model::Credit(60), Model::Padded(60)] {
+            assert!(durations(&[], &model).is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_negative_policy {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{order_datetimes, split_datetimes_runs, delta_datetimes_with_policy, NegativePolicy};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<DateTime<FixedOffset>> {
+        datetimes_strs.iter().map(|x| DateTime::parse_from_rfc3339(x).unwrap()).collect()
+    }
+
+    fn datetimes() -> Vec<DateTime<FixedOffset>> {
+        parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:01:00+00:00",
+            "2023-05-11T00:00:30+00:00",
+            "2023-05-11T00:02:00+00:00",
+        ])
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("clamp".parse::<NegativePolicy>(), Ok(NegativePolicy::Clamp));
+        assert_eq!("break".parse::<NegativePolicy>(), Ok(NegativePolicy::Break));
+        assert_eq!("drop".parse::<NegativePolicy>(), Ok(NegativePolicy::Drop));
+        assert_eq!("sort".parse::<NegativePolicy>(), Ok(NegativePolicy::Sort));
+        assert_eq!("skip".parse::<NegativePolicy>(), Err("unsupported negative=(skip) (must be clamp/break/drop/sort)".to_string()));
+    }
+
+    #[test]
+    fn test_order_datetimes() {
+        let input = datetimes();
+        assert_eq!(order_datetimes(input.clone(), &NegativePolicy::Clamp, |x| *x), input);
+        assert_eq!(order_datetimes(input.clone(), &NegativePolicy::Break, |x| *x), input);
+        assert_eq!(order_datetimes(input.clone(), &NegativePolicy::Drop, |x| *x), vec![input[0], input[1], input[3]]);
+        assert_eq!(order_datetimes(input.clone(), &NegativePolicy::Sort, |x| *x), vec![input[0], input[2], input[1], input[3]]);
+    }
+
+    #[test]
+    fn test_order_datetimes_drop_outlier() {
+        let input = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2099-01-01T00:00:00+00:00",
+            "2023-05-11T00:01:00+00:00",
+            "2023-05-11T00:02:00+00:00",
+        ]);
+        assert_eq!(order_datetimes(input.clone(), &NegativePolicy::Drop, |x| *x), vec![input[0], input[2], input[3]]);
+    }
+
+    #[test]
+    fn test_split_datetimes_runs() {
+        let input = datetimes();
+        assert_eq!(split_datetimes_runs(&input, &NegativePolicy::Clamp, |x| *x), vec![input.clone()]);
+        assert_eq!(split_datetimes_runs(&input, &NegativePolicy::Break, |x| *x), vec![input[0..2].to_vec(), input[2..4].to_vec()]);
+        assert!(split_datetimes_runs(&[], &NegativePolicy::Break, |x: &DateTime<FixedOffset>| *x).is_empty());
+    }
+
+    #[test]
+    fn test_delta_datetimes_with_policy() {
+        let input = datetimes();
+        assert_eq!(delta_datetimes_with_policy(&input, &NegativePolicy::Clamp), vec![60, 0, 90]);
+        assert_eq!(delta_datetimes_with_policy(&input, &NegativePolicy::Break), vec![60, -30, 90]);
+        assert_eq!(delta_datetimes_with_policy(&input, &NegativePolicy::Drop), vec![60, 60]);
+        assert_eq!(delta_datetimes_with_policy(&input, &NegativePolicy::Sort), vec![30, 30, 60]);
+    }
+}
+
+#[cfg(test)]
+mod test_sort_dedup {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{sort_datetimes, dedup_datetimes};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<(DateTime<FixedOffset>, usize)> {
+        datetimes_strs.iter().enumerate().map(|(i, x)| (DateTime::parse_from_rfc3339(x).unwrap(), i+1)).collect()
+    }
+
+    #[test]
+    fn test_sort_datetimes() {
+        let input = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T09:00:00+00:00",
+            "2023-05-11T00:01:00+00:00",
+            "2023-05-11T00:02:00+00:00",
+            "2023-05-11T00:01:00+00:00",
+        ]);
+        let (result, reordered) = sort_datetimes(input.clone(), |x| x.0);
+        let lines: Vec<usize> = result.iter().map(|x| x.1).collect();
+        assert_eq!(lines, vec![1, 3, 5, 4, 2]);
+        assert_eq!(reordered, 2);
+    }
+
+    #[test]
+    fn test_sort_datetimes_sorted() {
+        let input = parse(&["2023-05-11T00:00:00+00:00", "2023-05-11T00:00:00+00:00", "2023-05-11T00:01:00+00:00"]);
+        assert_eq!(sort_datetimes(input.clone(), |x| x.0), (input, 0));
+        assert_eq!(sort_datetimes(vec![], |x: &(DateTime<FixedOffset>, usize)| x.0), (vec![], 0));
+    }
+
+    #[test]
+    fn test_dedup_datetimes() {
+        let input = parse(&[
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:00:00+00:00",
+            "2023-05-11T00:00:05+00:00",
+            "2023-05-11T00:00:12+00:00",
+            "2023-05-11T00:00:02+00:00",
+        ]);
+        let lines = |within: u64| -> Vec<usize> { dedup_datetimes(input.clone(), within, |x| x.0).iter().map(|x| x.1).collect() };
+        assert_eq!(lines(0), vec![1, 3, 4, 5]);
+        assert_eq!(lines(5), vec![1, 4, 5]);
+        assert_eq!(lines(10), vec![1, 4]);
+    }
+}
+
+#[cfg(test)]
+mod test_anomalies {
+    use chrono::{DateTime, FixedOffset};
+    use datetimescan::delta_datetimes::{anomalies, AnomalyKind};
+
+    fn parse(datetimes_strs: &[&str]) -> Vec<(DateTime<FixedOffset>, usize)> {
+        datetimes_strs.iter().enumerate().map(|(i, x)| (DateTime::parse_from_rfc3339(x).unwrap(), i+1)).collect()
+    }
+
+    fn found(datetimes_strs: &[&str], dst_tolerance: u64, min_skew: u64) -> Vec<(AnomalyKind, i64, usize, usize)> {
+        anomalies(&parse(datetimes_strs), dst_tolerance, min_skew).iter().map(|x| (x.kind, x.delta, x.start_line, x.end_line)).collect()
+    }
+
+    #[test]
+    fn test_none() {
+        assert!(found(&[], 900, 60).is_empty());
+        assert!(found(&["2023-05-11T00:00:00+10:00", "2023-05-11T00:05:00+10:00", "2023-05-11T00:04:30+10:00"], 900, 60).is_empty());
+    }
+
+    #[test]
+    fn test_offset_change() {
+        let datetimes = ["2023-04-02T02:30:00+11:00", "2023-04-02T02:10:00+10:00", "2023-04-02T02:20:00+10:00"];
+        assert_eq!(found(&datetimes, 900, 60), vec![(AnomalyKind::OffsetChange, 2400, 1, 2)]);
+    }
+
+    #[test]
+    fn test_dst_repeat() {
+        let datetimes = ["2023-04-02T02:55:00+11:00", "2023-04-02T02:05:00+11:00", "2023-04-02T03:05:00+11:00", "2023-04-02T02:01:00+11:00"];
+        assert_eq!(found(&datetimes, 900, 60), vec![(AnomalyKind::DstRepeat, -3000, 1, 2), (AnomalyKind::DstRepeat, -3840, 3, 4)]);
+        assert_eq!(found(&datetimes, 300, 60), vec![(AnomalyKind::Skew, -3000, 1, 2), (AnomalyKind::DstRepeat, -3840, 3, 4)]);
+    }
+
+    #[test]
+    fn test_skew() {
+        let datetimes = ["2023-05-11T00:10:00+00:00", "2023-05-11T00:05:00+00:00", "2023-05-11T00:06:00+00:00", "2023-05-11T00:05:30+00:00"];
+        assert_eq!(found(&datetimes, 900, 60), vec![(AnomalyKind::Skew, -300, 1, 2)]);
+        assert_eq!(found(&datetimes, 900, 10), vec![(AnomalyKind::Skew, -300, 1, 2), (AnomalyKind::Skew, -30, 3, 4)]);
+    }
+
+    #[test]
+    fn test_offset_change_and_skew() {
+        let datetimes = ["2023-05-11T09:00:00+10:00", "2023-05-11T00:00:00+04:00"];
+        assert_eq!(found(&datetimes, 900, 60), vec![(AnomalyKind::OffsetChange, -10800, 1, 2), (AnomalyKind::Skew, -10800, 1, 2)]);
+    }
+}
@@ -1,8 +1,11 @@
 
 #[cfg(test)]
 mod test_group_datetimes {
-    use datetimescan::group_datetimes::group_datetimes;
-    use chrono::DateTime;
+    use datetimescan::group_datetimes::{group_datetimes, group_datetimes_with_calendar, group_splits_apportioned};
+    use datetimescan::calendar::Calendar;
+    use datetimescan::timezone::TimeZoneSpec;
+    use datetimescan::period::Period;
+    use chrono::{DateTime, FixedOffset};
 
     #[test]
     fn test_by_day() {
@@ -13,12 +16,12 @@ mod test_group_datetimes {
         let dt5 = DateTime::parse_from_rfc3339("2023-05-16T13:00:00+00:00").unwrap();
         let datetimes = vec![dt1, dt2, dt3, dt4, dt5];
 
-        let result = group_datetimes(&datetimes, "d");
+        let result = group_datetimes(&datetimes, &Period::Day);
 
         assert_eq!(result.len(), 3);
-        assert_eq!(result.get(&"2023-05-14".to_string()).unwrap().len(), 2);
-        assert_eq!(result.get(&"2023-05-15".to_string()).unwrap().len(), 1);
-        assert_eq!(result.get(&"2023-05-16".to_string()).unwrap().len(), 2);
+        assert_eq!(result.get("2023-05-14").unwrap().len(), 2);
+        assert_eq!(result.get("2023-05-15").unwrap().len(), 1);
+        assert_eq!(result.get("2023-05-16").unwrap().len(), 2);
     }
 
     #[test]
@@ -30,12 +33,12 @@ mod test_group_datetimes {
         let dt5 = DateTime::parse_from_rfc3339("2023-07-16T13:00:00+00:00").unwrap();
         let datetimes = vec![dt1, dt2, dt3, dt4, dt5];
 
-        let result = group_datetimes(&datetimes, "m");
+        let result = group_datetimes(&datetimes, &Period::Month);
 
         assert_eq!(result.len(), 3);
-        assert_eq!(result.get(&"2023-05".to_string()).unwrap().len(), 2);
-        assert_eq!(result.get(&"2023-06".to_string()).unwrap().len(), 1);
-        assert_eq!(result.get(&"2023-07".to_string()).unwrap().len(), 2);
+        assert_eq!(result.get("2023-05").unwrap().len(), 2);
+        assert_eq!(result.get("2023-06").unwrap().len(), 1);
+        assert_eq!(result.get("2023-07").unwrap().len(), 2);
     }
 
     #[test]
@@ -47,22 +50,132 @@ mod test_group_datetimes {
         let dt5 = DateTime::parse_from_rfc3339("2025-07-16T13:00:00+00:00").unwrap();
         let datetimes = vec![dt1, dt2, dt3, dt4, dt5];
 
-        let result = group_datetimes(&datetimes, "y");
+        let result = group_datetimes(&datetimes, &Period::Year);
 
         assert_eq!(result.len(), 3);
-        assert_eq!(result.get(&"2023".to_string()).unwrap().len(), 2);
-        assert_eq!(result.get(&"2024".to_string()).unwrap().len(), 1);
-        assert_eq!(result.get(&"2025".to_string()).unwrap().len(), 2);
+        assert_eq!(result.get("2023").unwrap().len(), 2);
+        assert_eq!(result.get("2024").unwrap().len(), 1);
+        assert_eq!(result.get("2025").unwrap().len(), 2);
     }
 
     #[test]
-    #[should_panic(expected = "unsupported interval=(invalid) (must be d/m/y)")]
-    fn test_invalid_date_interval() {
+    fn test_by_hour() {
+        let dt1 = DateTime::parse_from_rfc3339("2023-05-14T12:00:00+00:00").unwrap();
+        let dt2 = DateTime::parse_from_rfc3339("2023-05-14T12:59:59+00:00").unwrap();
+        let dt3 = DateTime::parse_from_rfc3339("2023-05-14T13:00:00+00:00").unwrap();
+        let datetimes = vec![dt1, dt2, dt3];
+
+        let result = group_datetimes(&datetimes, &Period::Hour);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("2023-05-14T12").unwrap().len(), 2);
+        assert_eq!(result.get("2023-05-14T13").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_by_week() {
+        //  2023-01-01 is a Sunday in ISO week 2022-W52
+        let dt1 = DateTime::parse_from_rfc3339("2023-01-01T12:00:00+00:00").unwrap();
+        let dt2 = DateTime::parse_from_rfc3339("2023-01-02T12:00:00+00:00").unwrap();
+        let dt3 = DateTime::parse_from_rfc3339("2023-01-08T12:00:00+00:00").unwrap();
+        let datetimes = vec![dt1, dt2, dt3];
+
+        let result = group_datetimes(&datetimes, &Period::Week);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("2022-W52").unwrap().len(), 1);
+        assert_eq!(result.get("2023-W01").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_by_quarter() {
+        let dt1 = DateTime::parse_from_rfc3339("2023-03-31T12:00:00+00:00").unwrap();
+        let dt2 = DateTime::parse_from_rfc3339("2023-04-01T12:00:00+00:00").unwrap();
+        let dt3 = DateTime::parse_from_rfc3339("2023-06-30T12:00:00+00:00").unwrap();
+        let dt4 = DateTime::parse_from_rfc3339("2023-12-31T12:00:00+00:00").unwrap();
+        let datetimes = vec![dt1, dt2, dt3, dt4];
+
+        let result = group_datetimes(&datetimes, &Period::Quarter);
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.get("2023-Q1").unwrap().len(), 1);
+        assert_eq!(result.get("2023-Q2").unwrap().len(), 2);
+        assert_eq!(result.get("2023-Q4").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_by_weekday() {
         let dt1 = DateTime::parse_from_rfc3339("2023-05-14T12:00:00+00:00").unwrap();
         let dt2 = DateTime::parse_from_rfc3339("2023-05-15T12:00:00+00:00").unwrap();
-        let datetimes = vec![dt1, dt2];
+        let dt3 = DateTime::parse_from_rfc3339("2023-05-22T12:00:00+00:00").unwrap();
+        let datetimes = vec![dt1, dt2, dt3];
+
+        let result = group_datetimes(&datetimes, &Period::Weekday);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("Sun").unwrap().len(), 1);
+        assert_eq!(result.get("Mon").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_by_hour_of_day() {
+        let dt1 = DateTime::parse_from_rfc3339("2023-05-14T09:00:00+00:00").unwrap();
+        let dt2 = DateTime::parse_from_rfc3339("2023-05-15T09:30:00+00:00").unwrap();
+        let dt3 = DateTime::parse_from_rfc3339("2023-05-15T21:00:00+00:00").unwrap();
+        let datetimes = vec![dt1, dt2, dt3];
+
+        let result = group_datetimes(&datetimes, &Period::HourOfDay);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("09").unwrap().len(), 2);
+        assert_eq!(result.get("21").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_by_day_with_calendar() {
+        //  The same instant, written in different offsets
+        let dt1 = DateTime::parse_from_rfc3339("2023-05-14T23:30:00+10:00").unwrap();
+        let dt2 = DateTime::parse_from_rfc3339("2023-05-14T13:30:00+00:00").unwrap();
+        let dt3 = DateTime::parse_from_rfc3339("2023-05-15T00:30:00+11:00").unwrap();
+        let datetimes = vec![dt1, dt2, dt3];
+
+        let result = group_datetimes(&datetimes, &Period::Day);
+        assert_eq!(result.len(), 2);
+
+        let calendar = Calendar::new(TimeZoneSpec::parse("Australia/Melbourne"));
+        let result = group_datetimes_with_calendar(&datetimes, &Period::Day, &calendar);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get("2023-05-14").unwrap().len(), 3);
+
+        let calendar = Calendar::new(TimeZoneSpec::parse("UTC"));
+        let result = group_datetimes_with_calendar(&datetimes, &Period::Day, &calendar);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get("2023-05-14").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_splits_apportioned_total() {
+        let datetimes: Vec<DateTime<FixedOffset>> = [
+            "2023-05-14T23:50:00+10:00",
+            "2023-05-14T23:55:00+10:00",
+            "2023-05-15T00:00:00+10:00",
+            "2023-05-15T00:04:00+10:00",
+            "2023-05-15T09:00:00+10:00",
+            "2023-05-15T09:01:00+10:00",
+        ].iter().map(|s| DateTime::parse_from_rfc3339(s).unwrap()).collect();
+        let seconds = |a: &DateTime<FixedOffset>, b: &DateTime<FixedOffset>| (*b - *a).num_seconds() as u64;
+
+        let result = group_splits_apportioned(&datetimes, 300, &Period::Day, &Calendar::default(), seconds);
+        assert_eq!(result.get("2023-05-14"), Some(&vec![600]));
+        assert_eq!(result.get("2023-05-15"), Some(&vec![240, 60]));
+
+        let result = group_splits_apportioned(&datetimes, 300, &Period::All, &Calendar::default(), seconds);
+        assert_eq!(result.get("all"), Some(&vec![840, 60]));
 
-        group_datetimes(&datetimes, "invalid");
+        let result = group_splits_apportioned(&datetimes, 300, &Period::HourOfDay, &Calendar::default(), seconds);
+        assert_eq!(result.get("23"), Some(&vec![600]));
+        assert_eq!(result.get("00"), Some(&vec![240]));
+        assert_eq!(result.get("09"), Some(&vec![60]));
     }
 }
 
@@ -103,3 +103,56 @@ mod test_parse_datetime {
     }
 }
 
+
+#[cfg(test)]
+mod test_parse_datetime_with_tz {
+    use datetimescan::parse_datetime::{parse_datetime_with_tz, parse_datetimes_with_tz};
+    use datetimescan::timezone::TimeZoneSpec;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_no_offset_uses_tz() {
+        let tz = TimeZoneSpec::parse("-05:00").unwrap();
+        assert_eq!(
+            parse_datetime_with_tz("2023-05-08T18:30:00", &tz).unwrap(),
+            DateTime::parse_from_rfc3339("2023-05-08T18:30:00-05:00").unwrap()
+        );
+        assert_eq!(
+            parse_datetime_with_tz("2023-05-08 18:30:00", &tz).unwrap(),
+            DateTime::parse_from_rfc3339("2023-05-08T18:30:00-05:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_offset_uses_offset_in_effect() {
+        let tz = TimeZoneSpec::parse("Australia/Sydney").unwrap();
+        assert_eq!(
+            parse_datetime_with_tz("2023-01-08T18:30:00", &tz).unwrap(),
+            DateTime::parse_from_rfc3339("2023-01-08T18:30:00+11:00").unwrap()
+        );
+        assert_eq!(
+            parse_datetime_with_tz("2023-06-08T18:30:00", &tz).unwrap(),
+            DateTime::parse_from_rfc3339("2023-06-08T18:30:00+10:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_offset_ignores_tz() {
+        let tz = TimeZoneSpec::parse("-05:00").unwrap();
+        assert_eq!(
+            parse_datetime_with_tz("2023-05-08T18:30:00AEST", &tz).unwrap(),
+            DateTime::parse_from_rfc3339("2023-05-08T18:30:00+10:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_datetimes_with_tz() {
+        let tz = TimeZoneSpec::parse("+01:00").unwrap();
+        let datetimes = vec!["2023-05-08T18:30:00".to_string(), "2023-05-08T18:30:00+00:00".to_string()];
+        let expected = vec![
+            DateTime::parse_from_rfc3339("2023-05-08T18:30:00+01:00").unwrap(),
+            DateTime::parse_from_rfc3339("2023-05-08T18:30:00+00:00").unwrap(),
+        ];
+        assert_eq!(parse_datetimes_with_tz(&datetimes, &tz), Some(expected));
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod test_period {
+    use datetimescan::period::Period;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, mi: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, mi, 0).unwrap()
+    }
+
+    const PERIODS: [Period; 9] = [Period::Hour, Period::Day, Period::Week, Period::Month, Period::Quarter, Period::Year, Period::Weekday, Period::HourOfDay, Period::All];
+
+    #[test]
+    fn test_from_str() {
+        for period in PERIODS {
+            assert_eq!(period.to_string().parse::<Period>(), Ok(period));
+        }
+        assert_eq!("D".parse::<Period>(), Ok(Period::Day));
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let result = "invalid".parse::<Period>();
+        assert_eq!(result, Err("unsupported period=(invalid) (must be h/d/w/m/q/y/weekday/hour_of_day/all)".to_string()));
+    }
+
+    #[test]
+    fn test_key() {
+        let local = datetime(2023, 1, 1, 9, 30);
+        let checks = [
+            (Period::Hour, "2023-01-01T09"),
+            (Period::Day, "2023-01-01"),
+            (Period::Week, "2022-W52"),
+            (Period::Month, "2023-01"),
+            (Period::Quarter, "2023-Q1"),
+            (Period::Year, "2023"),
+            (Period::Weekday, "Sun"),
+            (Period::HourOfDay, "09"),
+            (Period::All, "all"),
+        ];
+        for (period, expected) in checks {
+            assert_eq!(period.key(&local), expected, "period=({})", period);
+        }
+    }
+
+    #[test]
+    fn test_parse_key() {
+        let checks = [
+            (Period::Hour, "2023-01-01T09", datetime(2023, 1, 1, 9, 0)),
+            (Period::Day, "2023-01-01", datetime(2023, 1, 1, 0, 0)),
+            (Period::Week, "2022-W52", datetime(2022, 12, 26, 0, 0)),
+            (Period::Month, "2023-01", datetime(2023, 1, 1, 0, 0)),
+            (Period::Quarter, "2023-Q4", datetime(2023, 10, 1, 0, 0)),
+            (Period::Year, "2023", datetime(2023, 1, 1, 0, 0)),
+        ];
+        for (period, key, expected) in checks {
+            assert_eq!(period.parse_key(key), Some(expected), "period=({})", period);
+            assert_eq!(period.key(&expected), key);
+        }
+        assert_eq!(Period::Quarter.parse_key("2023-Q5"), None);
+        assert_eq!(Period::Day.parse_key("2023-01"), None);
+        assert_eq!(Period::Weekday.parse_key("Mon"), None);
+    }
+
+    #[test]
+    fn test_truncate_next() {
+        let local = datetime(2023, 11, 15, 13, 45);
+        let checks = [
+            (Period::Hour, datetime(2023, 11, 15, 13, 0), datetime(2023, 11, 15, 14, 0)),
+            (Period::Day, datetime(2023, 11, 15, 0, 0), datetime(2023, 11, 16, 0, 0)),
+            (Period::Week, datetime(2023, 11, 13, 0, 0), datetime(2023, 11, 20, 0, 0)),
+            (Period::Month, datetime(2023, 11, 1, 0, 0), datetime(2023, 12, 1, 0, 0)),
+            (Period::Quarter, datetime(2023, 10, 1, 0, 0), datetime(2024, 1, 1, 0, 0)),
+            (Period::Year, datetime(2023, 1, 1, 0, 0), datetime(2024, 1, 1, 0, 0)),
+            (Period::All, NaiveDateTime::MIN, NaiveDateTime::MAX),
+        ];
+        for (period, start, next) in checks {
+            assert_eq!(period.truncate(&local), start, "period=({})", period);
+            assert_eq!(period.next(&start), next, "period=({})", period);
+        }
+    }
+
+    #[test]
+    fn test_cyclic() {
+        assert!(Period::Weekday.is_cyclic());
+        assert!(Period::HourOfDay.is_cyclic());
+        assert!(!Period::Day.is_cyclic());
+        assert_eq!(Period::Weekday.cyclic_keys(), vec!["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]);
+        assert_eq!(Period::HourOfDay.cyclic_keys().len(), 24);
+        assert!(Period::Day.cyclic_keys().is_empty());
+    }
+
+    #[test]
+    fn test_sort_keys() {
+        let mut keys: Vec<String> = ["Sun", "Tue", "Mon", "Sat"].iter().map(|x| x.to_string()).collect();
+        Period::Weekday.sort_keys(&mut keys);
+        assert_eq!(keys, vec!["Mon", "Tue", "Sat", "Sun"]);
+        let mut keys: Vec<String> = ["2023-W02", "2022-W52", "2023-W01"].iter().map(|x| x.to_string()).collect();
+        Period::Week.sort_keys(&mut keys);
+        assert_eq!(keys, vec!["2022-W52", "2023-W01", "2023-W02"]);
+    }
+}
+
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod test_parse {
+    use chrono::{NaiveTime, Weekday};
+    use datetimescan::recurring_filter::{parse_time_of_day_range, parse_weekdays};
+
+    #[test]
+    fn test_parse_time_of_day_range() {
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert_eq!(parse_time_of_day_range("09:00-17:30"), Some((t(9, 0), t(17, 30))));
+        assert_eq!(parse_time_of_day_range("9-17"), Some((t(9, 0), t(17, 0))));
+        assert_eq!(parse_time_of_day_range("22:00-06:00"), Some((t(22, 0), t(6, 0))));
+        assert_eq!(parse_time_of_day_range("09:00-09:00"), None);
+        assert_eq!(parse_time_of_day_range("09:00-25:00"), None);
+        assert_eq!(parse_time_of_day_range("09:00"), None);
+    }
+
+    #[test]
+    fn test_parse_weekdays() {
+        use Weekday::*;
+        assert_eq!(parse_weekdays("mon-fri"), Some(vec![Mon, Tue, Wed, Thu, Fri]));
+        assert_eq!(parse_weekdays("Sat,Sun"), Some(vec![Sat, Sun]));
+        assert_eq!(parse_weekdays("mon,wed-thu"), Some(vec![Mon, Wed, Thu]));
+        assert_eq!(parse_weekdays("sat-mon"), Some(vec![Sat, Sun, Mon]));
+        assert_eq!(parse_weekdays("tuesday"), Some(vec![Tue]));
+        assert_eq!(parse_weekdays("mon-"), None);
+        assert_eq!(parse_weekdays(""), None);
+    }
+}
+
+#[cfg(test)]
+mod test_recurring_filter {
+    use chrono::DateTime;
+    use datetimescan::recurring_filter::{RecurringFilter, parse_time_of_day_range, parse_weekdays};
+    use datetimescan::timezone::TimeZoneSpec;
+
+    fn working_hours() -> RecurringFilter {
+        RecurringFilter {
+            time_of_day: parse_time_of_day_range("09:00-17:30"),
+            weekdays: parse_weekdays("mon-fri"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let filter = working_hours();
+        //  2023-05-26 is a Friday
+        let checks = vec![
+            ("2023-05-26T08:59:59+10:00", false),
+            ("2023-05-26T09:00:00+10:00", true),
+            ("2023-05-26T17:29:59+10:00", true),
+            ("2023-05-26T17:30:00+10:00", false),
+            ("2023-05-27T12:00:00+10:00", false),
+            ("2023-05-26T23:00:00+00:00", false),
+        ];
+        for (datetime_str, check) in checks {
+            let datetime = DateTime::parse_from_rfc3339(datetime_str).unwrap();
+            assert_eq!(filter.contains(&datetime), check, "datetime=({})", datetime_str);
+        }
+    }
+
+    #[test]
+    fn test_contains_invert() {
+        let filter = RecurringFilter { invert: true, ..working_hours() };
+        assert!(filter.contains(&DateTime::parse_from_rfc3339("2023-05-26T08:59:59+10:00").unwrap()));
+        assert!(!filter.contains(&DateTime::parse_from_rfc3339("2023-05-26T09:00:00+10:00").unwrap()));
+        assert!(filter.contains(&DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap()));
+    }
+
+    #[test]
+    fn test_contains_wrapping() {
+        let filter = RecurringFilter { time_of_day: parse_time_of_day_range("22:00-02:00"), ..Default::default() };
+        assert!(filter.contains(&DateTime::parse_from_rfc3339("2023-05-26T23:00:00+10:00").unwrap()));
+        assert!(filter.contains(&DateTime::parse_from_rfc3339("2023-05-27T01:59:59+10:00").unwrap()));
+        assert!(!filter.contains(&DateTime::parse_from_rfc3339("2023-05-27T02:00:00+10:00").unwrap()));
+    }
+
+    #[test]
+    fn test_contains_tz() {
+        let filter = RecurringFilter { tz: TimeZoneSpec::parse("Australia/Melbourne"), ..working_hours() };
+        assert!(filter.contains(&DateTime::parse_from_rfc3339("2023-05-25T23:00:00+00:00").unwrap()));
+        assert!(!filter.contains(&DateTime::parse_from_rfc3339("2023-05-26T23:00:00+00:00").unwrap()));
+    }
+
+    #[test]
+    fn test_overlap_seconds() {
+        let filter = working_hours();
+        let checks = vec![
+            ("2023-05-26T08:00:00+10:00", "2023-05-26T10:00:00+10:00", 3600),
+            ("2023-05-26T10:00:00+10:00", "2023-05-26T10:05:00+10:00", 300),
+            ("2023-05-26T17:00:00+10:00", "2023-05-29T09:30:00+10:00", 3600),
+            ("2023-05-27T10:00:00+10:00", "2023-05-27T12:00:00+10:00", 0),
+            ("2023-05-26T10:05:00+10:00", "2023-05-26T10:00:00+10:00", 0),
+        ];
+        for (start, end, check) in checks {
+            let start = DateTime::parse_from_rfc3339(start).unwrap();
+            let end = DateTime::parse_from_rfc3339(end).unwrap();
+            assert_eq!(filter.overlap_seconds(&start, &end), check, "start=({}), end=({})", start, end);
+        }
+    }
+
+    #[test]
+    fn test_overlap_seconds_invert_complements() {
+        let filter = working_hours();
+        let inverted = RecurringFilter { invert: true, ..working_hours() };
+        let start = DateTime::parse_from_rfc3339("2023-05-24T13:17:00+10:00").unwrap();
+        let end = DateTime::parse_from_rfc3339("2023-05-29T04:41:00+10:00").unwrap();
+        let total = (end - start).num_seconds() as u64;
+        assert_eq!(filter.overlap_seconds(&start, &end) + inverted.overlap_seconds(&start, &end), total);
+    }
+
+    #[test]
+    fn test_overlap_seconds_wrapping() {
+        let filter = RecurringFilter { time_of_day: parse_time_of_day_range("22:00-02:00"), ..Default::default() };
+        let start = DateTime::parse_from_rfc3339("2023-05-26T21:00:00+10:00").unwrap();
+        let end = DateTime::parse_from_rfc3339("2023-05-27T03:00:00+10:00").unwrap();
+        assert_eq!(filter.overlap_seconds(&start, &end), 4 * 3600);
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod test_parse_duration_seconds {
+    use datetimescan::relative_datetime::parse_duration_seconds;
+
+    #[test]
+    fn test_units() {
+        assert_eq!(parse_duration_seconds("45"), Some(45));
+        assert_eq!(parse_duration_seconds("45s"), Some(45));
+        assert_eq!(parse_duration_seconds("5m"), Some(300));
+        assert_eq!(parse_duration_seconds("2h"), Some(7200));
+        assert_eq!(parse_duration_seconds("7d"), Some(604800));
+        assert_eq!(parse_duration_seconds("2w"), Some(1209600));
+        assert_eq!(parse_duration_seconds("1D"), Some(86400));
+    }
+
+    #[test]
+    fn test_compound() {
+        assert_eq!(parse_duration_seconds("1h30m"), Some(5400));
+        assert_eq!(parse_duration_seconds("1d12h"), Some(129600));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(parse_duration_seconds(""), None);
+        assert_eq!(parse_duration_seconds("d"), None);
+        assert_eq!(parse_duration_seconds("7x"), None);
+        assert_eq!(parse_duration_seconds("1h30"), None);
+        assert_eq!(parse_duration_seconds("-7d"), None);
+    }
+}
+
+#[cfg(test)]
+mod test_parse_relative_bounds {
+    use datetimescan::clock::FixedClock;
+    use datetimescan::relative_datetime::parse_relative_bounds;
+    use datetimescan::timezone::TimeZoneSpec;
+    use chrono::DateTime;
+
+    fn check(expr: &str, now: &str, first: &str, last: &str) {
+        let now = DateTime::parse_from_rfc3339(now).unwrap();
+        let clock = FixedClock::new(now);
+        let result = parse_relative_bounds(expr, &clock, &TimeZoneSpec::Fixed(*now.offset()));
+        let expected = (DateTime::parse_from_rfc3339(first).unwrap(), DateTime::parse_from_rfc3339(last).unwrap());
+        assert_eq!(result, Some(expected), "expr=({})", expr);
+    }
+
+    #[test]
+    fn test_instants() {
+        let now = "2023-05-24T12:00:00+10:00";
+        check("now", now, now, now);
+        check("-7d", now, "2023-05-17T12:00:00+10:00", "2023-05-17T12:00:00+10:00");
+        check("-2w", now, "2023-05-10T12:00:00+10:00", "2023-05-10T12:00:00+10:00");
+        check("-90m", now, "2023-05-24T10:30:00+10:00", "2023-05-24T10:30:00+10:00");
+        check("+1d", now, "2023-05-25T12:00:00+10:00", "2023-05-25T12:00:00+10:00");
+    }
+
+    #[test]
+    fn test_days() {
+        let now = "2023-05-24T12:00:00+10:00";
+        check("today", now, "2023-05-24T00:00:00+10:00", "2023-05-24T23:59:59+10:00");
+        check("Yesterday", now, "2023-05-23T00:00:00+10:00", "2023-05-23T23:59:59+10:00");
+        check("yesterday", "2023-01-01T08:00:00+00:00", "2022-12-31T00:00:00+00:00", "2022-12-31T23:59:59+00:00");
+    }
+
+    #[test]
+    fn test_weeks() {
+        //  2023-05-24 is a Wednesday
+        let now = "2023-05-24T12:00:00+10:00";
+        check("this week", now, "2023-05-22T00:00:00+10:00", "2023-05-28T23:59:59+10:00");
+        check("this_week", now, "2023-05-22T00:00:00+10:00", "2023-05-28T23:59:59+10:00");
+        check("last-week", now, "2023-05-15T00:00:00+10:00", "2023-05-21T23:59:59+10:00");
+        check("this week", "2023-05-22T00:00:00+10:00", "2023-05-22T00:00:00+10:00", "2023-05-28T23:59:59+10:00");
+    }
+
+    #[test]
+    fn test_months_and_years() {
+        let now = "2023-01-15T12:00:00+10:00";
+        check("this month", now, "2023-01-01T00:00:00+10:00", "2023-01-31T23:59:59+10:00");
+        check("last month", now, "2022-12-01T00:00:00+10:00", "2022-12-31T23:59:59+10:00");
+        check("this year", now, "2023-01-01T00:00:00+10:00", "2023-12-31T23:59:59+10:00");
+        check("last year", now, "2022-01-01T00:00:00+10:00", "2022-12-31T23:59:59+10:00");
+        check("ytd", now, "2023-01-01T00:00:00+10:00", now);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let clock = FixedClock::new(DateTime::parse_from_rfc3339("2023-05-24T12:00:00+10:00").unwrap());
+        let tz = TimeZoneSpec::parse("+10:00").unwrap();
+        assert_eq!(parse_relative_bounds("next week", &clock, &tz), None);
+        assert_eq!(parse_relative_bounds("-7x", &clock, &tz), None);
+        assert_eq!(parse_relative_bounds("", &clock, &tz), None);
+        assert_eq!(parse_relative_bounds("-99999999999d", &clock, &tz), None);
+        assert_eq!(parse_relative_bounds("+99999999999999999", &clock, &tz), None);
+    }
+}
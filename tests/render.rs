@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod test_render {
+    use datetimescan::period::Period;
+    use datetimescan::printer::{CsvPrinter, JsonPrinter, Printer};
+    use datetimescan::render::{CountsPerPeriod, Field, Records, Render, SplitsPerPeriod, SumsPerPeriod};
+    use std::collections::HashMap;
+
+    fn counts(period: Period) -> CountsPerPeriod {
+        let counts_per_interval = HashMap::from([("2023-05-15".to_string(), 2), ("2023-05-14".to_string(), 3)]);
+        CountsPerPeriod::new(&counts_per_interval, &period)
+    }
+
+    fn render_to_string<F>(render: F) -> String
+    where
+        F: FnOnce(Printer) -> Box<dyn Render + '_>,
+    {
+        let mut buffer = Vec::<u8>::new();
+        let mut renderer = render(Printer::new(Some(&mut buffer)));
+        renderer.render_counts(&counts(Period::Day));
+        drop(renderer);
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_counts_sorted_by_period() {
+        let result = counts(Period::Day);
+        assert_eq!(result.counts, vec![("2023-05-14".to_string(), 3), ("2023-05-15".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_splits_records_empty_interval() {
+        let splits_per_interval = HashMap::from([("2023-05-14".to_string(), vec![60, 120]), ("2023-05-15".to_string(), vec![])]);
+        let result = SplitsPerPeriod::new(&splits_per_interval, &Period::Day, "m");
+        assert_eq!(result.fields(), vec!["interval", "duration", "unit"]);
+        let records = result.records();
+        let durations: Vec<&str> = records.iter().map(|x| x[1].text()).collect();
+        assert_eq!(durations, vec!["1.00", "2.00", "0.00"]);
+    }
+
+    #[test]
+    fn test_sums_records_hms() {
+        let result = SumsPerPeriod::new(&HashMap::from([("all".to_string(), 3725)]), &Period::All, "hms");
+        assert_eq!(result.records(), vec![vec![Field::Str("all".to_string()), Field::Str("1h02m05s".to_string()), Field::Str("hms".to_string())]]);
+    }
+
+    #[test]
+    fn test_printer_counts() {
+        let result = render_to_string(|printer| Box::new(printer));
+        assert_eq!(result, "2023-05-14: 3\n2023-05-15: 2\n");
+    }
+
+    #[test]
+    fn test_printer_counts_per_all() {
+        let mut buffer = Vec::<u8>::new();
+        let mut printer = Printer::new(Some(&mut buffer));
+        printer.render_counts(&CountsPerPeriod::new(&HashMap::from([("all".to_string(), 5)]), &Period::All));
+        assert_eq!(String::from_utf8(buffer).unwrap(), "5\n");
+    }
+
+    #[test]
+    fn test_json_printer_counts() {
+        let result = render_to_string(|printer| Box::new(JsonPrinter::new(printer, false)));
+        assert_eq!(result, "[\n{\"interval\":\"2023-05-14\",\"count\":3},\n{\"interval\":\"2023-05-15\",\"count\":2}\n]\n");
+        let result = render_to_string(|printer| Box::new(JsonPrinter::new(printer, true)));
+        assert_eq!(result, "{\"interval\":\"2023-05-14\",\"count\":3}\n{\"interval\":\"2023-05-15\",\"count\":2}\n");
+    }
+
+    #[test]
+    fn test_csv_printer_counts() {
+        let result = render_to_string(|printer| Box::new(CsvPrinter::new(printer, ',')));
+        assert_eq!(result, "interval,count\n2023-05-14,3\n2023-05-15,2\n");
+        let result = render_to_string(|printer| Box::new(CsvPrinter::new(printer, '\t')));
+        assert_eq!(result, "interval\tcount\n2023-05-14\t3\n2023-05-15\t2\n");
+    }
+}
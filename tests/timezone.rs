@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod test_timezone_spec {
+    use datetimescan::timezone::TimeZoneSpec;
+    use chrono::{DateTime, FixedOffset, NaiveDate};
+
+    #[test]
+    fn test_parse_fixed() {
+        let checks = vec![
+            ("UTC", 0),
+            ("Z", 0),
+            ("+1000", 36000),
+            ("+10:00", 36000),
+            ("+10", 36000),
+            ("-0530", -19800),
+            ("-05:30", -19800),
+        ];
+        for (tz_str, seconds) in checks {
+            assert_eq!(TimeZoneSpec::parse(tz_str), Some(TimeZoneSpec::Fixed(FixedOffset::east_opt(seconds).unwrap())), "tz_str=({})", tz_str);
+        }
+    }
+
+    #[test]
+    fn test_parse_named() {
+        assert_eq!(TimeZoneSpec::parse("Australia/Melbourne"), Some(TimeZoneSpec::Named(chrono_tz::Australia::Melbourne)));
+        assert_eq!(TimeZoneSpec::parse("America/New_York"), Some(TimeZoneSpec::Named(chrono_tz::America::New_York)));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(TimeZoneSpec::parse("+1"), None);
+        assert_eq!(TimeZoneSpec::parse("+10:75"), None);
+        assert_eq!(TimeZoneSpec::parse("Nowhere/Special"), None);
+        assert_eq!(TimeZoneSpec::parse(""), None);
+    }
+
+    #[test]
+    fn test_to_local_named_dst() {
+        let tz = TimeZoneSpec::parse("Australia/Melbourne").unwrap();
+        let winter = DateTime::parse_from_rfc3339("2023-06-01T00:00:00+00:00").unwrap();
+        let summer = DateTime::parse_from_rfc3339("2023-12-01T00:00:00+00:00").unwrap();
+        assert_eq!(tz.to_local(&winter), NaiveDate::from_ymd_opt(2023, 6, 1).unwrap().and_hms_opt(10, 0, 0).unwrap());
+        assert_eq!(tz.to_local(&summer), NaiveDate::from_ymd_opt(2023, 12, 1).unwrap().and_hms_opt(11, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_from_local() {
+        let tz = TimeZoneSpec::parse("Australia/Melbourne").unwrap();
+        let local = NaiveDate::from_ymd_opt(2023, 12, 1).unwrap().and_hms_opt(11, 0, 0).unwrap();
+        assert_eq!(tz.from_local(&local), DateTime::parse_from_rfc3339("2023-12-01T00:00:00+00:00").unwrap());
+        //  2023-10-01T02:30 does not exist in Melbourne (clocks go forward 02:00 -> 03:00)
+        let local = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap().and_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(tz.from_local(&local), DateTime::parse_from_rfc3339("2023-10-01T03:00:00+11:00").unwrap());
+        let tz = TimeZoneSpec::parse("+1000").unwrap();
+        assert_eq!(tz.from_local(&local), DateTime::parse_from_rfc3339("2023-10-01T02:30:00+10:00").unwrap());
+    }
+}
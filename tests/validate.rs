@@ -0,0 +1,111 @@
+
+#[cfg(test)]
+mod test_validate {
+    use chrono::DateTime;
+    use datetimescan::clock::FixedClock;
+    use datetimescan::timezone::TimeZoneSpec;
+    use datetimescan::validate::{validate, parse_located, check_future, check_unsorted, check_duplicates, check_gaps, check_mixed_offsets, Check, LocatedDatetime};
+
+    fn clock() -> FixedClock {
+        FixedClock::new(DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap())
+    }
+
+    fn tz() -> TimeZoneSpec {
+        TimeZoneSpec::parse("+10:00").unwrap()
+    }
+
+    fn locations(datetimes_strs: &[&str]) -> Vec<(String, usize, usize)> {
+        datetimes_strs.iter().enumerate().map(|(i, x)| (x.to_string(), i+1, 2)).collect()
+    }
+
+    fn located(datetimes_strs: &[&str]) -> Vec<LocatedDatetime> {
+        parse_located(&locations(datetimes_strs), &tz()).0
+    }
+
+    fn lines(issues: &[datetimescan::validate::Issue]) -> Vec<usize> {
+        issues.iter().map(|x| x.line).collect()
+    }
+
+    #[test]
+    fn test_check_from_str() {
+        for check in Check::ALL {
+            assert_eq!(check.name().parse::<Check>(), Ok(check));
+        }
+        assert_eq!("MIXED_OFFSET".parse::<Check>(), Ok(Check::MixedOffset));
+        assert!("offset".parse::<Check>().is_err());
+    }
+
+    #[test]
+    fn test_parse_located() {
+        let (located, issues) = parse_located(&locations(&["2023-05-11T00:00:00PST", "2023-05-11 00:00:00", "2023-02-30T00:00:00Z"]), &tz());
+        assert_eq!(located.len(), 1);
+        assert_eq!(located[0].datetime, DateTime::parse_from_rfc3339("2023-05-11T00:00:00+10:00").unwrap());
+        assert_eq!(lines(&issues), vec![1, 3]);
+        assert_eq!(issues[0].check, Check::Unparsable);
+        assert_eq!(issues[0].column, 3);
+        assert_eq!(issues[0].to_string(), "1:3: unparsable: 2023-05-11T00:00:00PST (failed to parse)");
+    }
+
+    #[test]
+    fn test_check_future() {
+        let located = located(&["2023-05-27T12:00:00+10:00", "2023-05-27T02:00:01+00:00", "2023-05-27T11:00:00+10:00"]);
+        let issues = check_future(&located, &DateTime::parse_from_rfc3339("2023-05-27T12:00:00+10:00").unwrap());
+        assert_eq!(lines(&issues), vec![2]);
+    }
+
+    #[test]
+    fn test_check_unsorted() {
+        let located = located(&["2023-05-11T00:00:00+00:00", "2023-05-11T00:05:00+00:00", "2023-05-11T00:01:00+00:00", "2023-05-11T00:02:00+00:00", "2023-05-11T00:02:00+00:00"]);
+        let issues = check_unsorted(&located);
+        assert_eq!(lines(&issues), vec![3]);
+        assert_eq!(issues[0].detail, "before 2023-05-11T00:05:00+00:00 on line 2");
+    }
+
+    #[test]
+    fn test_check_duplicates() {
+        let located = located(&["2023-05-11T00:00:00+00:00", "2023-05-11T10:00:00+10:00", "2023-05-11T00:01:00+00:00", "2023-05-11T00:00:00+00:00"]);
+        let issues = check_duplicates(&located);
+        assert_eq!(lines(&issues), vec![2, 4]);
+        assert_eq!(issues[1].detail, "same instant as line 1");
+    }
+
+    #[test]
+    fn test_check_gaps() {
+        let located = located(&["2023-05-11T00:00:00+00:00", "2023-05-11T00:10:00+00:00", "2023-05-11T00:05:00+00:00", "2023-05-11T00:15:01+00:00"]);
+        assert_eq!(lines(&check_gaps(&located, 600)), vec![4]);
+        assert_eq!(lines(&check_gaps(&located, 300)), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_check_mixed_offsets() {
+        let located = located(&["2023-05-11T00:00:00+10:00", "2023-05-11T00:01:00+10:00", "2023-05-11T00:02:00+11:00", "2023-05-11T00:03:00+11:00"]);
+        let issues = check_mixed_offsets(&located);
+        assert_eq!(lines(&issues), vec![3]);
+        assert_eq!(issues[0].detail, "offset +11:00 differs from +10:00 on line 2");
+    }
+
+    #[test]
+    fn test_validate() {
+        let datetimes_and_locations = locations(&[
+            "2023-05-11T00:00:00+10:00",
+            "2023-05-11T00:00:00+10:00",
+            "2023-05-11T00:00:00+1O:00",
+            "2023-06-11T00:00:00+11:00",
+            "2023-05-12T00:00:00+10:00",
+        ]);
+        let issues = validate(&datetimes_and_locations, &Check::ALL, &tz(), &clock(), 86400);
+        let found: Vec<(usize, Check)> = issues.iter().map(|x| (x.line, x.check)).collect();
+        assert_eq!(found, vec![
+            (2, Check::Duplicate),
+            (3, Check::Unparsable),
+            (4, Check::Future),
+            (4, Check::Gap),
+            (4, Check::MixedOffset),
+            (5, Check::Unsorted),
+            (5, Check::MixedOffset),
+        ]);
+        let issues = validate(&datetimes_and_locations, &[Check::Unsorted], &tz(), &clock(), 86400);
+        assert_eq!(lines(&issues), vec![5]);
+        assert!(validate(&[], &Check::ALL, &tz(), &clock(), 0).is_empty());
+    }
+}